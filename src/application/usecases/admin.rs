@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use anyhow::Result;
 
-use crate::domain::{repositories::users::UsersRepository, value_objects::roles::Roles};
+use crate::domain::{
+    repositories::users::UsersRepository,
+    value_objects::roles::{AssignRoleModel, Role, RoleGrantModel},
+};
 
 pub struct AdminUseCase<T>
 where
@@ -21,30 +24,62 @@ where
         }
     }
 
-    pub async fn assign_doctor_role(
+    pub async fn remove_user(
         &self,
-        executer_user_id: i32,
-        target_user_id: i32
+        _executer_user_id: i32,
+        user_id: i32
     ) -> Result<()> {
-        let role = Roles::Doctor;
-        self.users_repository.add_role_to_user_by_id(role, target_user_id).await
+        self.users_repository.remove_by_id(user_id).await
     }
 
-    pub async fn remove_doctor_role(
+    /// Grants the role at the hospital, registering the user there first when needed, e.g. a
+    /// nurse who also works at a second hospital. `Doctor` is granted only by approving a
+    /// doctor application. Returns the user's grants at the hospital after this one, or `None`
+    /// when the user does not exist.
+    pub async fn assign_role(
         &self,
+        hospital_id: i32,
         executer_user_id: i32,
-        target_user_id: i32
-    ) -> Result<()> {
-        let role = Roles::Doctor;
-        self.users_repository.remove_role_from_user_by_id(role, target_user_id).await
+        user_id: i32,
+        assign_role_model: AssignRoleModel
+    ) -> Result<Option<Vec<RoleGrantModel>>> {
+        assign_role_model.validate_assignment()?;
+
+        // find_by_id คืน error เมื่อไม่พบ user
+        if self.users_repository.find_by_id(user_id).await.is_err() {
+            return Ok(None);
+        }
+
+        self.users_repository
+            .grant_role(
+                hospital_id,
+                user_id,
+                assign_role_model.role,
+                Some(executer_user_id),
+                assign_role_model.valid_from,
+                assign_role_model.valid_until,
+            )
+            .await?;
+
+        Ok(Some(self.users_repository.find_role_grants(hospital_id, user_id).await?))
     }
 
-    pub async fn remove_user(
+    /// Grants at the hospital. Returns `None` when the user does not exist.
+    pub async fn find_role_grants(
         &self,
-        executer_user_id: i32,
-        user_id: i32
-    ) -> Result<()> {
-        self.users_repository.remove_by_id(user_id).await
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Option<Vec<RoleGrantModel>>> {
+        if self.users_repository.find_by_id(user_id).await.is_err() {
+            return Ok(None);
+        }
+
+        Ok(Some(self.users_repository.find_role_grants(hospital_id, user_id).await?))
+    }
+
+    /// Returns `false` when the user did not hold the role at the hospital.
+    pub async fn revoke_role(&self, hospital_id: i32, user_id: i32, role: Role) -> Result<bool> {
+        self.users_repository.revoke_role(hospital_id, user_id, role).await
     }
     
 }
@@ -0,0 +1,194 @@
+use std::{net::IpAddr, sync::Arc};
+
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::{
+    domain::{
+        entities::api_keys::InsertApiKeyEntity,
+        repositories::api_keys::ApiKeysRepository,
+        value_objects::{
+            api_keys_model::{ApiKeyModel, CreateApiKeyModel, CreateApiKeyResponseModel},
+            oauth_model::SERVICE_SCOPES,
+            principal::Principal,
+        },
+    },
+    infrastructure::opaque_token,
+};
+
+/// Every key starts with this so it can be recognised in logs and by secret scanners.
+const API_KEY_PREFIX: &str = "mbk";
+
+pub struct ApiKeysUseCase<K>
+where
+    K: ApiKeysRepository + Send + Sync,
+{
+    api_keys_repository: Arc<K>,
+}
+
+impl<K> ApiKeysUseCase<K>
+where
+    K: ApiKeysRepository + Send + Sync,
+{
+    pub fn new(api_keys_repository: Arc<K>) -> Self {
+        Self {
+            api_keys_repository,
+        }
+    }
+
+    /// The key only works at the hospital it is created at.
+    pub async fn create(
+        &self,
+        hospital_id: i32,
+        created_by: i32,
+        create_api_key_model: CreateApiKeyModel,
+    ) -> Result<CreateApiKeyResponseModel> {
+        if create_api_key_model.scopes.is_empty() {
+            return Err(anyhow::anyhow!("At least one scope is required"));
+        }
+
+        if let Some(scope) = create_api_key_model
+            .scopes
+            .iter()
+            .find(|scope| !SERVICE_SCOPES.contains(&scope.as_str()))
+        {
+            return Err(anyhow::anyhow!("Unsupported scope: {}", scope));
+        }
+
+        if let Some(entry) = create_api_key_model
+            .allowed_ips
+            .iter()
+            .find(|entry| parse_ip_entry(entry).is_none())
+        {
+            return Err(anyhow::anyhow!("Invalid IP address or range: {}", entry));
+        }
+
+        let now = Utc::now().naive_utc();
+        if create_api_key_model
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+        {
+            return Err(anyhow::anyhow!("Expiry must be in the future"));
+        }
+
+        let prefix = opaque_token::generate(8);
+        let api_key = format!(
+            "{}_{}_{}",
+            API_KEY_PREFIX,
+            prefix,
+            opaque_token::generate(40)
+        );
+
+        let id = self
+            .api_keys_repository
+            .create(InsertApiKeyEntity {
+                prefix: prefix.clone(),
+                key_hash: opaque_token::digest(&api_key),
+                name: create_api_key_model.name,
+                scopes: create_api_key_model.scopes,
+                allowed_ips: create_api_key_model.allowed_ips,
+                created_by: Some(created_by),
+                expires_at: create_api_key_model.expires_at,
+                created_at: now,
+                hospital_id,
+            })
+            .await?;
+
+        Ok(CreateApiKeyResponseModel {
+            id,
+            prefix,
+            api_key,
+        })
+    }
+
+    pub async fn list(&self, hospital_id: i32) -> Result<Vec<ApiKeyModel>> {
+        let api_keys = self.api_keys_repository.list(hospital_id).await?;
+
+        Ok(api_keys.into_iter().map(ApiKeyModel::from).collect())
+    }
+
+    pub async fn revoke(&self, hospital_id: i32, api_key_id: i32) -> Result<()> {
+        self.api_keys_repository
+            .revoke_by_id(hospital_id, api_key_id)
+            .await
+    }
+
+    pub async fn authenticate(
+        &self,
+        api_key: String,
+        client_ip: Option<IpAddr>,
+    ) -> Result<Principal> {
+        let (prefix, _) = api_key
+            .strip_prefix(API_KEY_PREFIX)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .and_then(|rest| rest.split_once('_'))
+            .ok_or_else(|| anyhow::anyhow!("Malformed API key"))?;
+
+        let stored_key = self
+            .api_keys_repository
+            .find_active_by_prefix(prefix.to_string())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Unknown, revoked or expired API key"))?;
+
+        if opaque_token::digest(&api_key) != stored_key.key_hash {
+            return Err(anyhow::anyhow!("Invalid API key"));
+        }
+
+        if !stored_key.allowed_ips.is_empty() {
+            let client_ip = client_ip.ok_or_else(|| anyhow::anyhow!("Unknown client address"))?;
+
+            if !stored_key
+                .allowed_ips
+                .iter()
+                .any(|entry| ip_entry_contains(entry, client_ip))
+            {
+                return Err(anyhow::anyhow!("Client address is not allowed"));
+            }
+        }
+
+        self.api_keys_repository
+            .touch_last_used_by_id(stored_key.id)
+            .await?;
+
+        Ok(Principal::ApiKey {
+            api_key_id: stored_key.id,
+            hospital_id: stored_key.hospital_id,
+            scopes: stored_key.scopes,
+        })
+    }
+}
+
+/// Parses `10.0.0.1` or `10.0.0.0/24` (and the IPv6 equivalents) into a network and prefix length.
+fn parse_ip_entry(entry: &str) -> Option<(IpAddr, u32)> {
+    match entry.split_once('/') {
+        None => {
+            let ip = entry.parse::<IpAddr>().ok()?;
+            let prefix_len = if ip.is_ipv4() { 32 } else { 128 };
+            Some((ip, prefix_len))
+        }
+        Some((network, prefix_len)) => {
+            let network = network.parse::<IpAddr>().ok()?;
+            let prefix_len = prefix_len.parse::<u32>().ok()?;
+            let max_len = if network.is_ipv4() { 32 } else { 128 };
+            (prefix_len <= max_len).then_some((network, prefix_len))
+        }
+    }
+}
+
+fn ip_entry_contains(entry: &str, ip: IpAddr) -> bool {
+    let Some((network, prefix_len)) = parse_ip_entry(entry) else {
+        return false;
+    };
+
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
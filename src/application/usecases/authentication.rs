@@ -4,49 +4,79 @@ use anyhow::Result;
 use chrono::{Duration, Utc};
 
 use crate::{
-    config::config_loader::{get_doctors_secret_env, get_patients_secret_env},
+    config::config_loader::{
+        get_doctors_secret_env, get_patients_secret_env, get_staff_secret_env,
+    },
     domain::{
-        entities::users::UserEntity, repositories::users::UsersRepository,
-        value_objects::roles::Roles,
+        entities::{sessions::InsertSessionEntity, users::UserEntity},
+        repositories::{
+            patient_profiles::PatientProfilesRepository, sessions::SessionsRepository,
+            users::UsersRepository,
+        },
+        value_objects::{
+            patient_profiles_model::PatientProfileModel,
+            roles::Role,
+            validation::{
+                normalize_citizen_id, normalize_hospital_number, validate_hospital_number,
+            },
+        },
     },
     infrastructure::{
         argon2_hashing,
         jwt_authentication::{
             self,
             authentication_model::LoginModel,
-            jwt_model::{self, Claims, Passport},
+            jwt_model::{Claims, Passport},
         },
-        postgres::schema::users,
+        opaque_token,
     },
 };
 
-pub struct AuthenticationUseCase<T>
+const REFRESH_TOKEN_TTL_DAYS: i64 = 7;
+
+pub struct AuthenticationUseCase<T, S, P>
 where
     T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
 {
     users_repository: Arc<T>,
+    sessions_repository: Arc<S>,
+    patient_profiles_repository: Arc<P>,
 }
 
-impl<T> AuthenticationUseCase<T>
+impl<T, S, P> AuthenticationUseCase<T, S, P>
 where
     T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
 {
-    pub fn new(users_repository: Arc<T>) -> Self {
-        Self { users_repository }
+    pub fn new(
+        users_repository: Arc<T>,
+        sessions_repository: Arc<S>,
+        patient_profiles_repository: Arc<P>,
+    ) -> Self {
+        Self {
+            users_repository,
+            sessions_repository,
+            patient_profiles_repository,
+        }
     }
 
-    pub async fn patients_login(&self, login_model: LoginModel) -> Result<Passport> {
+    pub async fn patients_login(
+        &self,
+        hospital_id: i32,
+        login_model: LoginModel,
+    ) -> Result<Passport> {
         let secret_env = get_patients_secret_env()?;
-        let patient = self
+        let patient = self.find_login_user(hospital_id, &login_model).await?;
+
+        let roles = self
             .users_repository
-            .find_by_id(login_model.hospital_number)
+            .find_roles(hospital_id, patient.id)
             .await?;
 
-        let patient_role_str = Roles::Patient.to_string();
-
-        let is_patient = patient.role.iter().any(|r| r == &patient_role_str);
-
-        if !is_patient {
+        if !roles.contains(&Role::Patient) {
             return Err(anyhow::anyhow!("User is not a patient"));
         }
 
@@ -57,18 +87,26 @@ where
             return Err(anyhow::anyhow!("Invalid password"));
         };
 
+        let session_id = self.create_session(patient.id).await?;
+
         let access_token_claims = Claims {
             sub: patient.id.to_string(),
-            role: jwt_model::Roles::Patient,
+            role: Role::Patient,
             exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            sid: Some(session_id.clone()),
+            hid: Some(hospital_id),
+            act: None,
         };
 
         let refresh_token_claims = Claims {
             sub: patient.id.to_string(),
-            role: jwt_model::Roles::Patient,
-            exp: (Utc::now() + Duration::days(7)).timestamp() as usize,
+            role: Role::Patient,
+            exp: (Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            sid: Some(session_id),
+            hid: Some(hospital_id),
+            act: None,
         };
 
         let access_token =
@@ -82,24 +120,40 @@ where
         })
     }
 
-    pub async fn patients_refresh_token(&self, refresh_token: String) -> Result<Passport> {
+    /// `hospital_id` is used for tokens issued before tenancy, which carry no `hid`.
+    pub async fn patients_refresh_token(
+        &self,
+        hospital_id: i32,
+        refresh_token: String,
+    ) -> Result<Passport> {
         let secret_env = get_patients_secret_env()?;
 
         let claims =
             jwt_authentication::verify_token(secret_env.refresh_secret.clone(), refresh_token)?;
 
+        let hospital_id = claims.hid.unwrap_or(hospital_id);
+
+        self.ensure_session_active(&claims.sid).await?;
+        self.ensure_role_held(hospital_id, &claims).await?;
+
         let access_token_claims = Claims {
             sub: claims.sub.clone(),
-            role: jwt_model::Roles::Patient,
+            role: Role::Patient,
             exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            sid: claims.sid.clone(),
+            hid: Some(hospital_id),
+            act: None,
         };
 
         let refresh_token_claims = Claims {
             sub: claims.sub,
-            role: jwt_model::Roles::Patient,
+            role: Role::Patient,
             exp: claims.exp,
             iat: Utc::now().timestamp() as usize,
+            sid: claims.sid,
+            hid: Some(hospital_id),
+            act: None,
         };
 
         let access_token =
@@ -113,18 +167,21 @@ where
         })
     }
 
-    pub async fn doctors_login(&self, login_model: LoginModel) -> Result<Passport> {
+    pub async fn doctors_login(
+        &self,
+        hospital_id: i32,
+        login_model: LoginModel,
+    ) -> Result<Passport> {
         let secret_env = get_doctors_secret_env()?;
 
-        let doctor = self
+        let doctor = self.find_login_user(hospital_id, &login_model).await?;
+
+        let roles = self
             .users_repository
-            .find_by_id(login_model.hospital_number)
+            .find_roles(hospital_id, doctor.id)
             .await?;
 
-        let doctor_role_str = Roles::Doctor.to_string();
-        let is_doctor = doctor.role.iter().any(|r| r == &doctor_role_str);
-
-        if !is_doctor {
+        if !roles.contains(&Role::Doctor) {
             return Err(anyhow::anyhow!("User is not a doctor"));
         }
 
@@ -135,18 +192,26 @@ where
             return Err(anyhow::anyhow!("Invalid password"));
         };
 
+        let session_id = self.create_session(doctor.id).await?;
+
         let access_token_claims = Claims {
             sub: doctor.id.to_string(),
-            role: jwt_model::Roles::Doctor,
+            role: Role::Doctor,
             exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            sid: Some(session_id.clone()),
+            hid: Some(hospital_id),
+            act: None,
         };
 
         let refresh_token_claims = Claims {
             sub: doctor.id.to_string(),
-            role: jwt_model::Roles::Doctor,
-            exp: (Utc::now() + Duration::days(7)).timestamp() as usize,
+            role: Role::Doctor,
+            exp: (Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            sid: Some(session_id),
+            hid: Some(hospital_id),
+            act: None,
         };
 
         let access_token =
@@ -160,24 +225,155 @@ where
         })
     }
 
-    pub async fn doctors_refresh_token(&self, refresh_token: String) -> Result<Passport> {
+    /// `hospital_id` is used for tokens issued before tenancy, which carry no `hid`.
+    pub async fn doctors_refresh_token(
+        &self,
+        hospital_id: i32,
+        refresh_token: String,
+    ) -> Result<Passport> {
         let secret_env = get_doctors_secret_env()?;
 
         let claims =
             jwt_authentication::verify_token(secret_env.refresh_secret.clone(), refresh_token)?;
 
+        let hospital_id = claims.hid.unwrap_or(hospital_id);
+
+        self.ensure_session_active(&claims.sid).await?;
+        self.ensure_role_held(hospital_id, &claims).await?;
+
+        let access_token_claims = Claims {
+            sub: claims.sub.clone(),
+            role: Role::Doctor,
+            exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
+            iat: Utc::now().timestamp() as usize,
+            sid: claims.sid.clone(),
+            hid: Some(hospital_id),
+            act: None,
+        };
+
+        let refresh_token_claims = Claims {
+            sub: claims.sub,
+            role: Role::Doctor,
+            exp: claims.exp,
+            iat: Utc::now().timestamp() as usize,
+            sid: claims.sid,
+            hid: Some(hospital_id),
+            act: None,
+        };
+
+        let access_token =
+            jwt_authentication::generate_token(secret_env.secret, &access_token_claims)?;
+        let refresh_token =
+            jwt_authentication::generate_token(secret_env.refresh_secret, &refresh_token_claims)?;
+
+        Ok(Passport {
+            access_token,
+            refresh_token,
+        })
+    }
+
+    /// Logs in a nurse, pharmacist or receptionist. The token carries `role`, so someone holding
+    /// several staff roles signs in once per role.
+    pub async fn staff_login(
+        &self,
+        hospital_id: i32,
+        login_model: LoginModel,
+        role: Role,
+    ) -> Result<Passport> {
+        if !role.is_clinical_staff() {
+            return Err(anyhow::anyhow!("{} is not a staff role", role));
+        }
+
+        let secret_env = get_staff_secret_env()?;
+
+        let staff = self.find_login_user(hospital_id, &login_model).await?;
+
+        let roles = self
+            .users_repository
+            .find_roles(hospital_id, staff.id)
+            .await?;
+
+        if !roles.contains(&role) {
+            return Err(anyhow::anyhow!(
+                "User is not a {}",
+                role.to_string().to_lowercase()
+            ));
+        }
+
+        let original_password = staff.password;
+        let login_password = login_model.password;
+
+        if !argon2_hashing::verify(login_password, original_password)? {
+            return Err(anyhow::anyhow!("Invalid password"));
+        };
+
+        let session_id = self.create_session(staff.id).await?;
+
+        let access_token_claims = Claims {
+            sub: staff.id.to_string(),
+            role,
+            exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
+            iat: Utc::now().timestamp() as usize,
+            sid: Some(session_id.clone()),
+            hid: Some(hospital_id),
+            act: None,
+        };
+
+        let refresh_token_claims = Claims {
+            sub: staff.id.to_string(),
+            role,
+            exp: (Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS)).timestamp() as usize,
+            iat: Utc::now().timestamp() as usize,
+            sid: Some(session_id),
+            hid: Some(hospital_id),
+            act: None,
+        };
+
+        let access_token =
+            jwt_authentication::generate_token(secret_env.secret, &access_token_claims)?;
+        let refresh_token =
+            jwt_authentication::generate_token(secret_env.refresh_secret, &refresh_token_claims)?;
+
+        Ok(Passport {
+            access_token,
+            refresh_token,
+        })
+    }
+
+    /// Fails once the role in the token has been revoked or its grant has ended.
+    pub async fn staff_refresh_token(
+        &self,
+        hospital_id: i32,
+        refresh_token: String,
+    ) -> Result<Passport> {
+        let secret_env = get_staff_secret_env()?;
+
+        let claims =
+            jwt_authentication::verify_token(secret_env.refresh_secret.clone(), refresh_token)?;
+
+        let hospital_id = claims.hid.unwrap_or(hospital_id);
+
+        self.ensure_session_active(&claims.sid).await?;
+        self.ensure_role_held(hospital_id, &claims).await?;
+
         let access_token_claims = Claims {
             sub: claims.sub.clone(),
-            role: jwt_model::Roles::Doctor,
+            role: claims.role,
             exp: (Utc::now() + Duration::days(1)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            sid: claims.sid.clone(),
+            hid: Some(hospital_id),
+            act: None,
         };
 
         let refresh_token_claims = Claims {
             sub: claims.sub,
-            role: jwt_model::Roles::Doctor,
+            role: claims.role,
             exp: claims.exp,
             iat: Utc::now().timestamp() as usize,
+            sid: claims.sid,
+            hid: Some(hospital_id),
+            act: None,
         };
 
         let access_token =
@@ -191,7 +387,216 @@ where
         })
     }
 
-    pub async fn get_me(&self, hospital_id: i32) -> Result<UserEntity> {
-        Ok(self.users_repository.find_by_id(hospital_id).await?)
+    /// Hospital numbers are looked up at the hospital; citizen IDs are global, and the role
+    /// check that follows keeps out users not registered at the hospital. Deleted and erased
+    /// users are not found.
+    async fn find_login_user(
+        &self,
+        hospital_id: i32,
+        login_model: &LoginModel,
+    ) -> Result<UserEntity> {
+        let user = match (&login_model.hospital_number, &login_model.citizen_id) {
+            (Some(hospital_number), _) => {
+                validate_hospital_number(hospital_number)
+                    .map_err(|_| anyhow::anyhow!("Invalid hospital number"))?;
+
+                self.users_repository
+                    .find_by_hospital_number(
+                        hospital_id,
+                        normalize_hospital_number(hospital_number),
+                    )
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("User not found"))
+            }
+            (None, Some(citizen_id)) => self
+                .users_repository
+                .find_by_citizen_id(normalize_citizen_id(citizen_id))
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("User not found")),
+            (None, None) => Err(anyhow::anyhow!(
+                "Either hospital_number or citizen_id is required"
+            )),
+        }?;
+
+        // account ที่ลบแล้ว login ไม่ได้ และ account ที่ erase แล้วไม่มี argon2 hash ให้ตรวจ
+        if user.deleted_at.is_some() {
+            return Err(anyhow::anyhow!("User not found"));
+        }
+
+        Ok(user)
+    }
+
+    /// The user and their roles at the hospital plus, for patients, their profile (`None` until
+    /// they fill it in).
+    pub async fn get_me(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<(UserEntity, Vec<Role>, Option<PatientProfileModel>)> {
+        let user = self.users_repository.find_by_id(user_id).await?;
+        let roles = self
+            .users_repository
+            .find_roles(hospital_id, user.id)
+            .await?;
+
+        let patient_profile = if roles.contains(&Role::Patient) {
+            self.patient_profiles_repository
+                .find_by_user_id(user.id)
+                .await?
+                .map(PatientProfileModel::try_from)
+                .transpose()?
+        } else {
+            None
+        };
+
+        Ok((user, roles, patient_profile))
+    }
+
+    pub async fn logout(&self, refresh_token: String) -> Result<()> {
+        let patients_secret = get_patients_secret_env()?;
+        let doctors_secret = get_doctors_secret_env()?;
+        let staff_secret = get_staff_secret_env()?;
+
+        let claims =
+            jwt_authentication::verify_token(patients_secret.refresh_secret, refresh_token.clone())
+                .or_else(|_| {
+                    jwt_authentication::verify_token(
+                        doctors_secret.refresh_secret,
+                        refresh_token.clone(),
+                    )
+                })
+                .or_else(|_| {
+                    jwt_authentication::verify_token(staff_secret.refresh_secret, refresh_token)
+                })?;
+
+        if let Some(session_id) = claims.sid {
+            self.sessions_repository.revoke_by_id(session_id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_session(&self, user_id: i32) -> Result<String> {
+        let now = Utc::now();
+
+        self.sessions_repository
+            .create(InsertSessionEntity {
+                id: opaque_token::generate(32),
+                user_id,
+                client_id: None,
+                created_at: now.naive_utc(),
+                expires_at: (now + Duration::days(REFRESH_TOKEN_TTL_DAYS)).naive_utc(),
+            })
+            .await
+    }
+
+    /// Grants can be revoked or end while a refresh token is still valid, e.g. a locum's Doctor
+    /// role at the end of the contract.
+    async fn ensure_role_held(&self, hospital_id: i32, claims: &Claims) -> Result<()> {
+        let user_id = claims.sub.parse::<i32>()?;
+        let roles = self
+            .users_repository
+            .find_roles(hospital_id, user_id)
+            .await?;
+
+        if !roles.contains(&claims.role) {
+            return Err(anyhow::anyhow!("Role {} is no longer held", claims.role));
+        }
+
+        Ok(())
+    }
+
+    /// Tokens without a `sid` predate sessions and are rejected like revoked ones.
+    async fn ensure_session_active(&self, session_id: &Option<String>) -> Result<()> {
+        let Some(session_id) = session_id else {
+            return Err(anyhow::anyhow!("Session has been revoked"));
+        };
+
+        match self
+            .sessions_repository
+            .find_active_by_id(session_id.clone())
+            .await?
+        {
+            Some(_) => Ok(()),
+            None => Err(anyhow::anyhow!("Session has been revoked")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::sessions::SessionEntity,
+        repositories::{
+            patient_profiles::MockPatientProfilesRepository, sessions::MockSessionsRepository,
+            users::MockUsersRepository,
+        },
+    };
+
+    fn use_case(
+        sessions_repository: MockSessionsRepository,
+    ) -> AuthenticationUseCase<
+        MockUsersRepository,
+        MockSessionsRepository,
+        MockPatientProfilesRepository,
+    > {
+        AuthenticationUseCase::new(
+            Arc::new(MockUsersRepository::new()),
+            Arc::new(sessions_repository),
+            Arc::new(MockPatientProfilesRepository::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn session_without_sid_is_rejected() {
+        let mut sessions_repository = MockSessionsRepository::new();
+        sessions_repository.expect_find_active_by_id().never();
+
+        let result = use_case(sessions_repository)
+            .ensure_session_active(&None)
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn revoked_session_is_rejected() {
+        let mut sessions_repository = MockSessionsRepository::new();
+        sessions_repository
+            .expect_find_active_by_id()
+            .returning(|_| Box::pin(async { Ok(None) }));
+
+        let result = use_case(sessions_repository)
+            .ensure_session_active(&Some("revoked".to_string()))
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn active_session_is_accepted() {
+        let mut sessions_repository = MockSessionsRepository::new();
+        sessions_repository
+            .expect_find_active_by_id()
+            .returning(|id| {
+                let now = Utc::now().naive_utc();
+                Box::pin(async move {
+                    Ok(Some(SessionEntity {
+                        id,
+                        user_id: 1,
+                        client_id: None,
+                        created_at: now,
+                        expires_at: now + Duration::days(1),
+                        revoked_at: None,
+                    }))
+                })
+            });
+
+        let result = use_case(sessions_repository)
+            .ensure_session_active(&Some("active".to_string()))
+            .await;
+
+        assert!(result.is_ok());
     }
 }
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::domain::{
+    repositories::{care_assignments::CareAssignmentsRepository, users::UsersRepository},
+    value_objects::{
+        care_assignments_model::{
+            CareAssignmentError, CareAssignmentFilter, CareAssignmentModel,
+            CareAssignmentQueryModel, CreateCareAssignmentModel,
+        },
+        roles::Role,
+    },
+};
+
+pub struct CareAssignmentsUseCase<C, U>
+where
+    C: CareAssignmentsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    care_assignments_repository: Arc<C>,
+    users_repository: Arc<U>,
+}
+
+impl<C, U> CareAssignmentsUseCase<C, U>
+where
+    C: CareAssignmentsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    pub fn new(care_assignments_repository: Arc<C>, users_repository: Arc<U>) -> Self {
+        Self {
+            care_assignments_repository,
+            users_repository,
+        }
+    }
+
+    /// Lets the doctor read and update the patient's records at the hospital. Fails with
+    /// `CareAssignmentError::DoctorNotFound` or `PatientNotFound` when either does not hold
+    /// that role at the hospital, or `RepositoryError::Conflict` when already assigned.
+    pub async fn create(
+        &self,
+        hospital_id: i32,
+        staff_id: i32,
+        create_care_assignment_model: CreateCareAssignmentModel,
+    ) -> Result<CareAssignmentModel> {
+        let doctor_roles = self
+            .users_repository
+            .find_roles(hospital_id, create_care_assignment_model.doctor_id)
+            .await?;
+        if !doctor_roles.contains(&Role::Doctor) {
+            return Err(CareAssignmentError::DoctorNotFound.into());
+        }
+
+        let patient_roles = self
+            .users_repository
+            .find_roles(hospital_id, create_care_assignment_model.patient_id)
+            .await?;
+        if !patient_roles.contains(&Role::Patient)
+            || create_care_assignment_model.patient_id == create_care_assignment_model.doctor_id
+        {
+            return Err(CareAssignmentError::PatientNotFound.into());
+        }
+
+        let entity = create_care_assignment_model.to_entity(hospital_id, staff_id);
+
+        Ok(self
+            .care_assignments_repository
+            .create(entity)
+            .await?
+            .into())
+    }
+
+    pub async fn list(
+        &self,
+        hospital_id: i32,
+        query: CareAssignmentQueryModel,
+    ) -> Result<Vec<CareAssignmentModel>> {
+        let filter = CareAssignmentFilter {
+            hospital_id,
+            doctor_id: query.doctor_id,
+            patient_id: query.patient_id,
+        };
+
+        let assignments = self.care_assignments_repository.list(filter).await?;
+
+        Ok(assignments
+            .into_iter()
+            .map(CareAssignmentModel::from)
+            .collect())
+    }
+
+    /// The doctor loses access to the patient's records on their next request. Fails with
+    /// `CareAssignmentError::NotFound` when it does not exist or has already ended.
+    pub async fn end(
+        &self,
+        hospital_id: i32,
+        staff_id: i32,
+        id: i32,
+    ) -> Result<CareAssignmentModel> {
+        match self
+            .care_assignments_repository
+            .end(hospital_id, id, staff_id)
+            .await?
+        {
+            Some(assignment) => Ok(assignment.into()),
+            None => Err(CareAssignmentError::NotFound.into()),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use validator::Validate;
+
+use crate::domain::{
+    entities::consents::InsertConsentRecordEntity,
+    repositories::consents::ConsentsRepository,
+    value_objects::consents_model::{
+        ConsentAction, ConsentError, ConsentPurposeModel, ConsentRecordModel,
+        ConsentReportRowModel, ConsentVersionModel, CreateConsentPurposeModel, CurrentConsentModel,
+        PublishConsentVersionModel, RecordConsentModel, UserConsentModel,
+    },
+};
+
+pub struct ConsentsUseCase<C>
+where
+    C: ConsentsRepository + Send + Sync,
+{
+    consents_repository: Arc<C>,
+}
+
+impl<C> ConsentsUseCase<C>
+where
+    C: ConsentsRepository + Send + Sync,
+{
+    pub fn new(consents_repository: Arc<C>) -> Self {
+        Self {
+            consents_repository,
+        }
+    }
+
+    pub async fn list_current(&self) -> Result<Vec<CurrentConsentModel>> {
+        let current = self.consents_repository.find_current_versions().await?;
+
+        Ok(current
+            .into_iter()
+            .map(|(purpose, version)| CurrentConsentModel::new(purpose, version))
+            .collect())
+    }
+
+    /// Every purpose in effect with the user's latest decision on it.
+    pub async fn list_by_user_id(&self, user_id: i32) -> Result<Vec<UserConsentModel>> {
+        let current = self.consents_repository.find_current_versions().await?;
+        let mut latest: HashMap<i32, _> = self
+            .consents_repository
+            .find_latest_records(user_id)
+            .await?
+            .into_iter()
+            .map(|(record, version)| (version.purpose_id, (record, version)))
+            .collect();
+
+        Ok(current
+            .into_iter()
+            .map(|(purpose, current_version)| {
+                let latest = latest.remove(&purpose.id);
+                let granted_current = latest.as_ref().is_some_and(|(record, version)| {
+                    record.action == ConsentAction::Granted.to_string()
+                        && version.id == current_version.id
+                });
+
+                UserConsentModel {
+                    current: CurrentConsentModel::new(purpose, current_version),
+                    latest: latest
+                        .map(|(record, version)| ConsentRecordModel::new(record, &version)),
+                    granted_current,
+                }
+            })
+            .collect())
+    }
+
+    /// Records the user's own decision. Fails with `ConsentError::VersionNotFound`,
+    /// `ConsentError::VersionNotCurrent` when granting an outdated version, or
+    /// `ConsentError::NotGranted` when withdrawing a purpose that is not granted.
+    pub async fn record(
+        &self,
+        user_id: i32,
+        record_consent_model: RecordConsentModel,
+    ) -> Result<ConsentRecordModel> {
+        let Some(version) = self
+            .consents_repository
+            .find_version(record_consent_model.consent_version_id)
+            .await?
+        else {
+            return Err(ConsentError::VersionNotFound.into());
+        };
+
+        let version = match record_consent_model.action {
+            ConsentAction::Granted => {
+                let is_current = self
+                    .consents_repository
+                    .find_current_versions()
+                    .await?
+                    .iter()
+                    .any(|(_, current_version)| current_version.id == version.id);
+                if !is_current {
+                    return Err(ConsentError::VersionNotCurrent.into());
+                }
+
+                version
+            }
+            // ถอนความยินยอมทั้ง purpose โดยบันทึกกับฉบับที่ยินยอมไว้
+            ConsentAction::Withdrawn => {
+                let granted = self
+                    .consents_repository
+                    .find_latest_records(user_id)
+                    .await?
+                    .into_iter()
+                    .find(|(record, granted_version)| {
+                        granted_version.purpose_id == version.purpose_id
+                            && record.action == ConsentAction::Granted.to_string()
+                    });
+
+                match granted {
+                    Some((_, granted_version)) => granted_version,
+                    None => return Err(ConsentError::NotGranted.into()),
+                }
+            }
+        };
+
+        let record = self
+            .consents_repository
+            .record(InsertConsentRecordEntity {
+                user_id,
+                consent_version_id: version.id,
+                action: record_consent_model.action.to_string(),
+                channel: record_consent_model.channel.to_string(),
+                recorded_by: None,
+                created_at: chrono::Utc::now().naive_utc(),
+            })
+            .await?;
+
+        Ok(ConsentRecordModel::new(record, &version))
+    }
+
+    /// Fails with `validator::ValidationErrors` or `RepositoryError::Conflict` when the code is
+    /// taken.
+    pub async fn create_purpose(
+        &self,
+        create_consent_purpose_model: CreateConsentPurposeModel,
+    ) -> Result<ConsentPurposeModel> {
+        create_consent_purpose_model.validate()?;
+
+        let purpose = self
+            .consents_repository
+            .create_purpose(create_consent_purpose_model.to_entity())
+            .await?;
+
+        Ok(purpose.into())
+    }
+
+    /// Users who granted an earlier version are asked again once this one takes effect. Fails
+    /// with `validator::ValidationErrors` or `ConsentError::PurposeNotFound`.
+    pub async fn publish_version(
+        &self,
+        purpose_id: i32,
+        created_by: i32,
+        publish_consent_version_model: PublishConsentVersionModel,
+    ) -> Result<ConsentVersionModel> {
+        publish_consent_version_model.validate()?;
+
+        let effective_from = publish_consent_version_model
+            .effective_from
+            .unwrap_or_else(|| chrono::Utc::now().naive_utc());
+
+        match self
+            .consents_repository
+            .publish_version(
+                purpose_id,
+                publish_consent_version_model.text,
+                effective_from,
+                created_by,
+            )
+            .await?
+        {
+            Some(version) => Ok(version.into()),
+            None => Err(ConsentError::PurposeNotFound.into()),
+        }
+    }
+
+    /// Where the hospital's users stand on each purpose in effect.
+    pub async fn report(&self, hospital_id: i32) -> Result<Vec<ConsentReportRowModel>> {
+        let current = self.consents_repository.find_current_versions().await?;
+        let latest = self
+            .consents_repository
+            .find_latest_records_at_hospital(hospital_id)
+            .await?;
+        let members = self.consents_repository.count_members(hospital_id).await?;
+
+        Ok(current
+            .into_iter()
+            .map(|(purpose, current_version)| {
+                let mut row = ConsentReportRowModel {
+                    purpose_code: purpose.code,
+                    purpose_name: purpose.name,
+                    current_version: current_version.version,
+                    ..Default::default()
+                };
+
+                for (record, version) in &latest {
+                    if version.purpose_id != purpose.id {
+                        continue;
+                    }
+
+                    if record.action != ConsentAction::Granted.to_string() {
+                        row.withdrawn += 1;
+                    } else if version.id == current_version.id {
+                        row.granted_current += 1;
+                    } else {
+                        row.granted_outdated += 1;
+                    }
+                }
+                row.undecided =
+                    members - row.granted_current - row.granted_outdated - row.withdrawn;
+
+                row
+            })
+            .collect())
+    }
+}
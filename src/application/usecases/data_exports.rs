@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+use crate::{
+    config::config_model::DataExport,
+    domain::{
+        entities::data_exports::InsertDataExportEntity,
+        repositories::data_exports::DataExportsRepository,
+        value_objects::data_exports_model::{
+            DataExportError, DataExportFileModel, DataExportFormat, DataExportModel,
+            DataExportOutcome, DataExportStatus,
+        },
+    },
+    infrastructure::{opaque_token, zip_archive},
+};
+
+pub struct DataExportsUseCase<D>
+where
+    D: DataExportsRepository + Send + Sync,
+{
+    data_exports_repository: Arc<D>,
+    config: DataExport,
+}
+
+impl<D> DataExportsUseCase<D>
+where
+    D: DataExportsRepository + Send + Sync,
+{
+    pub fn new(data_exports_repository: Arc<D>, config: DataExport) -> Self {
+        Self {
+            data_exports_repository,
+            config,
+        }
+    }
+
+    /// Generates the user's export during the request when it is small enough, otherwise
+    /// queues it for the background task.
+    pub async fn export(
+        &self,
+        user_id: i32,
+        format: DataExportFormat,
+    ) -> Result<DataExportOutcome> {
+        let rows = self.data_exports_repository.count_rows(user_id).await?;
+
+        if rows > self.config.sync_max_rows {
+            let now = Utc::now().naive_utc();
+            let export = self
+                .data_exports_repository
+                .create(InsertDataExportEntity {
+                    id: opaque_token::generate(48),
+                    user_id,
+                    format: format.to_string(),
+                    created_at: now,
+                    // ถ้ายังค้างอยู่ในคิวเกินเวลานี้จะถูกลบทิ้ง
+                    expires_at: now + Duration::hours(self.config.ttl_hours),
+                })
+                .await?;
+
+            return Ok(DataExportOutcome::Queued(export.into()));
+        }
+
+        let content = self.generate(user_id, format).await?;
+
+        Ok(DataExportOutcome::Ready(DataExportFileModel {
+            format,
+            content,
+        }))
+    }
+
+    /// Fails with `DataExportError::NotFound` unless the export is the user's.
+    pub async fn find_by_id(&self, user_id: i32, id: String) -> Result<DataExportModel> {
+        match self.data_exports_repository.find_by_id(user_id, id).await? {
+            Some(export) => Ok(export.into()),
+            None => Err(DataExportError::NotFound.into()),
+        }
+    }
+
+    /// Fails with `DataExportError::NotFound` unless the export is the user's,
+    /// `DataExportError::Expired`, or `DataExportError::NotReady`.
+    pub async fn download(&self, user_id: i32, id: String) -> Result<DataExportFileModel> {
+        let Some(export) = self
+            .data_exports_repository
+            .find_by_id(user_id, id.clone())
+            .await?
+        else {
+            return Err(DataExportError::NotFound.into());
+        };
+
+        if export.expires_at <= Utc::now().naive_utc() {
+            return Err(DataExportError::Expired.into());
+        }
+        if export.status != DataExportStatus::Ready.to_string() {
+            return Err(DataExportError::NotReady.into());
+        }
+
+        let Some(content) = self
+            .data_exports_repository
+            .find_content(user_id, id)
+            .await?
+        else {
+            return Err(DataExportError::NotFound.into());
+        };
+
+        Ok(DataExportFileModel {
+            format: export.format.parse()?,
+            content,
+        })
+    }
+
+    /// Generates the oldest queued export, which can then be downloaded for `ttl_hours`.
+    /// Returns whether there was one.
+    pub async fn process_next(&self) -> Result<bool> {
+        let stale_before = Utc::now().naive_utc() - Duration::minutes(self.config.lease_minutes);
+        let Some(export) = self
+            .data_exports_repository
+            .claim_next(stale_before)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let generated = match export.format.parse() {
+            Ok(format) => self.generate(export.user_id, format).await,
+            Err(e) => Err(e),
+        };
+
+        match generated {
+            Ok(content) => {
+                let now = Utc::now().naive_utc();
+                self.data_exports_repository
+                    .complete(
+                        export.id,
+                        content,
+                        now,
+                        now + Duration::hours(self.config.ttl_hours),
+                    )
+                    .await?;
+            }
+            Err(e) => {
+                self.data_exports_repository
+                    .fail(export.id, e.to_string())
+                    .await?
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub async fn delete_expired(&self) -> Result<usize> {
+        self.data_exports_repository
+            .delete_expired(Utc::now().naive_utc())
+            .await
+    }
+
+    async fn generate(&self, user_id: i32, format: DataExportFormat) -> Result<Vec<u8>> {
+        let Some(bundle) = self.data_exports_repository.find_bundle(user_id).await? else {
+            return Err(DataExportError::NotFound.into());
+        };
+
+        let json = serde_json::to_vec_pretty(&bundle)?;
+
+        match format {
+            DataExportFormat::Json => Ok(json),
+            DataExportFormat::Zip => zip_archive::single_file("export.json", &json),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use validator::Validate;
+
+use crate::domain::{
+    repositories::doctor_applications::DoctorApplicationsRepository,
+    value_objects::doctor_applications_model::{
+        DoctorApplicationError, DoctorApplicationModel, DoctorApplicationStatus,
+        DoctorApplicationTransition, DoctorApplicationTransitionModel,
+        ReviewDoctorApplicationModel, SubmitDoctorApplicationModel,
+    },
+};
+
+pub struct DoctorApplicationsUseCase<A>
+where
+    A: DoctorApplicationsRepository + Send + Sync,
+{
+    doctor_applications_repository: Arc<A>,
+}
+
+impl<A> DoctorApplicationsUseCase<A>
+where
+    A: DoctorApplicationsRepository + Send + Sync,
+{
+    pub fn new(doctor_applications_repository: Arc<A>) -> Self {
+        Self {
+            doctor_applications_repository,
+        }
+    }
+
+    /// Applies to practise at the hospital. Fails with `validator::ValidationErrors` for invalid
+    /// input and with `RepositoryError::Conflict` while another application to the hospital is
+    /// pending or approved.
+    pub async fn submit(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        submit_doctor_application_model: SubmitDoctorApplicationModel,
+    ) -> Result<DoctorApplicationModel> {
+        submit_doctor_application_model.validate()?;
+        let entity = submit_doctor_application_model.to_entity(hospital_id, user_id)?;
+
+        self.doctor_applications_repository
+            .submit(entity)
+            .await?
+            .try_into()
+    }
+
+    pub async fn list_by_user_id(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<DoctorApplicationModel>> {
+        self.doctor_applications_repository
+            .list_by_user_id(hospital_id, user_id)
+            .await?
+            .into_iter()
+            .map(DoctorApplicationModel::try_from)
+            .collect()
+    }
+
+    pub async fn list_by_status(
+        &self,
+        hospital_id: i32,
+        status: Option<DoctorApplicationStatus>,
+    ) -> Result<Vec<DoctorApplicationModel>> {
+        let status = status.unwrap_or(DoctorApplicationStatus::Pending);
+
+        self.doctor_applications_repository
+            .list_by_status(hospital_id, status.to_string())
+            .await?
+            .into_iter()
+            .map(DoctorApplicationModel::try_from)
+            .collect()
+    }
+
+    /// With its full history. Fails with `DoctorApplicationError::NotFound`.
+    pub async fn find_by_id(&self, hospital_id: i32, id: i32) -> Result<DoctorApplicationModel> {
+        let application = self
+            .doctor_applications_repository
+            .find_by_id(hospital_id, id)
+            .await?
+            .ok_or(DoctorApplicationError::NotFound)?;
+
+        self.with_history(application.try_into()?).await
+    }
+
+    /// Approves, rejects or revokes. The `Doctor` role is granted only here, on approval, and
+    /// taken away on revocation. Fails with `validator::ValidationErrors`,
+    /// `DoctorApplicationError::NotFound` or `DoctorApplicationError::InvalidTransition`.
+    pub async fn review(
+        &self,
+        hospital_id: i32,
+        reviewer_id: i32,
+        id: i32,
+        review_doctor_application_model: ReviewDoctorApplicationModel,
+    ) -> Result<DoctorApplicationModel> {
+        review_doctor_application_model.validate_review()?;
+        let to_status = review_doctor_application_model.status;
+
+        let current = self
+            .doctor_applications_repository
+            .find_by_id(hospital_id, id)
+            .await?
+            .ok_or(DoctorApplicationError::NotFound)?;
+        let from_status: DoctorApplicationStatus = current.status.parse()?;
+
+        if !from_status.can_transition_to(to_status) {
+            return Err(DoctorApplicationError::InvalidTransition {
+                from: from_status,
+                to: to_status,
+            }
+            .into());
+        }
+
+        let application = self
+            .doctor_applications_repository
+            .transition(DoctorApplicationTransition {
+                application_id: id,
+                from_status,
+                to_status,
+                actor_id: reviewer_id,
+                notes: review_doctor_application_model
+                    .notes
+                    .map(|notes| notes.trim().to_string())
+                    .filter(|notes| !notes.is_empty()),
+                valid_until: review_doctor_application_model.valid_until,
+            })
+            .await?;
+
+        match application {
+            Some(application) => self.with_history(application.try_into()?).await,
+            // มีคนตัดสินไปก่อนระหว่างที่เราตรวจ status
+            None => {
+                let latest = self
+                    .doctor_applications_repository
+                    .find_by_id(hospital_id, id)
+                    .await?
+                    .ok_or(DoctorApplicationError::NotFound)?;
+
+                Err(DoctorApplicationError::InvalidTransition {
+                    from: latest.status.parse()?,
+                    to: to_status,
+                }
+                .into())
+            }
+        }
+    }
+
+    async fn with_history(
+        &self,
+        mut application: DoctorApplicationModel,
+    ) -> Result<DoctorApplicationModel> {
+        application.history = self
+            .doctor_applications_repository
+            .list_transitions(application.id)
+            .await?
+            .into_iter()
+            .map(DoctorApplicationTransitionModel::try_from)
+            .collect::<Result<_>>()?;
+
+        Ok(application)
+    }
+}
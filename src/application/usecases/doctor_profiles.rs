@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::domain::{
+    repositories::doctor_profiles::DoctorProfilesRepository,
+    value_objects::{
+        doctor_profiles_model::{
+            DoctorDirectoryEntryModel, DoctorDirectoryFilter, DoctorProfileModel,
+            ListDoctorsQueryModel, UpsertDoctorProfileModel,
+        },
+        pagination::{self, PageModel},
+    },
+};
+
+pub struct DoctorProfilesUseCase<D>
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    doctor_profiles_repository: Arc<D>,
+}
+
+impl<D> DoctorProfilesUseCase<D>
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    pub fn new(doctor_profiles_repository: Arc<D>) -> Self {
+        Self {
+            doctor_profiles_repository,
+        }
+    }
+
+    pub async fn find_by_user_id(&self, user_id: i32) -> Result<Option<DoctorProfileModel>> {
+        Ok(self
+            .doctor_profiles_repository
+            .find_by_user_id(user_id)
+            .await?
+            .map(DoctorProfileModel::from))
+    }
+
+    /// New profiles start unverified. Fails with `validator::ValidationErrors` for invalid
+    /// input and with `RepositoryError::Conflict` on a duplicate profile or license number.
+    pub async fn create(
+        &self,
+        user_id: i32,
+        upsert_doctor_profile_model: UpsertDoctorProfileModel,
+    ) -> Result<DoctorProfileModel> {
+        upsert_doctor_profile_model.validate()?;
+        let entity = upsert_doctor_profile_model.normalized().to_entity(user_id);
+
+        Ok(self.doctor_profiles_repository.create(entity).await?.into())
+    }
+
+    /// Replaces the whole profile. A new license number has to be verified again.
+    pub async fn update(
+        &self,
+        user_id: i32,
+        upsert_doctor_profile_model: UpsertDoctorProfileModel,
+    ) -> Result<Option<DoctorProfileModel>> {
+        upsert_doctor_profile_model.validate()?;
+        let entity = upsert_doctor_profile_model.normalized().to_entity(user_id);
+
+        let Some(current) = self
+            .doctor_profiles_repository
+            .find_by_user_id(user_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let reset_verification = current.license_number != entity.license_number;
+
+        Ok(self
+            .doctor_profiles_repository
+            .update(entity, reset_verification)
+            .await?
+            .map(DoctorProfileModel::from))
+    }
+
+    pub async fn set_verification(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+        verified: bool,
+    ) -> Result<Option<DoctorProfileModel>> {
+        Ok(self
+            .doctor_profiles_repository
+            .set_verification(user_id, verified.then_some(admin_id))
+            .await?
+            .map(DoctorProfileModel::from))
+    }
+
+    /// Fails with `validator::ValidationErrors` for bad filters or a malformed cursor.
+    pub async fn list_directory(
+        &self,
+        hospital_id: i32,
+        list_doctors_query: ListDoctorsQueryModel,
+    ) -> Result<PageModel<DoctorDirectoryEntryModel>> {
+        list_doctors_query.validate()?;
+
+        let limit = pagination::clamp_limit(list_doctors_query.limit);
+        let after_user_id = match &list_doctors_query.cursor {
+            Some(cursor) => Some(pagination::decode_cursor::<i32>(cursor).map_err(|_| {
+                let mut errors = ValidationErrors::new();
+                errors.add(
+                    "cursor",
+                    ValidationError::new("cursor_invalid")
+                        .with_message("Cursor is malformed".into()),
+                );
+                errors
+            })?),
+            None => None,
+        };
+
+        // ขอเกินมา 1 แถวเพื่อรู้ว่ายังมีหน้าถัดไปไหม
+        let mut doctors = self
+            .doctor_profiles_repository
+            .list_directory(DoctorDirectoryFilter {
+                hospital_id,
+                specialty: list_doctors_query
+                    .specialty
+                    .map(|specialty| specialty.trim().to_lowercase()),
+                department: list_doctors_query
+                    .department
+                    .map(|department| department.trim().to_string()),
+                language: list_doctors_query
+                    .language
+                    .map(|language| language.to_lowercase()),
+                after_user_id,
+                limit: limit + 1,
+            })
+            .await?;
+
+        let has_more = doctors.len() as i64 > limit;
+        doctors.truncate(limit as usize);
+
+        let next_cursor = match doctors.last() {
+            Some(last) if has_more => Some(pagination::encode_cursor(&last.profile.user_id)?),
+            _ => None,
+        };
+
+        Ok(PageModel {
+            items: doctors
+                .into_iter()
+                .map(DoctorDirectoryEntryModel::from)
+                .collect(),
+            next_cursor,
+            has_more,
+            limit,
+        })
+    }
+
+    pub async fn find_in_directory(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Option<DoctorDirectoryEntryModel>> {
+        Ok(self
+            .doctor_profiles_repository
+            .find_in_directory(hospital_id, user_id)
+            .await?
+            .map(DoctorDirectoryEntryModel::from))
+    }
+}
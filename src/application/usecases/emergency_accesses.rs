@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use validator::Validate;
+
+use crate::domain::{
+    repositories::emergency_accesses::EmergencyAccessRepository,
+    value_objects::emergency_accesses_model::{
+        CreateEmergencyAccessModel, EmergencyAccessError, EmergencyAccessFilter,
+        EmergencyAccessModel, EmergencyAccessReportQueryModel, ReviewEmergencyAccessModel,
+    },
+};
+
+pub struct EmergencyAccessUseCase<E>
+where
+    E: EmergencyAccessRepository + Send + Sync,
+{
+    emergency_access_repository: Arc<E>,
+}
+
+impl<E> EmergencyAccessUseCase<E>
+where
+    E: EmergencyAccessRepository + Send + Sync,
+{
+    pub fn new(emergency_access_repository: Arc<E>) -> Self {
+        Self {
+            emergency_access_repository,
+        }
+    }
+
+    /// Breaks the glass: the user may read and update the patient's records at the hospital
+    /// until the access expires. Fails with `validator::ValidationErrors` or
+    /// `EmergencyAccessError::PatientNotFound`.
+    pub async fn grant(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        create_emergency_access_model: CreateEmergencyAccessModel,
+    ) -> Result<EmergencyAccessModel> {
+        create_emergency_access_model.validate()?;
+
+        let entity = create_emergency_access_model.to_entity(hospital_id, user_id);
+
+        match self.emergency_access_repository.grant(entity).await? {
+            Some(access) => Ok(access.into()),
+            None => Err(EmergencyAccessError::PatientNotFound.into()),
+        }
+    }
+
+    pub async fn report(
+        &self,
+        hospital_id: i32,
+        query: EmergencyAccessReportQueryModel,
+    ) -> Result<Vec<EmergencyAccessModel>> {
+        let filter = EmergencyAccessFilter {
+            hospital_id,
+            from: query.from,
+            to: query.to,
+            unreviewed: query.unreviewed.unwrap_or(false),
+        };
+
+        let accesses = self.emergency_access_repository.list(filter).await?;
+
+        Ok(accesses
+            .into_iter()
+            .map(EmergencyAccessModel::from)
+            .collect())
+    }
+
+    /// Closes an access for review. Fails with `EmergencyAccessError::NotFound` when it does not
+    /// exist at the hospital or has already been reviewed.
+    pub async fn review(
+        &self,
+        hospital_id: i32,
+        reviewer_id: i32,
+        id: i32,
+        review_emergency_access_model: ReviewEmergencyAccessModel,
+    ) -> Result<EmergencyAccessModel> {
+        review_emergency_access_model.validate()?;
+
+        let notes = review_emergency_access_model.notes.trim().to_string();
+
+        match self
+            .emergency_access_repository
+            .review(hospital_id, id, reviewer_id, notes)
+            .await?
+        {
+            Some(access) => Ok(access.into()),
+            None => Err(EmergencyAccessError::NotFound.into()),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Months;
+use validator::Validate;
+
+use crate::{
+    config::config_model::Erasure,
+    domain::{
+        repositories::erasures::ErasuresRepository,
+        value_objects::erasures_model::{EraseUserModel, ErasureError, ErasureModel},
+    },
+};
+
+pub struct ErasuresUseCase<E>
+where
+    E: ErasuresRepository + Send + Sync,
+{
+    erasures_repository: Arc<E>,
+    config: Erasure,
+}
+
+impl<E> ErasuresUseCase<E>
+where
+    E: ErasuresRepository + Send + Sync,
+{
+    pub fn new(erasures_repository: Arc<E>, config: Erasure) -> Self {
+        Self {
+            erasures_repository,
+            config,
+        }
+    }
+
+    /// Scrubs the user's personal data and revokes their credentials, keeping the row for the
+    /// records that refer to it. Fails with `validator::ValidationErrors`,
+    /// `ErasureError::UserNotFound`, `ErasureError::RetentionPeriod` while any hospital must
+    /// still keep the medical record, or `RepositoryError::Conflict` when already erased.
+    pub async fn erase(
+        &self,
+        hospital_id: i32,
+        erased_by: i32,
+        user_id: i32,
+        erase_user_model: EraseUserModel,
+    ) -> Result<ErasureModel> {
+        erase_user_model.validate()?;
+
+        // ข้อมูลใน users ใช้ร่วมทุกโรงพยาบาล จึงนับจากกิจกรรมล่าสุดของทุกแห่ง
+        if let Some(last_clinical_activity_at) = self
+            .erasures_repository
+            .find_last_clinical_activity_at(hospital_id, user_id)
+            .await?
+        {
+            let retention_months = self.config.medical_record_retention_years.max(0) as u32 * 12;
+            let retained_until = last_clinical_activity_at
+                .checked_add_months(Months::new(retention_months))
+                .unwrap_or(last_clinical_activity_at);
+
+            if retained_until > chrono::Utc::now().naive_utc() {
+                return Err(ErasureError::RetentionPeriod { retained_until }.into());
+            }
+        }
+
+        let entity = erase_user_model.to_entity(hospital_id, user_id, erased_by);
+
+        match self.erasures_repository.erase(entity).await? {
+            Some(erasure) => erasure.try_into(),
+            None => Err(ErasureError::UserNotFound.into()),
+        }
+    }
+}
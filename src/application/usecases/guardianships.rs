@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+use crate::{
+    config::config_loader::get_patients_secret_env,
+    domain::{
+        repositories::{guardianships::GuardianshipsRepository, users::UsersRepository},
+        value_objects::{
+            guardianships_model::{
+                ActingAsModel, CreateGuardianshipModel, DELEGATED_TOKEN_TTL_MINUTES,
+                GuardianshipError, GuardianshipModel,
+            },
+            roles::Role,
+        },
+    },
+    infrastructure::jwt_authentication::{
+        self,
+        jwt_model::{Actor, Claims},
+    },
+};
+
+pub struct GuardianshipsUseCase<G, U>
+where
+    G: GuardianshipsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    guardianships_repository: Arc<G>,
+    users_repository: Arc<U>,
+}
+
+impl<G, U> GuardianshipsUseCase<G, U>
+where
+    G: GuardianshipsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    pub fn new(guardianships_repository: Arc<G>, users_repository: Arc<U>) -> Self {
+        Self {
+            guardianships_repository,
+            users_repository,
+        }
+    }
+
+    /// Records a guardianship after staff have checked the consent evidence. Fails with
+    /// `validator::ValidationErrors`, `GuardianshipError::UserNotFound` when either side is not
+    /// registered at the hospital, or `RepositoryError::Conflict` when the pair already has one.
+    pub async fn create(
+        &self,
+        hospital_id: i32,
+        staff_id: i32,
+        create_guardianship_model: CreateGuardianshipModel,
+    ) -> Result<GuardianshipModel> {
+        create_guardianship_model.validate_guardianship()?;
+
+        let guardian_id = create_guardianship_model.guardian_id;
+        let dependent_id = create_guardianship_model.dependent_id;
+
+        let hospital_numbers = self
+            .users_repository
+            .find_hospital_numbers(hospital_id, vec![guardian_id, dependent_id])
+            .await?;
+        if !hospital_numbers.contains_key(&guardian_id)
+            || !hospital_numbers.contains_key(&dependent_id)
+        {
+            return Err(GuardianshipError::UserNotFound.into());
+        }
+
+        let entity = create_guardianship_model.to_entity(staff_id)?;
+
+        self.guardianships_repository
+            .create(hospital_id, entity)
+            .await?
+            .try_into()
+    }
+
+    /// Delegated tokens issued under the guardianship stop working on their next request.
+    /// Fails with `GuardianshipError::NotFound` when it does not exist or is already revoked.
+    pub async fn revoke(
+        &self,
+        hospital_id: i32,
+        staff_id: i32,
+        id: i32,
+    ) -> Result<GuardianshipModel> {
+        self.guardianships_repository
+            .revoke(hospital_id, id, staff_id)
+            .await?
+            .ok_or(GuardianshipError::NotFound)?
+            .try_into()
+    }
+
+    pub async fn list_by_user_id(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<GuardianshipModel>> {
+        self.guardianships_repository
+            .list_by_user_id(hospital_id, user_id)
+            .await?
+            .into_iter()
+            .map(GuardianshipModel::try_from)
+            .collect()
+    }
+
+    /// The guardian's active guardianships, i.e. whom they can switch to.
+    pub async fn list_dependents(&self, guardian_id: i32) -> Result<Vec<GuardianshipModel>> {
+        self.guardianships_repository
+            .list_active_for_guardian(guardian_id)
+            .await?
+            .into_iter()
+            .map(GuardianshipModel::try_from)
+            .collect()
+    }
+
+    /// Issues a patient access token for the dependent carrying the guardian as `act`, and
+    /// audits the switch. The token is short-lived and grants only what the dependent may do
+    /// with their own records. Fails with `GuardianshipError::NotGuardian`.
+    pub async fn act_as(
+        &self,
+        hospital_id: i32,
+        guardian_id: i32,
+        dependent_id: i32,
+    ) -> Result<(String, ActingAsModel)> {
+        let guardianship = self
+            .guardianships_repository
+            .find_active(guardian_id, dependent_id)
+            .await?
+            .ok_or(GuardianshipError::NotGuardian)?;
+
+        let dependent_roles = self
+            .users_repository
+            .find_roles(hospital_id, dependent_id)
+            .await?;
+        if !dependent_roles.contains(&Role::Patient) {
+            return Err(GuardianshipError::NotGuardian.into());
+        }
+
+        let now = Utc::now();
+        let expires_at = match guardianship.valid_until {
+            Some(valid_until) => {
+                (now + Duration::minutes(DELEGATED_TOKEN_TTL_MINUTES)).min(valid_until.and_utc())
+            }
+            None => now + Duration::minutes(DELEGATED_TOKEN_TTL_MINUTES),
+        };
+
+        let claims = Claims {
+            sub: dependent_id.to_string(),
+            role: Role::Patient,
+            exp: expires_at.timestamp() as usize,
+            iat: now.timestamp() as usize,
+            sid: None,
+            hid: Some(hospital_id),
+            act: Some(Actor {
+                sub: guardian_id.to_string(),
+            }),
+        };
+        let access_token =
+            jwt_authentication::generate_token(get_patients_secret_env()?.secret, &claims)?;
+
+        self.guardianships_repository
+            .record_switch(
+                &guardianship,
+                serde_json::json!({
+                    "guardianship_id": guardianship.id,
+                    "hospital_id": hospital_id,
+                    "expires_at": expires_at.naive_utc(),
+                }),
+            )
+            .await?;
+
+        Ok((
+            access_token,
+            ActingAsModel {
+                guardianship_id: guardianship.id,
+                dependent_id,
+                expires_at: expires_at.naive_utc(),
+            },
+        ))
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+use crate::{
+    domain::{
+        entities::idempotency_keys::InsertIdempotencyKeyEntity,
+        repositories::idempotency_keys::IdempotencyKeysRepository,
+    },
+    infrastructure::opaque_token,
+};
+
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// What to do with a request that carries an `Idempotency-Key`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyOutcome {
+    /// First time this key is seen: run the handler, then `complete` or `release`.
+    Proceed,
+    /// Same key and same request: send back the stored response.
+    Replay {
+        status_code: u16,
+        content_type: Option<String>,
+        response_body: Vec<u8>,
+    },
+    /// The first request with this key has not finished yet.
+    InProgress,
+    /// The key was already used for a different request.
+    Mismatch,
+}
+
+pub struct IdempotencyUseCase<I>
+where
+    I: IdempotencyKeysRepository + Send + Sync,
+{
+    idempotency_keys_repository: Arc<I>,
+    ttl_hours: i64,
+}
+
+impl<I> IdempotencyUseCase<I>
+where
+    I: IdempotencyKeysRepository + Send + Sync,
+{
+    pub fn new(idempotency_keys_repository: Arc<I>, ttl_hours: i64) -> Self {
+        Self {
+            idempotency_keys_repository,
+            ttl_hours,
+        }
+    }
+
+    /// `scope` is the method, route and caller (`POST /users user:7@1`); the fingerprint covers
+    /// scope and body.
+    pub async fn begin(
+        &self,
+        idempotency_key: &str,
+        scope: &str,
+        request_body: &[u8],
+    ) -> Result<IdempotencyOutcome> {
+        let fingerprint = fingerprint(scope, request_body);
+        let now = Utc::now().naive_utc();
+
+        let reserved = self
+            .idempotency_keys_repository
+            .reserve(InsertIdempotencyKeyEntity {
+                idempotency_key: idempotency_key.to_string(),
+                scope: scope.to_string(),
+                fingerprint: fingerprint.clone(),
+                created_at: now,
+                expires_at: now + Duration::hours(self.ttl_hours),
+            })
+            .await?;
+
+        if reserved {
+            return Ok(IdempotencyOutcome::Proceed);
+        }
+
+        let Some(stored) = self
+            .idempotency_keys_repository
+            .find_active(idempotency_key.to_string(), scope.to_string())
+            .await?
+        else {
+            // หมดอายุหรือถูก release ไประหว่างนี้ ให้ client ลองใหม่
+            return Ok(IdempotencyOutcome::InProgress);
+        };
+
+        if stored.fingerprint != fingerprint {
+            return Ok(IdempotencyOutcome::Mismatch);
+        }
+
+        match (stored.status_code, stored.response_body) {
+            (Some(status_code), Some(response_body)) => Ok(IdempotencyOutcome::Replay {
+                status_code: u16::try_from(status_code)?,
+                content_type: stored.content_type,
+                response_body,
+            }),
+            _ => Ok(IdempotencyOutcome::InProgress),
+        }
+    }
+
+    pub async fn complete(
+        &self,
+        idempotency_key: &str,
+        scope: &str,
+        status_code: u16,
+        content_type: Option<String>,
+        response_body: Vec<u8>,
+    ) -> Result<()> {
+        self.idempotency_keys_repository
+            .complete(
+                idempotency_key.to_string(),
+                scope.to_string(),
+                i32::from(status_code),
+                content_type,
+                response_body,
+            )
+            .await
+    }
+
+    /// Drops a pending key so the request can be retried, e.g. after a server error.
+    pub async fn release(&self, idempotency_key: &str, scope: &str) -> Result<()> {
+        self.idempotency_keys_repository
+            .release(idempotency_key.to_string(), scope.to_string())
+            .await
+    }
+}
+
+pub fn is_valid_idempotency_key(idempotency_key: &str) -> bool {
+    !idempotency_key.is_empty()
+        && idempotency_key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH
+        && idempotency_key.chars().all(|c| c.is_ascii_graphic())
+}
+
+fn fingerprint(scope: &str, request_body: &[u8]) -> String {
+    let mut input = Vec::with_capacity(scope.len() + 1 + request_body.len());
+    input.extend_from_slice(scope.as_bytes());
+    input.push(b'\n');
+    input.extend_from_slice(request_body);
+
+    opaque_token::digest(&input)
+}
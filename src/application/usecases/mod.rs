@@ -1,3 +1,18 @@
+pub mod api_keys;
+pub mod consents;
+pub mod data_exports;
+pub mod doctor_applications;
+pub mod doctor_profiles;
+pub mod emergency_accesses;
+pub mod guardianships;
 pub mod authentication;
 pub mod admin;
+pub mod idempotency;
+pub mod notifications;
+pub mod oauth;
+pub mod patient_profiles;
+pub mod policy;
+pub mod role_grants;
 pub mod users;
+pub mod erasures;
+pub mod care_assignments;
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::domain::{
+    repositories::notifications::NotificationsRepository,
+    value_objects::notifications_model::NotificationModel,
+};
+
+pub struct NotificationsUseCase<N>
+where
+    N: NotificationsRepository + Send + Sync,
+{
+    notifications_repository: Arc<N>,
+}
+
+impl<N> NotificationsUseCase<N>
+where
+    N: NotificationsRepository + Send + Sync,
+{
+    pub fn new(notifications_repository: Arc<N>) -> Self {
+        Self {
+            notifications_repository,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        recipient_id: i32,
+        unread_only: bool,
+    ) -> Result<Vec<NotificationModel>> {
+        Ok(self
+            .notifications_repository
+            .list_by_recipient_id(recipient_id, unread_only)
+            .await?
+            .into_iter()
+            .map(NotificationModel::from)
+            .collect())
+    }
+
+    /// Returns `false` when the notification is not the recipient's.
+    pub async fn mark_read(&self, id: i32, recipient_id: i32) -> Result<bool> {
+        self.notifications_repository
+            .mark_read(id, recipient_id)
+            .await
+    }
+}
@@ -0,0 +1,898 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use url::Url;
+
+use crate::{
+    config::config_loader::{
+        get_doctors_secret_env, get_oauth_secret_env, get_patients_secret_env,
+    },
+    domain::{
+        entities::{
+            oauth::{
+                InsertOAuthAuthorizationCodeEntity, OAuthClientEntity, RegisterOAuthClientEntity,
+                RegisterServiceAccountEntity, ServiceAccountEntity, UpsertOAuthConsentEntity,
+            },
+            sessions::InsertSessionEntity,
+        },
+        repositories::{
+            oauth::OAuthRepository, sessions::SessionsRepository, users::UsersRepository,
+        },
+        value_objects::oauth_model::{
+            AuthorizeRequestModel, ConsentDecisionModel, ConsentDetailsResponseModel,
+            IntrospectionResponseModel, JsonWebKeySetModel, OAuthClientModel, OAuthError,
+            OpenIdConfigurationModel, RegisterOAuthClientModel, RegisterOAuthClientResponseModel,
+            RegisterServiceAccountModel, RegisterServiceAccountResponseModel, SERVICE_SCOPES,
+            SUPPORTED_SCOPES, ServiceAccountModel, TokenIntrospectionRequestModel,
+            TokenRequestModel, TokenResponseModel, TokenRevocationRequestModel,
+            UserInfoResponseModel,
+        },
+    },
+    infrastructure::{
+        argon2_hashing,
+        jwt_authentication::{
+            self,
+            jwt_model::{IdTokenClaims, OAuthClaims},
+        },
+        opaque_token,
+    },
+};
+
+const AUTHORIZATION_CODE_TTL_MINUTES: i64 = 5;
+const ACCESS_TOKEN_TTL_MINUTES: i64 = 60;
+const SERVICE_TOKEN_TTL_MINUTES: i64 = 15;
+const CLIENT_CREDENTIALS_GRANT: &str = "client_credentials";
+
+/// Claims common to every token this service issues, used by introspection and revocation.
+struct IssuedToken {
+    sub: String,
+    exp: usize,
+    iat: usize,
+    sid: Option<String>,
+    client_id: Option<String>,
+    scope: Option<String>,
+    token_type: String,
+    is_service: bool,
+}
+
+pub struct OAuthUseCase<T, U, S>
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    users_repository: Arc<T>,
+    oauth_repository: Arc<U>,
+    sessions_repository: Arc<S>,
+}
+
+impl<T, U, S> OAuthUseCase<T, U, S>
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    pub fn new(
+        users_repository: Arc<T>,
+        oauth_repository: Arc<U>,
+        sessions_repository: Arc<S>,
+    ) -> Self {
+        Self {
+            users_repository,
+            oauth_repository,
+            sessions_repository,
+        }
+    }
+
+    pub fn openid_configuration(&self) -> Result<OpenIdConfigurationModel> {
+        let oauth_env = get_oauth_secret_env()?;
+        let issuer = oauth_env.issuer.trim_end_matches('/').to_string();
+
+        Ok(OpenIdConfigurationModel {
+            authorization_endpoint: format!("{}/oauth/authorize", issuer),
+            token_endpoint: format!("{}/oauth/token", issuer),
+            introspection_endpoint: format!("{}/oauth/introspect", issuer),
+            revocation_endpoint: format!("{}/oauth/revoke", issuer),
+            userinfo_endpoint: format!("{}/userinfo", issuer),
+            jwks_uri: format!("{}/oauth/jwks", issuer),
+            issuer,
+            response_types_supported: vec!["code".to_string()],
+            grant_types_supported: vec![
+                "authorization_code".to_string(),
+                CLIENT_CREDENTIALS_GRANT.to_string(),
+            ],
+            subject_types_supported: vec!["public".to_string()],
+            id_token_signing_alg_values_supported: vec!["RS256".to_string()],
+            scopes_supported: SUPPORTED_SCOPES.iter().map(|s| s.to_string()).collect(),
+            token_endpoint_auth_methods_supported: vec![
+                "client_secret_basic".to_string(),
+                "client_secret_post".to_string(),
+                "none".to_string(),
+            ],
+            code_challenge_methods_supported: vec!["S256".to_string()],
+            claims_supported: vec![
+                "sub".to_string(),
+                "given_name".to_string(),
+                "family_name".to_string(),
+                "phone_number".to_string(),
+            ],
+        })
+    }
+
+    /// Keys clients verify ID tokens with.
+    pub fn jwks(&self) -> Result<JsonWebKeySetModel> {
+        Ok(JsonWebKeySetModel {
+            keys: vec![jwt_authentication::id_token_jwk()?],
+        })
+    }
+
+    /// Returns the client redirect (with a fresh code) when the user already consented,
+    /// or `None` when the consent screen has to be shown first.
+    pub async fn authorize(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        authorize_model: AuthorizeRequestModel,
+    ) -> Result<Option<String>> {
+        let details = self
+            .consent_details(hospital_id, user_id, authorize_model.clone())
+            .await?;
+
+        if !details.already_granted {
+            return Ok(None);
+        }
+
+        let redirect_to = self
+            .issue_authorization_code(user_id, &authorize_model)
+            .await?;
+
+        Ok(Some(redirect_to))
+    }
+
+    pub async fn consent_details(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        authorize_model: AuthorizeRequestModel,
+    ) -> Result<ConsentDetailsResponseModel> {
+        let client = self
+            .validate_authorize_request(hospital_id, &authorize_model)
+            .await?;
+        let scopes = authorize_model.scopes();
+
+        let already_granted = self
+            .oauth_repository
+            .find_consent(user_id, client.client_id.clone())
+            .await?
+            .map(|consent| scopes.iter().all(|scope| consent.scopes.contains(scope)))
+            .unwrap_or(false);
+
+        Ok(ConsentDetailsResponseModel {
+            client_id: client.client_id,
+            client_name: client.name,
+            scopes,
+            already_granted,
+        })
+    }
+
+    pub fn consent_url(&self, authorize_model: &AuthorizeRequestModel) -> Result<String> {
+        let oauth_env = get_oauth_secret_env()?;
+        let mut url = Url::parse(&oauth_env.consent_url)?;
+
+        url.query_pairs_mut()
+            .append_pair("response_type", &authorize_model.response_type)
+            .append_pair("client_id", &authorize_model.client_id)
+            .append_pair("redirect_uri", &authorize_model.redirect_uri)
+            .append_pair("scope", &authorize_model.scope)
+            .append_pair("code_challenge", &authorize_model.code_challenge)
+            .append_pair(
+                "code_challenge_method",
+                &authorize_model.code_challenge_method,
+            );
+
+        if let Some(state) = &authorize_model.state {
+            url.query_pairs_mut().append_pair("state", state);
+        }
+        if let Some(nonce) = &authorize_model.nonce {
+            url.query_pairs_mut().append_pair("nonce", nonce);
+        }
+
+        Ok(url.to_string())
+    }
+
+    pub async fn decide_consent(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        consent_model: ConsentDecisionModel,
+    ) -> Result<String> {
+        let authorize_model = consent_model.request;
+        let client = self
+            .validate_authorize_request(hospital_id, &authorize_model)
+            .await?;
+
+        if !consent_model.approve {
+            let mut params = vec![("error", OAuthError::AccessDenied.to_string())];
+            if let Some(state) = &authorize_model.state {
+                params.push(("state", state.clone()));
+            }
+            return build_redirect(&authorize_model.redirect_uri, params);
+        }
+
+        let now = Utc::now().naive_utc();
+        self.oauth_repository
+            .upsert_consent(UpsertOAuthConsentEntity {
+                user_id,
+                client_id: client.client_id,
+                scopes: authorize_model.scopes(),
+                created_at: now,
+                updated_at: now,
+            })
+            .await?;
+
+        self.issue_authorization_code(user_id, &authorize_model)
+            .await
+    }
+
+    pub async fn revoke_consent(&self, user_id: i32, client_id: String) -> Result<()> {
+        self.oauth_repository
+            .revoke_consent(user_id, client_id)
+            .await
+    }
+
+    pub async fn token(
+        &self,
+        hospital_id: i32,
+        basic_credentials: Option<(String, String)>,
+        token_model: TokenRequestModel,
+    ) -> Result<TokenResponseModel> {
+        match token_model.grant_type.as_str() {
+            "authorization_code" => {
+                self.exchange_authorization_code(hospital_id, basic_credentials, token_model)
+                    .await
+            }
+            CLIENT_CREDENTIALS_GRANT => {
+                self.exchange_client_credentials(hospital_id, basic_credentials, token_model)
+                    .await
+            }
+            _ => Err(OAuthError::UnsupportedGrantType.into()),
+        }
+    }
+
+    pub async fn userinfo(&self, access_token: String) -> Result<UserInfoResponseModel> {
+        let oauth_env = get_oauth_secret_env()?;
+        let claims = jwt_authentication::verify_oauth_token(
+            oauth_env.secret,
+            &oauth_env.issuer,
+            access_token,
+        )?;
+
+        if !self.is_session_active(&claims.sid).await? {
+            return Err(anyhow::anyhow!("Session has been revoked"));
+        }
+
+        let scopes: Vec<&str> = claims.scope.split_whitespace().collect();
+        let user = self
+            .users_repository
+            .find_by_id(claims.sub.parse()?)
+            .await?;
+
+        let with_profile = scopes.contains(&"profile");
+        let with_phone = scopes.contains(&"phone");
+
+        Ok(UserInfoResponseModel {
+            sub: claims.sub,
+            given_name: with_profile.then_some(user.first_name),
+            family_name: with_profile.then_some(user.last_name),
+            phone_number: with_phone.then_some(user.phone_number),
+        })
+    }
+
+    /// User tokens report the roles held at the hospital the introspection is made to.
+    pub async fn introspect(
+        &self,
+        hospital_id: i32,
+        basic_credentials: Option<(String, String)>,
+        introspection_model: TokenIntrospectionRequestModel,
+    ) -> Result<IntrospectionResponseModel> {
+        self.authenticate_service_client(
+            hospital_id,
+            basic_credentials,
+            introspection_model.client_id,
+            introspection_model.client_secret,
+        )
+        .await?;
+
+        let Some(issued_token) = decode_issued_token(&introspection_model.token) else {
+            return Ok(IntrospectionResponseModel::inactive());
+        };
+
+        if issued_token.is_service {
+            let service_account = match self
+                .oauth_repository
+                .find_service_account_by_client_id(issued_token.sub.clone())
+                .await
+            {
+                Ok(service_account) if service_account.hospital_id == hospital_id => {
+                    service_account
+                }
+                _ => return Ok(IntrospectionResponseModel::inactive()),
+            };
+            let scopes = service_token_scopes(
+                &service_account,
+                issued_token.scope.as_deref().unwrap_or_default(),
+            );
+
+            return Ok(IntrospectionResponseModel {
+                active: true,
+                sub: Some(issued_token.sub),
+                roles: None,
+                exp: Some(issued_token.exp),
+                iat: Some(issued_token.iat),
+                sid: None,
+                client_id: issued_token.client_id,
+                scope: Some(scopes.join(" ")),
+                token_type: Some(issued_token.token_type),
+            });
+        }
+
+        if !self.is_session_active(&issued_token.sid).await? {
+            return Ok(IntrospectionResponseModel::inactive());
+        }
+
+        let Ok(user_id) = issued_token.sub.parse::<i32>() else {
+            return Ok(IntrospectionResponseModel::inactive());
+        };
+
+        match self.users_repository.find_by_id(user_id).await {
+            Ok(user) if user.deleted_at.is_none() => {}
+            _ => return Ok(IntrospectionResponseModel::inactive()),
+        };
+        let roles = self
+            .users_repository
+            .find_roles(hospital_id, user_id)
+            .await?;
+
+        Ok(IntrospectionResponseModel {
+            active: true,
+            sub: Some(issued_token.sub),
+            roles: Some(roles),
+            exp: Some(issued_token.exp),
+            iat: Some(issued_token.iat),
+            sid: issued_token.sid,
+            client_id: issued_token.client_id,
+            scope: issued_token.scope,
+            token_type: Some(issued_token.token_type),
+        })
+    }
+
+    /// Revokes the session behind a token (RFC 7009). Unknown or invalid tokens are ignored.
+    pub async fn revoke(
+        &self,
+        hospital_id: i32,
+        basic_credentials: Option<(String, String)>,
+        revocation_model: TokenRevocationRequestModel,
+    ) -> Result<()> {
+        let client = self
+            .authenticate_service_client(
+                hospital_id,
+                basic_credentials,
+                revocation_model.client_id,
+                revocation_model.client_secret,
+            )
+            .await?;
+
+        let Some(issued_token) = decode_issued_token(&revocation_model.token) else {
+            return Ok(());
+        };
+
+        // token ของ OAuth client อื่นห้ามเพิกถอนแทนกัน
+        if issued_token
+            .client_id
+            .as_ref()
+            .is_some_and(|client_id| client_id != &client.client_id)
+        {
+            return Ok(());
+        }
+
+        if let Some(session_id) = issued_token.sid {
+            self.sessions_repository.revoke_by_id(session_id).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn register_client(
+        &self,
+        hospital_id: i32,
+        register_client_model: RegisterOAuthClientModel,
+    ) -> Result<RegisterOAuthClientResponseModel> {
+        if register_client_model.redirect_uris.is_empty() {
+            return Err(anyhow::anyhow!("At least one redirect uri is required"));
+        }
+
+        for redirect_uri in register_client_model.redirect_uris.iter() {
+            Url::parse(redirect_uri)
+                .map_err(|_| anyhow::anyhow!("Invalid redirect uri: {}", redirect_uri))?;
+        }
+
+        if let Some(scope) = register_client_model
+            .allowed_scopes
+            .iter()
+            .find(|scope| !SUPPORTED_SCOPES.contains(&scope.as_str()))
+        {
+            return Err(anyhow::anyhow!("Unsupported scope: {}", scope));
+        }
+
+        let client_id = opaque_token::generate(24);
+        let client_secret = register_client_model
+            .confidential
+            .then(|| opaque_token::generate(48));
+
+        let hashed_client_secret = match &client_secret {
+            Some(secret) => Some(argon2_hashing::hash(secret.clone())?),
+            None => None,
+        };
+
+        let now = Utc::now().naive_utc();
+        self.oauth_repository
+            .register_client(RegisterOAuthClientEntity {
+                client_id: client_id.clone(),
+                client_secret: hashed_client_secret,
+                name: register_client_model.name,
+                redirect_uris: register_client_model.redirect_uris,
+                allowed_scopes: register_client_model.allowed_scopes,
+                created_at: now,
+                updated_at: now,
+                hospital_id,
+            })
+            .await?;
+
+        Ok(RegisterOAuthClientResponseModel {
+            client_id,
+            client_secret,
+        })
+    }
+
+    pub async fn list_clients(&self, hospital_id: i32) -> Result<Vec<OAuthClientModel>> {
+        let clients = self.oauth_repository.list_clients(hospital_id).await?;
+
+        Ok(clients.into_iter().map(OAuthClientModel::from).collect())
+    }
+
+    pub async fn remove_client(&self, hospital_id: i32, client_id: String) -> Result<()> {
+        self.oauth_repository
+            .remove_client_by_client_id(hospital_id, client_id)
+            .await
+    }
+
+    pub async fn register_service_account(
+        &self,
+        hospital_id: i32,
+        created_by: i32,
+        register_service_account_model: RegisterServiceAccountModel,
+    ) -> Result<RegisterServiceAccountResponseModel> {
+        if register_service_account_model.scopes.is_empty() {
+            return Err(anyhow::anyhow!("At least one scope is required"));
+        }
+
+        if let Some(scope) = register_service_account_model
+            .scopes
+            .iter()
+            .find(|scope| !SERVICE_SCOPES.contains(&scope.as_str()))
+        {
+            return Err(anyhow::anyhow!("Unsupported scope: {}", scope));
+        }
+
+        let client_id = format!("svc_{}", opaque_token::generate(24));
+        let client_secret = opaque_token::generate(48);
+
+        let now = Utc::now().naive_utc();
+        self.oauth_repository
+            .register_service_account(RegisterServiceAccountEntity {
+                client_id: client_id.clone(),
+                client_secret: argon2_hashing::hash(client_secret.clone())?,
+                name: register_service_account_model.name,
+                scopes: register_service_account_model.scopes,
+                created_by: Some(created_by),
+                created_at: now,
+                updated_at: now,
+                hospital_id,
+            })
+            .await?;
+
+        Ok(RegisterServiceAccountResponseModel {
+            client_id,
+            client_secret,
+        })
+    }
+
+    pub async fn list_service_accounts(
+        &self,
+        hospital_id: i32,
+    ) -> Result<Vec<ServiceAccountModel>> {
+        let service_accounts = self
+            .oauth_repository
+            .list_service_accounts(hospital_id)
+            .await?;
+
+        Ok(service_accounts
+            .into_iter()
+            .map(ServiceAccountModel::from)
+            .collect())
+    }
+
+    pub async fn remove_service_account(&self, hospital_id: i32, client_id: String) -> Result<()> {
+        self.oauth_repository
+            .remove_service_account_by_client_id(hospital_id, client_id)
+            .await
+    }
+
+    async fn validate_authorize_request(
+        &self,
+        hospital_id: i32,
+        authorize_model: &AuthorizeRequestModel,
+    ) -> Result<OAuthClientEntity> {
+        if authorize_model.response_type != "code" {
+            return Err(OAuthError::UnsupportedResponseType.into());
+        }
+
+        let client = self
+            .find_client(hospital_id, authorize_model.client_id.clone())
+            .await?;
+
+        if !client.redirect_uris.contains(&authorize_model.redirect_uri) {
+            return Err(OAuthError::InvalidRequest.into());
+        }
+
+        // รองรับเฉพาะ PKCE แบบ S256 ทั้ง public และ confidential client
+        if authorize_model.code_challenge_method != "S256"
+            || authorize_model.code_challenge.is_empty()
+        {
+            return Err(OAuthError::InvalidRequest.into());
+        }
+
+        let scopes = authorize_model.scopes();
+        if !scopes.iter().any(|scope| scope == "openid")
+            || scopes
+                .iter()
+                .any(|scope| !client.allowed_scopes.contains(scope))
+        {
+            return Err(OAuthError::InvalidScope.into());
+        }
+
+        Ok(client)
+    }
+
+    async fn issue_authorization_code(
+        &self,
+        user_id: i32,
+        authorize_model: &AuthorizeRequestModel,
+    ) -> Result<String> {
+        let code = opaque_token::generate(48);
+        let now = Utc::now().naive_utc();
+
+        self.oauth_repository
+            .create_authorization_code(InsertOAuthAuthorizationCodeEntity {
+                code: opaque_token::digest(&code),
+                client_id: authorize_model.client_id.clone(),
+                user_id,
+                redirect_uri: authorize_model.redirect_uri.clone(),
+                scopes: authorize_model.scopes(),
+                code_challenge: authorize_model.code_challenge.clone(),
+                code_challenge_method: authorize_model.code_challenge_method.clone(),
+                nonce: authorize_model.nonce.clone(),
+                expires_at: now + Duration::minutes(AUTHORIZATION_CODE_TTL_MINUTES),
+                created_at: now,
+            })
+            .await?;
+
+        let mut params = vec![("code", code)];
+        if let Some(state) = &authorize_model.state {
+            params.push(("state", state.clone()));
+        }
+
+        build_redirect(&authorize_model.redirect_uri, params)
+    }
+
+    /// Clients registered at another hospital are unknown here.
+    async fn find_client(&self, hospital_id: i32, client_id: String) -> Result<OAuthClientEntity> {
+        match self
+            .oauth_repository
+            .find_client_by_client_id(client_id)
+            .await
+        {
+            Ok(client) if client.hospital_id == hospital_id => Ok(client),
+            _ => Err(OAuthError::InvalidClient.into()),
+        }
+    }
+
+    async fn authenticate_client(
+        &self,
+        hospital_id: i32,
+        basic_credentials: Option<(String, String)>,
+        client_id: Option<String>,
+        client_secret: Option<String>,
+    ) -> Result<OAuthClientEntity> {
+        let (client_id, client_secret) = match basic_credentials {
+            Some((client_id, client_secret)) => (client_id, Some(client_secret)),
+            None => (client_id.ok_or(OAuthError::InvalidClient)?, client_secret),
+        };
+
+        let client = self.find_client(hospital_id, client_id).await?;
+
+        if let Some(hashed_secret) = &client.client_secret {
+            let client_secret = client_secret.ok_or(OAuthError::InvalidClient)?;
+            if !argon2_hashing::verify(client_secret, hashed_secret.clone())? {
+                return Err(OAuthError::InvalidClient.into());
+            }
+        }
+
+        Ok(client)
+    }
+
+    /// Introspection and revocation are only open to confidential clients.
+    async fn authenticate_service_client(
+        &self,
+        hospital_id: i32,
+        basic_credentials: Option<(String, String)>,
+        client_id: Option<String>,
+        client_secret: Option<String>,
+    ) -> Result<OAuthClientEntity> {
+        let client = self
+            .authenticate_client(hospital_id, basic_credentials, client_id, client_secret)
+            .await?;
+
+        if client.client_secret.is_none() {
+            return Err(OAuthError::InvalidClient.into());
+        }
+
+        Ok(client)
+    }
+
+    async fn is_session_active(&self, session_id: &Option<String>) -> Result<bool> {
+        match session_id {
+            Some(session_id) => Ok(self
+                .sessions_repository
+                .find_active_by_id(session_id.clone())
+                .await?
+                .is_some()),
+            None => Ok(true),
+        }
+    }
+
+    async fn exchange_authorization_code(
+        &self,
+        hospital_id: i32,
+        basic_credentials: Option<(String, String)>,
+        token_model: TokenRequestModel,
+    ) -> Result<TokenResponseModel> {
+        let client = self
+            .authenticate_client(
+                hospital_id,
+                basic_credentials,
+                token_model.client_id.clone(),
+                token_model.client_secret.clone(),
+            )
+            .await?;
+
+        let code = token_model.code.ok_or(OAuthError::InvalidRequest)?;
+        let code_verifier = token_model
+            .code_verifier
+            .ok_or(OAuthError::InvalidRequest)?;
+
+        let authorization_code = self
+            .oauth_repository
+            .consume_authorization_code(opaque_token::digest(&code))
+            .await
+            .map_err(|_| OAuthError::InvalidGrant)?;
+
+        if authorization_code.client_id != client.client_id
+            || token_model.redirect_uri.as_deref() != Some(authorization_code.redirect_uri.as_str())
+            || authorization_code.expires_at < Utc::now().naive_utc()
+            || opaque_token::pkce_challenge(&code_verifier) != authorization_code.code_challenge
+        {
+            return Err(OAuthError::InvalidGrant.into());
+        }
+
+        let user = self
+            .users_repository
+            .find_by_id(authorization_code.user_id)
+            .await?;
+
+        if user.deleted_at.is_some() {
+            return Err(OAuthError::InvalidGrant.into());
+        }
+
+        let oauth_env = get_oauth_secret_env()?;
+        let scope = authorization_code.scopes.join(" ");
+        let with_profile = authorization_code.scopes.iter().any(|s| s == "profile");
+        let with_phone = authorization_code.scopes.iter().any(|s| s == "phone");
+
+        let now = Utc::now();
+        let expires_at = now + Duration::minutes(ACCESS_TOKEN_TTL_MINUTES);
+        let exp = expires_at.timestamp() as usize;
+
+        let session_id = self
+            .sessions_repository
+            .create(InsertSessionEntity {
+                id: opaque_token::generate(32),
+                user_id: user.id,
+                client_id: Some(client.client_id.clone()),
+                created_at: now.naive_utc(),
+                expires_at: expires_at.naive_utc(),
+            })
+            .await?;
+
+        let access_token_claims = OAuthClaims {
+            iss: oauth_env.issuer.clone(),
+            sub: user.id.to_string(),
+            aud: client.client_id.clone(),
+            scope: scope.clone(),
+            exp,
+            iat: now.timestamp() as usize,
+            sid: Some(session_id),
+            gty: Some("authorization_code".to_string()),
+        };
+
+        let id_token_claims = IdTokenClaims {
+            iss: oauth_env.issuer,
+            sub: user.id.to_string(),
+            aud: client.client_id,
+            exp,
+            iat: now.timestamp() as usize,
+            nonce: authorization_code.nonce,
+            given_name: with_profile.then_some(user.first_name),
+            family_name: with_profile.then_some(user.last_name),
+            phone_number: with_phone.then_some(user.phone_number),
+        };
+
+        let access_token =
+            jwt_authentication::generate_token(oauth_env.secret, &access_token_claims)?;
+        let id_token = jwt_authentication::generate_id_token(&id_token_claims)?;
+
+        Ok(TokenResponseModel {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: ACCESS_TOKEN_TTL_MINUTES * 60,
+            scope,
+            id_token: Some(id_token),
+        })
+    }
+
+    async fn exchange_client_credentials(
+        &self,
+        hospital_id: i32,
+        basic_credentials: Option<(String, String)>,
+        token_model: TokenRequestModel,
+    ) -> Result<TokenResponseModel> {
+        let (client_id, client_secret) = match basic_credentials {
+            Some(credentials) => credentials,
+            None => (
+                token_model.client_id.ok_or(OAuthError::InvalidClient)?,
+                token_model.client_secret.ok_or(OAuthError::InvalidClient)?,
+            ),
+        };
+
+        let service_account = self
+            .oauth_repository
+            .find_service_account_by_client_id(client_id)
+            .await
+            .map_err(|_| OAuthError::InvalidClient)?;
+
+        if service_account.hospital_id != hospital_id {
+            return Err(OAuthError::InvalidClient.into());
+        }
+
+        if !argon2_hashing::verify(client_secret, service_account.client_secret.clone())? {
+            return Err(OAuthError::InvalidClient.into());
+        }
+
+        // ไม่ระบุ scope มา = ได้ทุก scope ที่ service account มี
+        let scopes: Vec<String> = match token_model.scope {
+            Some(scope) => scope.split_whitespace().map(str::to_string).collect(),
+            None => service_account.scopes.clone(),
+        };
+
+        if scopes.is_empty()
+            || scopes
+                .iter()
+                .any(|scope| !service_account.scopes.contains(scope))
+        {
+            return Err(OAuthError::InvalidScope.into());
+        }
+
+        let oauth_env = get_oauth_secret_env()?;
+        let scope = scopes.join(" ");
+        let now = Utc::now();
+
+        let access_token_claims = OAuthClaims {
+            iss: oauth_env.issuer,
+            sub: service_account.client_id.clone(),
+            aud: service_account.client_id,
+            scope: scope.clone(),
+            exp: (now + Duration::minutes(SERVICE_TOKEN_TTL_MINUTES)).timestamp() as usize,
+            iat: now.timestamp() as usize,
+            sid: None,
+            gty: Some(CLIENT_CREDENTIALS_GRANT.to_string()),
+        };
+
+        let access_token =
+            jwt_authentication::generate_token(oauth_env.secret, &access_token_claims)?;
+
+        Ok(TokenResponseModel {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: SERVICE_TOKEN_TTL_MINUTES * 60,
+            scope,
+            id_token: None,
+        })
+    }
+}
+
+/// Tries every secret this service signs with and returns the first token that verifies.
+fn decode_issued_token(token: &str) -> Option<IssuedToken> {
+    let patients_secret = get_patients_secret_env().ok()?;
+    let doctors_secret = get_doctors_secret_env().ok()?;
+    let oauth_env = get_oauth_secret_env().ok()?;
+
+    let first_party_secrets = [
+        (patients_secret.secret, "access_token"),
+        (patients_secret.refresh_secret, "refresh_token"),
+        (doctors_secret.secret, "access_token"),
+        (doctors_secret.refresh_secret, "refresh_token"),
+    ];
+
+    for (secret, token_type) in first_party_secrets {
+        if let Ok(claims) = jwt_authentication::verify_token(secret, token.to_string()) {
+            return Some(IssuedToken {
+                sub: claims.sub,
+                exp: claims.exp,
+                iat: claims.iat,
+                sid: claims.sid,
+                client_id: None,
+                scope: None,
+                token_type: token_type.to_string(),
+                is_service: false,
+            });
+        }
+    }
+
+    let claims = jwt_authentication::verify_oauth_token(
+        oauth_env.secret,
+        &oauth_env.issuer,
+        token.to_string(),
+    )
+    .ok()?;
+
+    Some(IssuedToken {
+        is_service: claims.gty.as_deref() == Some(CLIENT_CREDENTIALS_GRANT),
+        sub: claims.sub,
+        exp: claims.exp,
+        iat: claims.iat,
+        sid: claims.sid,
+        client_id: Some(claims.aud),
+        scope: Some(claims.scope),
+        token_type: "access_token".to_string(),
+    })
+}
+
+/// Scopes a service token still grants: those of `scope` the service account still has.
+pub fn service_token_scopes(service_account: &ServiceAccountEntity, scope: &str) -> Vec<String> {
+    scope
+        .split_whitespace()
+        .filter(|scope| service_account.scopes.iter().any(|held| held == scope))
+        .map(str::to_string)
+        .collect()
+}
+
+fn build_redirect(redirect_uri: &str, params: Vec<(&str, String)>) -> Result<String> {
+    let mut url = Url::parse(redirect_uri)?;
+
+    for (key, value) in params {
+        url.query_pairs_mut().append_pair(key, &value);
+    }
+
+    Ok(url.to_string())
+}
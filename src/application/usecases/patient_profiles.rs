@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use validator::Validate;
+
+use crate::domain::{
+    repositories::patient_profiles::PatientProfilesRepository,
+    value_objects::patient_profiles_model::{PatientProfileModel, UpsertPatientProfileModel},
+};
+
+pub struct PatientProfilesUseCase<P>
+where
+    P: PatientProfilesRepository + Send + Sync,
+{
+    patient_profiles_repository: Arc<P>,
+}
+
+impl<P> PatientProfilesUseCase<P>
+where
+    P: PatientProfilesRepository + Send + Sync,
+{
+    pub fn new(patient_profiles_repository: Arc<P>) -> Self {
+        Self {
+            patient_profiles_repository,
+        }
+    }
+
+    pub async fn find_by_user_id(&self, user_id: i32) -> Result<Option<PatientProfileModel>> {
+        self.patient_profiles_repository
+            .find_by_user_id(user_id)
+            .await?
+            .map(PatientProfileModel::try_from)
+            .transpose()
+    }
+
+    /// Fails with `validator::ValidationErrors` for invalid input and with
+    /// `RepositoryError::Conflict` when the profile already exists.
+    pub async fn create(
+        &self,
+        user_id: i32,
+        upsert_patient_profile_model: UpsertPatientProfileModel,
+    ) -> Result<PatientProfileModel> {
+        upsert_patient_profile_model.validate()?;
+        let entity = upsert_patient_profile_model
+            .normalized()
+            .to_entity(user_id)?;
+
+        self.patient_profiles_repository
+            .create(entity)
+            .await?
+            .try_into()
+    }
+
+    /// Replaces the whole profile. Returns `None` when there is no profile to update.
+    pub async fn update(
+        &self,
+        user_id: i32,
+        upsert_patient_profile_model: UpsertPatientProfileModel,
+    ) -> Result<Option<PatientProfileModel>> {
+        upsert_patient_profile_model.validate()?;
+        let entity = upsert_patient_profile_model
+            .normalized()
+            .to_entity(user_id)?;
+
+        self.patient_profiles_repository
+            .update(entity)
+            .await?
+            .map(PatientProfileModel::try_from)
+            .transpose()
+    }
+
+    pub async fn delete(&self, user_id: i32) -> Result<bool> {
+        self.patient_profiles_repository
+            .delete_by_user_id(user_id)
+            .await
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::domain::{
+    repositories::{permissions::PermissionsRepository, users::UsersRepository},
+    value_objects::{
+        policy::{self, ExplainPolicyModel, Permission, PolicyDecision, PolicySubject},
+        principal::Principal,
+    },
+};
+
+pub struct PolicyUseCase<U, P>
+where
+    U: UsersRepository + Send + Sync,
+    P: PermissionsRepository + Send + Sync,
+{
+    users_repository: Arc<U>,
+    permissions_repository: Arc<P>,
+}
+
+impl<U, P> PolicyUseCase<U, P>
+where
+    U: UsersRepository + Send + Sync,
+    P: PermissionsRepository + Send + Sync,
+{
+    pub fn new(users_repository: Arc<U>, permissions_repository: Arc<P>) -> Self {
+        Self {
+            users_repository,
+            permissions_repository,
+        }
+    }
+
+    /// Users are judged on the roles they hold at the hospital; service accounts and API keys
+    /// only work at the hospital they were created at.
+    pub async fn authorize(
+        &self,
+        hospital_id: i32,
+        principal: Principal,
+        permission: Permission,
+        resource_owner_id: Option<i32>,
+    ) -> Result<PolicyDecision> {
+        let subject = self.subject(hospital_id, principal).await?;
+
+        Ok(policy::evaluate(&subject, permission, resource_owner_id))
+    }
+
+    /// The decision `authorize` would make for the user, for debugging access problems.
+    pub async fn explain(
+        &self,
+        hospital_id: i32,
+        explain_policy_model: ExplainPolicyModel,
+    ) -> Result<PolicyDecision> {
+        self.authorize(
+            hospital_id,
+            Principal::User {
+                user_id: explain_policy_model.user_id,
+            },
+            explain_policy_model.permission,
+            explain_policy_model.resource_owner_id,
+        )
+        .await
+    }
+
+    async fn subject(&self, hospital_id: i32, principal: Principal) -> Result<PolicySubject> {
+        let user_id = match principal {
+            Principal::User { user_id } => user_id,
+            Principal::Delegate { user_id, .. } => {
+                // ผู้ปกครองได้แค่สิทธิ์เจ้าของข้อมูล ไม่ได้ role ของผู้อยู่ในความดูแล
+                let user = self.users_repository.find_by_id(user_id).await?;
+
+                return Ok(PolicySubject {
+                    principal,
+                    hospital_id,
+                    roles: Vec::new(),
+                    role_permissions: Vec::new(),
+                    care_patient_ids: Vec::new(),
+                    emergency_patient_ids: Vec::new(),
+                    active: user.deleted_at.is_none(),
+                });
+            }
+            // service กับ API key ได้สิทธิ์จาก scope อย่างเดียว และเฉพาะโรงพยาบาลที่สร้างไว้
+            Principal::Service { .. } | Principal::ApiKey { .. } => {
+                return Ok(PolicySubject {
+                    principal,
+                    hospital_id,
+                    roles: Vec::new(),
+                    role_permissions: Vec::new(),
+                    care_patient_ids: Vec::new(),
+                    emergency_patient_ids: Vec::new(),
+                    active: true,
+                });
+            }
+        };
+
+        let user = self.users_repository.find_by_id(user_id).await?;
+        let roles = self
+            .users_repository
+            .find_roles(hospital_id, user_id)
+            .await?;
+        let role_permissions = self
+            .permissions_repository
+            .find_by_user_id(hospital_id, user_id)
+            .await?;
+        let care_patient_ids = self
+            .permissions_repository
+            .find_care_patient_ids(hospital_id, user_id)
+            .await?;
+        let emergency_patient_ids = self
+            .permissions_repository
+            .find_emergency_patient_ids(hospital_id, user_id)
+            .await?;
+
+        Ok(PolicySubject {
+            principal,
+            hospital_id,
+            roles,
+            role_permissions,
+            care_patient_ids,
+            emergency_patient_ids,
+            active: user.deleted_at.is_none(),
+        })
+    }
+}
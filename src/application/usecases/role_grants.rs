@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+    repositories::role_grants::RoleGrantsRepository, value_objects::roles::RoleGrantSweepModel,
+};
+
+pub struct RoleGrantsUseCase<R>
+where
+    R: RoleGrantsRepository + Send + Sync,
+{
+    role_grants_repository: Arc<R>,
+}
+
+impl<R> RoleGrantsUseCase<R>
+where
+    R: RoleGrantsRepository + Send + Sync,
+{
+    pub fn new(role_grants_repository: Arc<R>) -> Self {
+        Self {
+            role_grants_repository,
+        }
+    }
+
+    /// Warns about grants ending within `notice`, then expires the ones that have ended.
+    pub async fn sweep(&self, notice: Duration) -> Result<RoleGrantSweepModel> {
+        let now = Utc::now().naive_utc();
+
+        let notified = self
+            .role_grants_repository
+            .notify_expiring(now, now + notice)
+            .await?;
+        let (expired, revoked_sessions) = self.role_grants_repository.expire_due(now).await?;
+
+        Ok(RoleGrantSweepModel {
+            notified,
+            expired,
+            revoked_sessions,
+        })
+    }
+}
@@ -1,15 +1,32 @@
 use std::sync::Arc;
 
 use anyhow::Result;
+use validator::{Validate, ValidationError, ValidationErrors};
 
 use crate::{
     domain::{
-        entities::users::UserEntity, repositories::users::UsersRepository,
-        value_objects::users_model::RegisterUserModel,
+        repositories::{errors::RepositoryError, users::UsersRepository},
+        value_objects::{
+            consents_model::{ConsentChannel, RegistrationConsents},
+            pagination::{self, PageModel},
+            roles::Role,
+            users_model::{
+                FindUserByIdResponseModel, ListUsersQueryModel, RegisterUserModel,
+                RegisterUserResponseModel, SearchUsersQueryModel, UserCursor, UserListFilter,
+                UserSearchResultModel,
+            },
+            validation::{
+                normalize_citizen_id, normalize_hospital_number, normalize_phone_number,
+                validate_hospital_number,
+            },
+        },
     },
-    infrastructure::argon2_hashing,
+    infrastructure::{argon2_hashing, transliteration},
 };
 
+/// Unique constraint on the citizen ID blind index.
+const CITIZEN_ID_CONSTRAINT: &str = "users_citizen_id_hash_key";
+
 pub struct UsersUseCase<T>
 where
     T: UsersRepository + Send + Sync,
@@ -25,21 +42,337 @@ where
         Self { users_repository }
     }
 
-    pub async fn register(&self, mut register_user_model: RegisterUserModel) -> Result<i32> {
-        let hashed_password = argon2_hashing::hash(register_user_model.password.clone())?;
+    /// Registers a patient at the hospital. A patient who already has an account, e.g. from
+    /// another hospital, is registered here with it when the password matches. Fails with
+    /// `validator::ValidationErrors` when the input does not pass validation, or with
+    /// `RepositoryError::Conflict` when the citizen ID is taken otherwise.
+    pub async fn register(
+        &self,
+        hospital_id: i32,
+        register_user_model: RegisterUserModel,
+    ) -> Result<RegisterUserResponseModel> {
+        self.register_patient(hospital_id, register_user_model, None)
+            .await
+    }
+
+    /// Registers a patient for someone at the front desk. The account is the patient's own; the
+    /// staff member is recorded as having registered it. A patient who already has an account
+    /// is registered here with it, since the staff member checks their identity in person.
+    pub async fn register_on_behalf(
+        &self,
+        hospital_id: i32,
+        staff_id: i32,
+        register_user_model: RegisterUserModel,
+    ) -> Result<RegisterUserResponseModel> {
+        self.register_patient(hospital_id, register_user_model, Some(staff_id))
+            .await
+    }
+
+    async fn register_patient(
+        &self,
+        hospital_id: i32,
+        register_user_model: RegisterUserModel,
+        registered_by: Option<i32>,
+    ) -> Result<RegisterUserResponseModel> {
+        register_user_model.validate()?;
+        let mut register_user_model = register_user_model.normalized();
+
+        let password = register_user_model.password.clone();
+        let hashed_password = argon2_hashing::hash(password.clone())?;
 
         register_user_model.password = hashed_password;
 
         let register_entity = register_user_model.to_entity();
+        let mut consents = register_user_model.to_registration_consents();
+        // ลงทะเบียนแทนที่เคาน์เตอร์ ผู้ป่วยให้ความยินยอมต่อหน้าเจ้าหน้าที่
+        if registered_by.is_some() {
+            consents.channel = ConsentChannel::InPerson;
+        }
+
+        let result = self
+            .users_repository
+            .register(
+                hospital_id,
+                register_entity,
+                Role::Patient,
+                registered_by,
+                consents.clone(),
+            )
+            .await;
 
-        let user_id = self.users_repository.register(register_entity).await?;
+        let is_citizen_id_taken = match &result {
+            Err(e) => matches!(
+                e.downcast_ref::<RepositoryError>(),
+                Some(RepositoryError::Conflict { constraint })
+                    if constraint == CITIZEN_ID_CONSTRAINT
+            ),
+            Ok(_) => false,
+        };
+        if !is_citizen_id_taken {
+            return result;
+        }
 
-        Ok(user_id)
+        match self
+            .register_existing_patient(
+                hospital_id,
+                register_user_model,
+                password,
+                registered_by,
+                consents,
+            )
+            .await?
+        {
+            Some(registered) => Ok(registered),
+            None => result,
+        }
     }
 
-    pub async fn find_by_id(&self, user_id: i32) -> Result<UserEntity> {
+    /// `None` when the account cannot be used here: it was deleted, the password (or, at the
+    /// front desk, the name and date of birth) does not match, or it is already registered at
+    /// the hospital.
+    async fn register_existing_patient(
+        &self,
+        hospital_id: i32,
+        register_user_model: RegisterUserModel,
+        password: String,
+        registered_by: Option<i32>,
+        consents: RegistrationConsents,
+    ) -> Result<Option<RegisterUserResponseModel>> {
+        let Some(user) = self
+            .users_repository
+            .find_by_citizen_id(register_user_model.citizen_id.clone())
+            .await?
+        else {
+            return Ok(None);
+        };
+        if user.deleted_at.is_some() {
+            return Ok(None);
+        }
+
+        let is_confirmed = match registered_by {
+            // สมัครเองต้องยืนยันด้วยรหัสผ่านของ account เดิม
+            None => argon2_hashing::verify(password, user.password.clone()).unwrap_or(false),
+            // เลขบัตรพิมพ์ผิดหลักเดียวอาจเจอ account ของคนอื่น จึงต้องตรงทั้งชื่อและวันเกิด
+            Some(_) => {
+                let date_of_birth = self.users_repository.find_date_of_birth(user.id).await?;
+
+                user.first_name.to_lowercase() == register_user_model.first_name.to_lowercase()
+                    && user.last_name.to_lowercase() == register_user_model.last_name.to_lowercase()
+                    && date_of_birth.is_some()
+                    && date_of_birth == register_user_model.date_of_birth
+            }
+        };
+        if !is_confirmed {
+            return Ok(None);
+        }
+
+        self.users_repository
+            .register_existing(hospital_id, user.id, Role::Patient, registered_by, consents)
+            .await
+    }
+
+    /// `None` when the user is not registered at the hospital.
+    pub async fn find_by_id(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Option<FindUserByIdResponseModel>> {
+        let Some(hospital_number) = self
+            .users_repository
+            .find_hospital_numbers(hospital_id, vec![user_id])
+            .await?
+            .remove(&user_id)
+        else {
+            return Ok(None);
+        };
+
         let user_entity = self.users_repository.find_by_id(user_id).await?;
+        let roles = self
+            .users_repository
+            .find_roles(hospital_id, user_id)
+            .await?;
+
+        Ok(Some(FindUserByIdResponseModel::new(
+            user_entity,
+            Some(hospital_number),
+            roles,
+        )))
+    }
+
+    /// Front-desk lookup by the number on the patient's card. Fails with
+    /// `validator::ValidationErrors` when the number is malformed or its check character is wrong;
+    /// `None` when no one at the hospital holds it.
+    pub async fn find_by_hospital_number(
+        &self,
+        hospital_id: i32,
+        hospital_number: String,
+    ) -> Result<Option<FindUserByIdResponseModel>> {
+        if let Err(error) = validate_hospital_number(&hospital_number) {
+            let mut errors = ValidationErrors::new();
+            errors.add("hospital_number", error);
+            return Err(errors.into());
+        }
+
+        let hospital_number = normalize_hospital_number(&hospital_number);
+
+        let Some(user_entity) = self
+            .users_repository
+            .find_by_hospital_number(hospital_id, hospital_number.clone())
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let roles = self
+            .users_repository
+            .find_roles(hospital_id, user_entity.id)
+            .await?;
+
+        Ok(Some(FindUserByIdResponseModel::new(
+            user_entity,
+            Some(hospital_number),
+            roles,
+        )))
+    }
+
+    /// Fails with `validator::ValidationErrors` for bad filters or a cursor from another sort.
+    pub async fn list(
+        &self,
+        hospital_id: i32,
+        list_users_query: ListUsersQueryModel,
+    ) -> Result<PageModel<FindUserByIdResponseModel>> {
+        list_users_query.validate()?;
+
+        let sort = list_users_query.sort.unwrap_or_default();
+        let direction = list_users_query.direction.unwrap_or_default();
+        let limit = pagination::clamp_limit(list_users_query.limit);
+
+        let after = match &list_users_query.cursor {
+            Some(cursor) => {
+                let cursor = pagination::decode_cursor::<UserCursor>(cursor)
+                    .ok()
+                    .filter(|cursor| cursor.sort() == sort)
+                    .ok_or_else(|| {
+                        let mut errors = ValidationErrors::new();
+                        errors.add(
+                            "cursor",
+                            ValidationError::new("cursor_invalid")
+                                .with_message("Cursor is malformed or from another sort".into()),
+                        );
+                        errors
+                    })?;
+                Some(cursor)
+            }
+            None => None,
+        };
+
+        // ขอเกินมา 1 แถวเพื่อรู้ว่ายังมีหน้าถัดไปไหม
+        let mut users = self
+            .users_repository
+            .list(UserListFilter {
+                hospital_id,
+                role: list_users_query
+                    .role
+                    .as_deref()
+                    .map(str::parse::<Role>)
+                    .transpose()?,
+                deleted: list_users_query.deleted.unwrap_or(false),
+                created_from: list_users_query.created_from,
+                created_to: list_users_query.created_to,
+                name: list_users_query.name.map(|name| name.trim().to_string()),
+                phone_number: list_users_query
+                    .phone_number
+                    .as_deref()
+                    .and_then(normalize_phone_number),
+                citizen_id_prefix: list_users_query
+                    .citizen_id_prefix
+                    .as_deref()
+                    .map(normalize_citizen_id),
+                sort,
+                direction,
+                after,
+                limit: limit + 1,
+            })
+            .await?;
+
+        let has_more = users.len() as i64 > limit;
+        users.truncate(limit as usize);
+
+        let next_cursor = match users.last() {
+            Some(last) if has_more => Some(pagination::encode_cursor(&UserCursor::from_user(
+                sort, last,
+            ))?),
+            _ => None,
+        };
+
+        let user_ids: Vec<i32> = users.iter().map(|user| user.id).collect();
+        let mut roles = self
+            .users_repository
+            .find_roles_by_user_ids(hospital_id, user_ids.clone())
+            .await?;
+        let mut hospital_numbers = self
+            .users_repository
+            .find_hospital_numbers(hospital_id, user_ids)
+            .await?;
+
+        Ok(PageModel {
+            items: users
+                .into_iter()
+                .map(|user| {
+                    let user_roles = roles.remove(&user.id).unwrap_or_default();
+                    let hospital_number = hospital_numbers.remove(&user.id);
+                    FindUserByIdResponseModel::new(user, hospital_number, user_roles)
+                })
+                .collect(),
+            next_cursor,
+            has_more,
+            limit,
+        })
+    }
+
+    /// Fuzzy name search, best match first. Fails with `validator::ValidationErrors` when the
+    /// query is too short.
+    pub async fn search(
+        &self,
+        hospital_id: i32,
+        search_users_query: SearchUsersQueryModel,
+    ) -> Result<Vec<UserSearchResultModel>> {
+        search_users_query.validate()?;
+
+        let name = search_users_query.q.trim().to_string();
+        let name_romanized = search_users_query
+            .transliterate
+            .unwrap_or(true)
+            .then(|| transliteration::romanize(&name))
+            .filter(|romanized| !romanized.is_empty());
+
+        let hits = self
+            .users_repository
+            .search_by_name(
+                hospital_id,
+                name,
+                name_romanized,
+                pagination::clamp_limit(search_users_query.limit),
+            )
+            .await?;
+
+        let user_ids: Vec<i32> = hits.iter().map(|hit| hit.user.id).collect();
+        let mut roles = self
+            .users_repository
+            .find_roles_by_user_ids(hospital_id, user_ids.clone())
+            .await?;
+        let mut hospital_numbers = self
+            .users_repository
+            .find_hospital_numbers(hospital_id, user_ids)
+            .await?;
 
-        Ok(user_entity)
+        Ok(hits
+            .into_iter()
+            .map(|hit| {
+                let user_roles = roles.remove(&hit.user.id).unwrap_or_default();
+                let hospital_number = hospital_numbers.remove(&hit.user.id);
+                UserSearchResultModel::new(hit, hospital_number, user_roles)
+            })
+            .collect())
     }
 }
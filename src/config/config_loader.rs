@@ -1,9 +1,12 @@
 use anyhow::Result;
 
-use crate::config::config_model::Frontend;
+use crate::config::config_model::{Frontend, Tls};
 
 use super::{
-    config_model::{Database, DoctorsSecret, DotEnvyConfig, PatientsSecret, Server},
+    config_model::{
+        DataExport, Database, DoctorsSecret, DotEnvyConfig, Erasure, FieldEncryption, Idempotency,
+        OAuthSecret, PatientsSecret, RoleGrantExpiry, Server, StaffSecret, Tenancy,
+    },
     stage::Stage,
 };
 
@@ -34,10 +37,24 @@ pub fn load() -> Result<DotEnvyConfig> {
         url: std::env::var("DATABASE_URL").expect("DATABASE_URL is invalid"),
     };
 
+    // ไม่ตั้ง TLS_CERT_PATH = เสิร์ฟ HTTP ธรรมดา (ใช้ตอน local)
+    let tls = match std::env::var("TLS_CERT_PATH") {
+        Ok(cert_path) => Some(Tls {
+            cert_path,
+            key_path: std::env::var("TLS_KEY_PATH").expect("TLS_KEY_PATH is invalid"),
+            client_ca_path: std::env::var("TLS_CLIENT_CA_PATH").ok(),
+            client_auth_required: std::env::var("TLS_CLIENT_AUTH_REQUIRED")
+                .map(|value| value.parse())
+                .unwrap_or(Ok(false))?,
+        }),
+        Err(_) => None,
+    };
+
     Ok(DotEnvyConfig {
         server,
         frontend,
         database,
+        tls,
     })
 }
 
@@ -67,3 +84,107 @@ pub fn get_doctors_secret_env() -> Result<DoctorsSecret> {
             .expect("JWT_DOCTOR_REFRESH_SECRET is invalid"),
     })
 }
+
+pub fn get_staff_secret_env() -> Result<StaffSecret> {
+    dotenvy::dotenv().ok();
+
+    Ok(StaffSecret {
+        secret: std::env::var("JWT_STAFF_SECRET").expect("JWT_STAFF_SECRET is invalid"),
+        refresh_secret: std::env::var("JWT_STAFF_REFRESH_SECRET")
+            .expect("JWT_STAFF_REFRESH_SECRET is invalid"),
+    })
+}
+
+pub fn get_oauth_secret_env() -> Result<OAuthSecret> {
+    dotenvy::dotenv().ok();
+
+    Ok(OAuthSecret {
+        issuer: std::env::var("OAUTH_ISSUER").expect("OAUTH_ISSUER is invalid"),
+        secret: std::env::var("JWT_OAUTH_SECRET").expect("JWT_OAUTH_SECRET is invalid"),
+        consent_url: std::env::var("OAUTH_CONSENT_URL").expect("OAUTH_CONSENT_URL is invalid"),
+        id_token_key_path: std::env::var("OAUTH_ID_TOKEN_KEY_PATH")
+            .expect("OAUTH_ID_TOKEN_KEY_PATH is invalid"),
+        id_token_public_key_path: std::env::var("OAUTH_ID_TOKEN_PUBLIC_KEY_PATH")
+            .expect("OAUTH_ID_TOKEN_PUBLIC_KEY_PATH is invalid"),
+    })
+}
+
+pub fn get_idempotency_env() -> Result<Idempotency> {
+    dotenvy::dotenv().ok();
+
+    Ok(Idempotency {
+        ttl_hours: std::env::var("IDEMPOTENCY_TTL_HOURS")
+            .unwrap_or("24".to_string())
+            .parse()?,
+        body_limit: std::env::var("SERVER_BODY_LIMIT")
+            .expect("SERVER_BODY_LIMIT is invalid")
+            .parse::<usize>()?
+            * 1024
+            * 1024,
+    })
+}
+
+pub fn get_role_grant_expiry_env() -> Result<RoleGrantExpiry> {
+    dotenvy::dotenv().ok();
+
+    Ok(RoleGrantExpiry {
+        interval_seconds: std::env::var("ROLE_GRANT_EXPIRY_INTERVAL_SECONDS")
+            .unwrap_or("60".to_string())
+            .parse()?,
+        notice_days: std::env::var("ROLE_GRANT_EXPIRY_NOTICE_DAYS")
+            .unwrap_or("7".to_string())
+            .parse()?,
+    })
+}
+
+pub fn get_tenancy_env() -> Result<Tenancy> {
+    dotenvy::dotenv().ok();
+
+    Ok(Tenancy {
+        base_domain: std::env::var("TENANT_BASE_DOMAIN")
+            .ok()
+            .filter(|base_domain| !base_domain.is_empty()),
+        default_hospital_code: std::env::var("DEFAULT_HOSPITAL_CODE")
+            .unwrap_or("main".to_string()),
+    })
+}
+
+pub fn get_field_encryption_env() -> Result<FieldEncryption> {
+    dotenvy::dotenv().ok();
+
+    Ok(FieldEncryption {
+        data_key_path: std::env::var("FIELD_ENCRYPTION_KEY_PATH")
+            .expect("FIELD_ENCRYPTION_KEY_PATH is invalid"),
+        blind_index_key_path: std::env::var("BLIND_INDEX_KEY_PATH")
+            .expect("BLIND_INDEX_KEY_PATH is invalid"),
+    })
+}
+
+pub fn get_data_export_env() -> Result<DataExport> {
+    dotenvy::dotenv().ok();
+
+    Ok(DataExport {
+        interval_seconds: std::env::var("DATA_EXPORT_INTERVAL_SECONDS")
+            .unwrap_or("30".to_string())
+            .parse()?,
+        sync_max_rows: std::env::var("DATA_EXPORT_SYNC_MAX_ROWS")
+            .unwrap_or("1000".to_string())
+            .parse()?,
+        ttl_hours: std::env::var("DATA_EXPORT_TTL_HOURS")
+            .unwrap_or("24".to_string())
+            .parse()?,
+        lease_minutes: std::env::var("DATA_EXPORT_LEASE_MINUTES")
+            .unwrap_or("10".to_string())
+            .parse()?,
+    })
+}
+
+pub fn get_erasure_env() -> Result<Erasure> {
+    dotenvy::dotenv().ok();
+
+    Ok(Erasure {
+        medical_record_retention_years: std::env::var("MEDICAL_RECORD_RETENTION_YEARS")
+            .unwrap_or("5".to_string())
+            .parse()?,
+    })
+}
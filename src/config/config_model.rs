@@ -3,6 +3,7 @@ pub struct DotEnvyConfig {
     pub server: Server,
     pub frontend: Frontend,
     pub database: Database,
+    pub tls: Option<Tls>,
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +25,14 @@ pub struct Database {
     pub url: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct Tls {
+    pub cert_path: String,
+    pub key_path: String,
+    pub client_ca_path: Option<String>,
+    pub client_auth_required: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct PatientsSecret {
     pub secret: String,
@@ -35,3 +44,71 @@ pub struct DoctorsSecret {
     pub secret: String,
     pub refresh_secret: String,
 }
+
+/// Signs tokens for nurses, pharmacists and receptionists; the role is in the claims.
+#[derive(Debug, Clone)]
+pub struct StaffSecret {
+    pub secret: String,
+    pub refresh_secret: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct OAuthSecret {
+    pub issuer: String,
+    pub secret: String,
+    pub consent_url: String,
+    /// PEM RSA private key that signs ID tokens (RS256).
+    pub id_token_key_path: String,
+    /// PEM public half of `id_token_key_path`, published at `/oauth/jwks`.
+    pub id_token_public_key_path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Idempotency {
+    pub ttl_hours: i64,
+    /// Largest request or response body, in bytes, that is stored for replay; from
+    /// `SERVER_BODY_LIMIT`.
+    pub body_limit: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoleGrantExpiry {
+    pub interval_seconds: u64,
+    /// How long before a grant ends that admins are notified.
+    pub notice_days: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tenancy {
+    /// Requests to `<code>.<base_domain>` are served for the hospital with that code.
+    pub base_domain: Option<String>,
+    /// Hospital served when the request names none, e.g. on a single-hospital deployment.
+    pub default_hospital_code: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldEncryption {
+    pub data_key_path: String,
+    pub blind_index_key_path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DataExport {
+    /// How often queued exports are generated and expired ones deleted.
+    pub interval_seconds: u64,
+    /// Exports of at most this many sessions, consent records and access log entries are
+    /// returned at once; larger ones are queued.
+    pub sync_max_rows: i64,
+    /// How long a queued export can be downloaded once ready.
+    pub ttl_hours: i64,
+    /// An export still processing this long after it was claimed, e.g. because the instance
+    /// generating it crashed, is claimed again.
+    pub lease_minutes: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Erasure {
+    /// How long after a patient's record was last updated it must be kept. Erasure is refused
+    /// until then.
+    pub medical_record_retention_years: i64,
+}
@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::api_keys;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = api_keys)]
+pub struct ApiKeyEntity {
+    pub id: i32,
+    pub prefix: String,
+    pub key_hash: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub allowed_ips: Vec<String>,
+    pub created_by: Option<i32>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub hospital_id: i32,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = api_keys)]
+pub struct InsertApiKeyEntity {
+    pub prefix: String,
+    pub key_hash: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub allowed_ips: Vec<String>,
+    pub created_by: Option<i32>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub hospital_id: i32,
+}
@@ -0,0 +1,32 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::audit_logs;
+
+/// `details` is a JSON object whose shape depends on `action`.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = audit_logs)]
+pub struct AuditLogEntity {
+    pub id: i32,
+    pub actor_id: Option<i32>,
+    pub action: String,
+    pub subject_user_id: Option<i32>,
+    pub details: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    /// `normal` or `high`, set from the action.
+    pub priority: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = audit_logs)]
+pub struct InsertAuditLogEntity {
+    pub actor_id: Option<i32>,
+    pub action: String,
+    pub subject_user_id: Option<i32>,
+    pub details: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    pub priority: String,
+}
@@ -0,0 +1,32 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::care_assignments;
+
+/// `doctor_id` cares for `patient_id` at the hospital and may read and update their records
+/// until `ended_at` is set.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = care_assignments)]
+pub struct CareAssignmentEntity {
+    pub id: i32,
+    pub hospital_id: i32,
+    pub doctor_id: i32,
+    pub patient_id: i32,
+    pub assigned_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub ended_by: Option<i32>,
+    pub ended_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = care_assignments)]
+pub struct InsertCareAssignmentEntity {
+    pub hospital_id: i32,
+    pub doctor_id: i32,
+    pub patient_id: i32,
+    pub assigned_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
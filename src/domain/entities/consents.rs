@@ -0,0 +1,81 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::{
+    consent_purposes, consent_records, consent_versions,
+};
+
+/// Something personal data is processed for, e.g. `terms_of_service` or `research`.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = consent_purposes)]
+pub struct ConsentPurposeEntity {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    pub description: String,
+    pub required_at_registration: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = consent_purposes)]
+pub struct InsertConsentPurposeEntity {
+    pub code: String,
+    pub name: String,
+    pub description: String,
+    pub required_at_registration: bool,
+    pub created_at: NaiveDateTime,
+}
+
+/// A consent text as published. The purpose's current version is the latest one whose
+/// `effective_from` has passed.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = consent_versions)]
+pub struct ConsentVersionEntity {
+    pub id: i32,
+    pub purpose_id: i32,
+    pub version: i32,
+    pub text: String,
+    pub effective_from: NaiveDateTime,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = consent_versions)]
+pub struct InsertConsentVersionEntity {
+    pub purpose_id: i32,
+    pub version: i32,
+    pub text: String,
+    pub effective_from: NaiveDateTime,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A user granting or withdrawing a consent version. Records are never updated; the latest
+/// one per purpose is the user's current decision.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = consent_records)]
+pub struct ConsentRecordEntity {
+    pub id: i32,
+    pub user_id: i32,
+    pub consent_version_id: i32,
+    pub action: String,
+    pub channel: String,
+    pub recorded_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = consent_records)]
+pub struct InsertConsentRecordEntity {
+    pub user_id: i32,
+    pub consent_version_id: i32,
+    pub action: String,
+    pub channel: String,
+    pub recorded_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
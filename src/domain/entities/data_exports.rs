@@ -0,0 +1,31 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::data_exports;
+
+/// A queued personal data export, without its content. Deleted once past `expires_at`.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = data_exports)]
+pub struct DataExportEntity {
+    pub id: String,
+    pub user_id: i32,
+    pub format: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = data_exports)]
+pub struct InsertDataExportEntity {
+    pub id: String,
+    pub user_id: i32,
+    pub format: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,66 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::{
+    doctor_application_transitions, doctor_applications,
+};
+
+/// `documents` is a JSON array shaped like `Vec<ApplicationDocumentModel>`.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = doctor_applications)]
+pub struct DoctorApplicationEntity {
+    pub id: i32,
+    pub user_id: i32,
+    pub license_number: String,
+    pub specialties: Vec<String>,
+    pub department: Option<String>,
+    pub documents: serde_json::Value,
+    pub status: String,
+    pub review_notes: Option<String>,
+    pub reviewed_by: Option<i32>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    /// Where the doctor applied to practise; approval grants `Doctor` there only.
+    pub hospital_id: i32,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = doctor_applications)]
+pub struct InsertDoctorApplicationEntity {
+    pub hospital_id: i32,
+    pub user_id: i32,
+    pub license_number: String,
+    pub specialties: Vec<String>,
+    pub department: Option<String>,
+    pub documents: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// One status change of an application; `from_status` is `None` for the submission.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = doctor_application_transitions)]
+pub struct DoctorApplicationTransitionEntity {
+    pub id: i32,
+    pub application_id: i32,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor_id: i32,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = doctor_application_transitions)]
+pub struct InsertDoctorApplicationTransitionEntity {
+    pub application_id: i32,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor_id: i32,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,45 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{AsChangeset, Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::doctor_profiles;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = doctor_profiles, primary_key(user_id))]
+pub struct DoctorProfileEntity {
+    pub user_id: i32,
+    pub license_number: String,
+    pub specialties: Vec<String>,
+    pub languages: Vec<String>,
+    pub department: Option<String>,
+    pub bio: Option<String>,
+    pub photo_url: Option<String>,
+    pub verified_at: Option<NaiveDateTime>,
+    pub verified_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Fields the doctor edits. Verification is set separately by admins.
+#[derive(Debug, Clone, Insertable, AsChangeset)]
+#[diesel(table_name = doctor_profiles, primary_key(user_id), treat_none_as_null = true)]
+pub struct UpsertDoctorProfileEntity {
+    pub user_id: i32,
+    pub license_number: String,
+    pub specialties: Vec<String>,
+    pub languages: Vec<String>,
+    pub department: Option<String>,
+    pub bio: Option<String>,
+    pub photo_url: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A verified doctor joined with the public part of their user row.
+#[derive(Debug, Clone)]
+pub struct DoctorDirectoryEntity {
+    pub profile: DoctorProfileEntity,
+    pub first_name: String,
+    pub last_name: String,
+}
@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::emergency_accesses;
+
+/// A break-the-glass access: `user_id` may read and update `patient_id`'s records at the
+/// hospital until `expires_at`, whatever their roles. Open for review until `reviewed_at` is set.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = emergency_accesses)]
+pub struct EmergencyAccessEntity {
+    pub id: i32,
+    pub hospital_id: i32,
+    pub user_id: i32,
+    pub patient_id: i32,
+    pub reason: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub reviewed_by: Option<i32>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub review_notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = emergency_accesses)]
+pub struct InsertEmergencyAccessEntity {
+    pub hospital_id: i32,
+    pub user_id: i32,
+    pub patient_id: i32,
+    pub reason: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,32 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::erasures;
+
+/// A right-to-erasure request carried out: the user's personal data was scrubbed and their
+/// credentials revoked, while the `users` row is kept for the records that refer to it.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = erasures)]
+pub struct ErasureEntity {
+    pub id: i32,
+    pub user_id: i32,
+    pub hospital_id: i32,
+    pub legal_basis: String,
+    pub notes: String,
+    pub erased_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = erasures)]
+pub struct InsertErasureEntity {
+    pub user_id: i32,
+    pub hospital_id: i32,
+    pub legal_basis: String,
+    pub notes: String,
+    pub erased_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,36 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::guardianships;
+
+/// A guardian's authority to act for a dependent. `consent_evidence` is a JSON object shaped
+/// like `ConsentEvidenceModel`. Active while neither revoked nor past `valid_until`.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = guardianships)]
+pub struct GuardianshipEntity {
+    pub id: i32,
+    pub guardian_id: i32,
+    pub dependent_id: i32,
+    pub relationship: String,
+    pub consent_evidence: serde_json::Value,
+    pub valid_until: Option<NaiveDateTime>,
+    pub created_by: i32,
+    pub created_at: NaiveDateTime,
+    pub revoked_by: Option<i32>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = guardianships)]
+pub struct InsertGuardianshipEntity {
+    pub guardian_id: i32,
+    pub dependent_id: i32,
+    pub relationship: String,
+    pub consent_evidence: serde_json::Value,
+    pub valid_until: Option<NaiveDateTime>,
+    pub created_by: i32,
+    pub created_at: NaiveDateTime,
+}
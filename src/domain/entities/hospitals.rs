@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::{hospital_memberships, hospitals};
+
+/// A tenant. `code` is its subdomain and the value of the `X-Hospital` header; the `hn_`
+/// columns are its hospital number format.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = hospitals)]
+pub struct HospitalEntity {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub hn_prefix: String,
+    pub hn_sequence_digits: i16,
+    pub hn_buddhist_year: bool,
+}
+
+/// A user registered at a hospital, under that hospital's own hospital number.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = hospital_memberships, primary_key(hospital_id, user_id))]
+pub struct HospitalMembershipEntity {
+    pub hospital_id: i32,
+    pub user_id: i32,
+    pub hospital_number: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = hospital_memberships)]
+pub struct InsertHospitalMembershipEntity {
+    pub hospital_id: i32,
+    pub user_id: i32,
+    pub hospital_number: String,
+    pub created_at: NaiveDateTime,
+}
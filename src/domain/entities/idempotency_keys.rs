@@ -0,0 +1,31 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::idempotency_keys;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = idempotency_keys)]
+pub struct IdempotencyKeyEntity {
+    pub id: i32,
+    pub idempotency_key: String,
+    pub scope: String,
+    pub fingerprint: String,
+    pub status_code: Option<i32>,
+    pub content_type: Option<String>,
+    pub response_body: Option<Vec<u8>>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = idempotency_keys)]
+pub struct InsertIdempotencyKeyEntity {
+    pub idempotency_key: String,
+    pub scope: String,
+    pub fingerprint: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,33 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::notifications;
+
+/// `payload` is a JSON object whose shape depends on `kind`.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = notifications)]
+pub struct NotificationEntity {
+    pub id: i32,
+    pub recipient_id: i32,
+    pub kind: String,
+    pub subject_user_id: Option<i32>,
+    pub payload: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    pub read_at: Option<NaiveDateTime>,
+    /// `normal` or `high`, set from the kind.
+    pub priority: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = notifications)]
+pub struct InsertNotificationEntity {
+    pub recipient_id: i32,
+    pub kind: String,
+    pub subject_user_id: Option<i32>,
+    pub payload: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    pub priority: String,
+}
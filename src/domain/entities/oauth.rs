@@ -0,0 +1,119 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::{
+    oauth_authorization_codes, oauth_clients, oauth_consents, service_accounts,
+};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = oauth_clients)]
+pub struct OAuthClientEntity {
+    pub id: i32,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub hospital_id: i32,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = oauth_clients)]
+pub struct RegisterOAuthClientEntity {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub hospital_id: i32,
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = oauth_authorization_codes)]
+pub struct OAuthAuthorizationCodeEntity {
+    pub id: i32,
+    pub code: String,
+    pub client_id: String,
+    pub user_id: i32,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+    pub nonce: Option<String>,
+    pub expires_at: NaiveDateTime,
+    pub consumed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = oauth_authorization_codes)]
+pub struct InsertOAuthAuthorizationCodeEntity {
+    pub code: String,
+    pub client_id: String,
+    pub user_id: i32,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+    pub nonce: Option<String>,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = oauth_consents)]
+pub struct OAuthConsentEntity {
+    pub id: i32,
+    pub user_id: i32,
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = oauth_consents)]
+pub struct UpsertOAuthConsentEntity {
+    pub user_id: i32,
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = service_accounts)]
+pub struct ServiceAccountEntity {
+    pub id: i32,
+    pub client_id: String,
+    pub client_secret: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub hospital_id: i32,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = service_accounts)]
+pub struct RegisterServiceAccountEntity {
+    pub client_id: String,
+    pub client_secret: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub hospital_id: i32,
+}
@@ -0,0 +1,39 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{
+    Selectable,
+    prelude::{AsChangeset, Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::patient_profiles;
+
+/// `address` and `emergency_contacts` are JSON documents shaped like `AddressModel` and
+/// `Vec<EmergencyContactModel>`.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = patient_profiles, primary_key(user_id))]
+pub struct PatientProfileEntity {
+    pub user_id: i32,
+    pub date_of_birth: NaiveDate,
+    pub sex: String,
+    pub blood_group: Option<String>,
+    pub allergies: Vec<String>,
+    pub chronic_conditions: Vec<String>,
+    pub address: Option<serde_json::Value>,
+    pub emergency_contacts: serde_json::Value,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Used for both create and full update, so cleared optional fields are written as NULL.
+#[derive(Debug, Clone, Insertable, AsChangeset)]
+#[diesel(table_name = patient_profiles, primary_key(user_id), treat_none_as_null = true)]
+pub struct UpsertPatientProfileEntity {
+    pub user_id: i32,
+    pub date_of_birth: NaiveDate,
+    pub sex: String,
+    pub blood_group: Option<String>,
+    pub allergies: Vec<String>,
+    pub chronic_conditions: Vec<String>,
+    pub address: Option<serde_json::Value>,
+    pub emergency_contacts: serde_json::Value,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,47 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::{roles, user_roles};
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = roles)]
+pub struct RoleEntity {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A role held by a user at one hospital. `granted_by` is `None` for grants made by the system
+/// itself, e.g. at registration. The grant is in effect from `valid_from` until `valid_until`;
+/// `None` leaves that end open.
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = user_roles, primary_key(hospital_id, user_id, role_id))]
+pub struct UserRoleEntity {
+    pub user_id: i32,
+    pub role_id: i32,
+    pub granted_by: Option<i32>,
+    pub granted_at: NaiveDateTime,
+    pub valid_until: Option<NaiveDateTime>,
+    pub valid_from: Option<NaiveDateTime>,
+    /// When admins were told the grant is about to end.
+    pub expiry_notified_at: Option<NaiveDateTime>,
+    /// When the expiry task ended the grant and revoked the user's sessions.
+    pub expired_at: Option<NaiveDateTime>,
+    pub hospital_id: i32,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = user_roles)]
+pub struct InsertUserRoleEntity {
+    pub user_id: i32,
+    pub role_id: i32,
+    pub granted_by: Option<i32>,
+    pub granted_at: NaiveDateTime,
+    pub valid_until: Option<NaiveDateTime>,
+    pub valid_from: Option<NaiveDateTime>,
+    pub hospital_id: i32,
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    Selectable,
+    prelude::{Identifiable, Insertable, Queryable},
+};
+
+use crate::infrastructure::postgres::schema::sessions;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = sessions)]
+pub struct SessionEntity {
+    pub id: String,
+    pub user_id: i32,
+    pub client_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = sessions)]
+pub struct InsertSessionEntity {
+    pub id: String,
+    pub user_id: i32,
+    pub client_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
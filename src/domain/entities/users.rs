@@ -1,14 +1,25 @@
 use chrono::NaiveDateTime;
 use diesel::{
     Selectable,
-    prelude::{Identifiable, Insertable, Queryable},
+    prelude::{Identifiable, Insertable, Queryable, QueryableByName},
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::infrastructure::postgres::schema::users;
 
-#[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize, Deserialize, ToSchema)]
+/// `citizen_id` and `phone_number` hold plaintext here; the repository encrypts them at rest.
+#[derive(
+    Debug,
+    Clone,
+    Identifiable,
+    Selectable,
+    Queryable,
+    QueryableByName,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
 #[diesel(table_name = users)]
 pub struct UserEntity {
     pub id: i32,
@@ -17,7 +28,6 @@ pub struct UserEntity {
     pub last_name: String,
     pub phone_number: String,
     pub password: String,
-    pub role: Vec<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
@@ -31,8 +41,14 @@ pub struct RegisterUserEntity {
     pub last_name: String,
     pub phone_number: String,
     pub password: String,
-    pub role: Vec<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
+    /// Blind indexes of `citizen_id`, its prefixes and `phone_number`; filled in by the
+    /// repository.
+    pub citizen_id_hash: Option<String>,
+    pub phone_number_hash: Option<String>,
+    pub citizen_id_prefix_hashes: Vec<String>,
+    /// Romanized `first_name last_name` for fuzzy search; filled in by the repository.
+    pub name_romanized: String,
 }
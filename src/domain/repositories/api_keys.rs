@@ -0,0 +1,16 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::entities::api_keys::{ApiKeyEntity, InsertApiKeyEntity};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait ApiKeysRepository {
+    async fn create(&self, insert_api_key_entity: InsertApiKeyEntity) -> Result<i32>;
+    async fn find_active_by_prefix(&self, prefix: String) -> Result<Option<ApiKeyEntity>>;
+    /// Keys created at the hospital.
+    async fn list(&self, hospital_id: i32) -> Result<Vec<ApiKeyEntity>>;
+    /// Only revokes a key created at the hospital.
+    async fn revoke_by_id(&self, hospital_id: i32, id: i32) -> Result<()>;
+    async fn touch_last_used_by_id(&self, id: i32) -> Result<()>;
+}
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::{
+    entities::care_assignments::{CareAssignmentEntity, InsertCareAssignmentEntity},
+    value_objects::care_assignments_model::CareAssignmentFilter,
+};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait CareAssignmentsRepository {
+    /// `RepositoryError::Conflict` when the doctor already cares for the patient.
+    async fn create(
+        &self,
+        insert_care_assignment_entity: InsertCareAssignmentEntity,
+    ) -> Result<CareAssignmentEntity>;
+    async fn list(&self, filter: CareAssignmentFilter) -> Result<Vec<CareAssignmentEntity>>;
+    /// `None` when it is not at the hospital or has already ended.
+    async fn end(
+        &self,
+        hospital_id: i32,
+        id: i32,
+        ended_by: i32,
+    ) -> Result<Option<CareAssignmentEntity>>;
+}
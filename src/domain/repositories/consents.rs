@@ -0,0 +1,46 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+use crate::domain::entities::consents::{
+    ConsentPurposeEntity, ConsentRecordEntity, ConsentVersionEntity, InsertConsentPurposeEntity,
+    InsertConsentRecordEntity,
+};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait ConsentsRepository {
+    /// Fails with `RepositoryError::Conflict` when the code is taken.
+    async fn create_purpose(
+        &self,
+        insert_consent_purpose_entity: InsertConsentPurposeEntity,
+    ) -> Result<ConsentPurposeEntity>;
+    /// Publishes the purpose's next version number. `None` when the purpose does not exist.
+    async fn publish_version(
+        &self,
+        purpose_id: i32,
+        text: String,
+        effective_from: NaiveDateTime,
+        created_by: i32,
+    ) -> Result<Option<ConsentVersionEntity>>;
+    /// Every purpose that has a version in effect, with that version.
+    async fn find_current_versions(
+        &self,
+    ) -> Result<Vec<(ConsentPurposeEntity, ConsentVersionEntity)>>;
+    async fn find_version(&self, id: i32) -> Result<Option<ConsentVersionEntity>>;
+    /// The user's latest record per purpose.
+    async fn find_latest_records(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<(ConsentRecordEntity, ConsentVersionEntity)>>;
+    /// The latest record per purpose of every user registered at the hospital.
+    async fn find_latest_records_at_hospital(
+        &self,
+        hospital_id: i32,
+    ) -> Result<Vec<(ConsentRecordEntity, ConsentVersionEntity)>>;
+    async fn count_members(&self, hospital_id: i32) -> Result<i64>;
+    async fn record(
+        &self,
+        insert_consent_record_entity: InsertConsentRecordEntity,
+    ) -> Result<ConsentRecordEntity>;
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+use crate::domain::{
+    entities::data_exports::{DataExportEntity, InsertDataExportEntity},
+    value_objects::data_exports_model::DataExportBundleModel,
+};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait DataExportsRepository {
+    /// Everything held on the user, read in one transaction. `None` when the user does not
+    /// exist.
+    async fn find_bundle(&self, user_id: i32) -> Result<Option<DataExportBundleModel>>;
+    /// Sessions, consent records and access log entries of the user, to size an export.
+    async fn count_rows(&self, user_id: i32) -> Result<i64>;
+    async fn create(
+        &self,
+        insert_data_export_entity: InsertDataExportEntity,
+    ) -> Result<DataExportEntity>;
+    async fn find_by_id(&self, user_id: i32, id: String) -> Result<Option<DataExportEntity>>;
+    /// Decrypted content of a ready export.
+    async fn find_content(&self, user_id: i32, id: String) -> Result<Option<Vec<u8>>>;
+    /// Marks the oldest pending export, or one left processing since before `stale_before`, as
+    /// processing and returns it, skipping ones another instance is claiming.
+    async fn claim_next(&self, stale_before: NaiveDateTime) -> Result<Option<DataExportEntity>>;
+    /// Stores the content encrypted with `field_encryption`.
+    async fn complete(
+        &self,
+        id: String,
+        content: Vec<u8>,
+        completed_at: NaiveDateTime,
+        expires_at: NaiveDateTime,
+    ) -> Result<()>;
+    async fn fail(&self, id: String, error: String) -> Result<()>;
+    /// Returns how many were deleted.
+    async fn delete_expired(&self, now: NaiveDateTime) -> Result<usize>;
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::{
+    entities::doctor_applications::{
+        DoctorApplicationEntity, DoctorApplicationTransitionEntity, InsertDoctorApplicationEntity,
+    },
+    value_objects::doctor_applications_model::DoctorApplicationTransition,
+};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait DoctorApplicationsRepository {
+    /// Inserts the application with its first (`pending`) transition. Fails with
+    /// `RepositoryError::Conflict` while the user has another pending or approved application
+    /// at the same hospital.
+    async fn submit(
+        &self,
+        insert_doctor_application_entity: InsertDoctorApplicationEntity,
+    ) -> Result<DoctorApplicationEntity>;
+    /// `None` as well for applications to another hospital.
+    async fn find_by_id(
+        &self,
+        hospital_id: i32,
+        id: i32,
+    ) -> Result<Option<DoctorApplicationEntity>>;
+    async fn list_by_user_id(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<DoctorApplicationEntity>>;
+    /// Oldest first, so the review queue is worked in submission order.
+    async fn list_by_status(
+        &self,
+        hospital_id: i32,
+        status: String,
+    ) -> Result<Vec<DoctorApplicationEntity>>;
+    async fn list_transitions(
+        &self,
+        application_id: i32,
+    ) -> Result<Vec<DoctorApplicationTransitionEntity>>;
+    /// In one transaction: moves the application if it is still in `from_status`, records the
+    /// transition and applies its `DoctorApplicationEffect` at the application's hospital.
+    /// Returns `None` when the status changed in the meantime.
+    async fn transition(
+        &self,
+        transition: DoctorApplicationTransition,
+    ) -> Result<Option<DoctorApplicationEntity>>;
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::{
+    entities::doctor_profiles::{
+        DoctorDirectoryEntity, DoctorProfileEntity, UpsertDoctorProfileEntity,
+    },
+    value_objects::doctor_profiles_model::DoctorDirectoryFilter,
+};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait DoctorProfilesRepository {
+    /// Fails with `RepositoryError::Conflict` when the user already has a profile or the
+    /// license number is taken.
+    async fn create(
+        &self,
+        upsert_doctor_profile_entity: UpsertDoctorProfileEntity,
+    ) -> Result<DoctorProfileEntity>;
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Option<DoctorProfileEntity>>;
+    /// Returns `None` when the user has no profile yet. `reset_verification` clears the
+    /// admin verification in the same statement.
+    async fn update(
+        &self,
+        upsert_doctor_profile_entity: UpsertDoctorProfileEntity,
+        reset_verification: bool,
+    ) -> Result<Option<DoctorProfileEntity>>;
+    /// `verified_by: None` withdraws the verification.
+    async fn set_verification(
+        &self,
+        user_id: i32,
+        verified_by: Option<i32>,
+    ) -> Result<Option<DoctorProfileEntity>>;
+    /// Verified profiles of active users who still hold the `Doctor` role at
+    /// `filter.hospital_id`, by user id.
+    async fn list_directory(
+        &self,
+        filter: DoctorDirectoryFilter,
+    ) -> Result<Vec<DoctorDirectoryEntity>>;
+    async fn find_in_directory(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Option<DoctorDirectoryEntity>>;
+}
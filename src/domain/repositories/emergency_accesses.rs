@@ -0,0 +1,29 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::{
+    entities::emergency_accesses::{EmergencyAccessEntity, InsertEmergencyAccessEntity},
+    value_objects::emergency_accesses_model::EmergencyAccessFilter,
+};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait EmergencyAccessRepository {
+    /// In one transaction: records the access, writes a high-priority audit entry and notifies
+    /// the hospital's admins at high priority. `None` when the patient is not a registered
+    /// patient of the hospital or is deleted.
+    async fn grant(
+        &self,
+        insert_emergency_access_entity: InsertEmergencyAccessEntity,
+    ) -> Result<Option<EmergencyAccessEntity>>;
+    async fn list(&self, filter: EmergencyAccessFilter) -> Result<Vec<EmergencyAccessEntity>>;
+    /// Signs off the access and audits it. `None` when it is not at the hospital or already
+    /// reviewed.
+    async fn review(
+        &self,
+        hospital_id: i32,
+        id: i32,
+        reviewed_by: i32,
+        notes: String,
+    ) -> Result<Option<EmergencyAccessEntity>>;
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+use crate::domain::entities::erasures::{ErasureEntity, InsertErasureEntity};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait ErasuresRepository {
+    /// The user's latest clinical activity at any hospital: profile updates, registrations,
+    /// care assignments (ongoing ones count as now) and break-the-glass accesses. `None` when
+    /// the user is not a member of the hospital.
+    async fn find_last_clinical_activity_at(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Option<NaiveDateTime>>;
+    /// In one transaction: records the erasure, scrubs the user's name, phone number, citizen
+    /// ID and contact details, revokes their sessions, OAuth grants and guardianships, deletes
+    /// their data exports and audits it. `None` when the user is not a member of the hospital;
+    /// `RepositoryError::Conflict` when already erased.
+    async fn erase(
+        &self,
+        insert_erasure_entity: InsertErasureEntity,
+    ) -> Result<Option<ErasureEntity>>;
+}
@@ -0,0 +1,21 @@
+use std::fmt;
+
+/// Failures a repository reports in domain terms instead of as raw database errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepositoryError {
+    /// A unique constraint rejected the write. Carries the constraint name, e.g.
+    /// `users_citizen_id_key`.
+    Conflict { constraint: String },
+}
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepositoryError::Conflict { constraint } => {
+                write!(f, "conflict on {}", constraint)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::entities::guardianships::{GuardianshipEntity, InsertGuardianshipEntity};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait GuardianshipsRepository {
+    /// Records the guardianship and audits it in one transaction. Fails with
+    /// `RepositoryError::Conflict` while the pair already has an unrevoked guardianship.
+    async fn create(
+        &self,
+        hospital_id: i32,
+        insert_guardianship_entity: InsertGuardianshipEntity,
+    ) -> Result<GuardianshipEntity>;
+    /// Revokes and audits in one transaction. `None` when there is no such unrevoked
+    /// guardianship between two members of the hospital.
+    async fn revoke(
+        &self,
+        hospital_id: i32,
+        id: i32,
+        revoked_by: i32,
+    ) -> Result<Option<GuardianshipEntity>>;
+    /// Neither revoked nor past `valid_until`.
+    async fn find_active(
+        &self,
+        guardian_id: i32,
+        dependent_id: i32,
+    ) -> Result<Option<GuardianshipEntity>>;
+    async fn list_active_for_guardian(&self, guardian_id: i32) -> Result<Vec<GuardianshipEntity>>;
+    /// Every guardianship the user is part of on either side, revoked ones included, newest
+    /// first. Only those where both sides are members of the hospital.
+    async fn list_by_user_id(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<GuardianshipEntity>>;
+    /// Audits a guardian switching to a dependent. `details` describes the delegated token.
+    async fn record_switch(
+        &self,
+        guardianship: &GuardianshipEntity,
+        details: serde_json::Value,
+    ) -> Result<()>;
+    /// Audits one request made with a delegated token.
+    async fn record_delegated_request(
+        &self,
+        guardian_id: i32,
+        dependent_id: i32,
+        details: serde_json::Value,
+    ) -> Result<()>;
+}
@@ -0,0 +1,11 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::entities::hospitals::HospitalEntity;
+
+#[async_trait::async_trait]
+#[automock]
+pub trait HospitalsRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<HospitalEntity>>;
+    async fn find_by_code(&self, code: String) -> Result<Option<HospitalEntity>>;
+}
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::entities::idempotency_keys::{IdempotencyKeyEntity, InsertIdempotencyKeyEntity};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait IdempotencyKeysRepository {
+    /// Inserts a pending record, replacing an expired one. Returns `false` when a live record
+    /// for the same key and scope already exists.
+    async fn reserve(
+        &self,
+        insert_idempotency_key_entity: InsertIdempotencyKeyEntity,
+    ) -> Result<bool>;
+    async fn find_active(
+        &self,
+        idempotency_key: String,
+        scope: String,
+    ) -> Result<Option<IdempotencyKeyEntity>>;
+    async fn complete(
+        &self,
+        idempotency_key: String,
+        scope: String,
+        status_code: i32,
+        content_type: Option<String>,
+        response_body: Vec<u8>,
+    ) -> Result<()>;
+    async fn release(&self, idempotency_key: String, scope: String) -> Result<()>;
+}
@@ -1 +1,19 @@
+pub mod api_keys;
+pub mod consents;
+pub mod data_exports;
+pub mod doctor_applications;
+pub mod doctor_profiles;
+pub mod emergency_accesses;
+pub mod guardianships;
+pub mod errors;
+pub mod hospitals;
+pub mod idempotency_keys;
+pub mod notifications;
+pub mod oauth;
+pub mod patient_profiles;
+pub mod permissions;
+pub mod role_grants;
+pub mod sessions;
 pub mod users;
+pub mod erasures;
+pub mod care_assignments;
@@ -0,0 +1,17 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::entities::notifications::NotificationEntity;
+
+#[async_trait::async_trait]
+#[automock]
+pub trait NotificationsRepository {
+    /// Newest first.
+    async fn list_by_recipient_id(
+        &self,
+        recipient_id: i32,
+        unread_only: bool,
+    ) -> Result<Vec<NotificationEntity>>;
+    /// Returns `false` when the notification does not exist or belongs to someone else.
+    async fn mark_read(&self, id: i32, recipient_id: i32) -> Result<bool>;
+}
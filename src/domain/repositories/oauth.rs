@@ -0,0 +1,53 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::entities::oauth::{
+    InsertOAuthAuthorizationCodeEntity, OAuthAuthorizationCodeEntity, OAuthClientEntity,
+    OAuthConsentEntity, RegisterOAuthClientEntity, RegisterServiceAccountEntity,
+    ServiceAccountEntity, UpsertOAuthConsentEntity,
+};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait OAuthRepository {
+    async fn register_client(
+        &self,
+        register_client_entity: RegisterOAuthClientEntity,
+    ) -> Result<i32>;
+    async fn find_client_by_client_id(&self, client_id: String) -> Result<OAuthClientEntity>;
+    /// Clients registered at the hospital.
+    async fn list_clients(&self, hospital_id: i32) -> Result<Vec<OAuthClientEntity>>;
+    /// Only removes a client registered at the hospital.
+    async fn remove_client_by_client_id(&self, hospital_id: i32, client_id: String) -> Result<()>;
+    async fn create_authorization_code(
+        &self,
+        authorization_code_entity: InsertOAuthAuthorizationCodeEntity,
+    ) -> Result<()>;
+    async fn consume_authorization_code(
+        &self,
+        code: String,
+    ) -> Result<OAuthAuthorizationCodeEntity>;
+    async fn find_consent(
+        &self,
+        user_id: i32,
+        client_id: String,
+    ) -> Result<Option<OAuthConsentEntity>>;
+    async fn upsert_consent(&self, consent_entity: UpsertOAuthConsentEntity) -> Result<()>;
+    async fn revoke_consent(&self, user_id: i32, client_id: String) -> Result<()>;
+    async fn register_service_account(
+        &self,
+        register_service_account_entity: RegisterServiceAccountEntity,
+    ) -> Result<i32>;
+    async fn find_service_account_by_client_id(
+        &self,
+        client_id: String,
+    ) -> Result<ServiceAccountEntity>;
+    /// Service accounts created at the hospital.
+    async fn list_service_accounts(&self, hospital_id: i32) -> Result<Vec<ServiceAccountEntity>>;
+    /// Only removes a service account created at the hospital.
+    async fn remove_service_account_by_client_id(
+        &self,
+        hospital_id: i32,
+        client_id: String,
+    ) -> Result<()>;
+}
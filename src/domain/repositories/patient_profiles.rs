@@ -0,0 +1,22 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::entities::patient_profiles::{PatientProfileEntity, UpsertPatientProfileEntity};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait PatientProfilesRepository {
+    /// Fails with `RepositoryError::Conflict` when the user already has a profile.
+    async fn create(
+        &self,
+        upsert_patient_profile_entity: UpsertPatientProfileEntity,
+    ) -> Result<PatientProfileEntity>;
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Option<PatientProfileEntity>>;
+    /// Returns `None` when the user has no profile yet.
+    async fn update(
+        &self,
+        upsert_patient_profile_entity: UpsertPatientProfileEntity,
+    ) -> Result<Option<PatientProfileEntity>>;
+    /// Returns whether a profile was deleted.
+    async fn delete_by_user_id(&self, user_id: i32) -> Result<bool>;
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::value_objects::policy::RolePermission;
+
+#[async_trait::async_trait]
+#[automock]
+pub trait PermissionsRepository {
+    /// Permissions of the roles the user currently holds at the hospital, tagged with the
+    /// granting role.
+    async fn find_by_user_id(&self, hospital_id: i32, user_id: i32) -> Result<Vec<RolePermission>>;
+    /// Patients the user is assigned to care for at the hospital.
+    async fn find_care_patient_ids(&self, hospital_id: i32, user_id: i32) -> Result<Vec<i32>>;
+    /// Patients the user holds an unexpired break-the-glass access to at the hospital.
+    async fn find_emergency_patient_ids(&self, hospital_id: i32, user_id: i32) -> Result<Vec<i32>>;
+}
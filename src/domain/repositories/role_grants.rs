@@ -0,0 +1,19 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+#[async_trait::async_trait]
+#[automock]
+pub trait RoleGrantsRepository {
+    /// Notifies admins, and whoever made the grant, of grants ending after `now` and by
+    /// `notice_until`. Each grant is notified once. Returns how many grants were notified.
+    async fn notify_expiring(
+        &self,
+        now: NaiveDateTime,
+        notice_until: NaiveDateTime,
+    ) -> Result<usize>;
+    /// Marks grants that ended by `now` as expired, revokes the holders' sessions and records
+    /// each expiry in the audit log. Returns the number of grants expired and of sessions
+    /// revoked.
+    async fn expire_due(&self, now: NaiveDateTime) -> Result<(usize, usize)>;
+}
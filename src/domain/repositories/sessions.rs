@@ -0,0 +1,12 @@
+use anyhow::Result;
+use mockall::automock;
+
+use crate::domain::entities::sessions::{InsertSessionEntity, SessionEntity};
+
+#[async_trait::async_trait]
+#[automock]
+pub trait SessionsRepository {
+    async fn create(&self, insert_session_entity: InsertSessionEntity) -> Result<String>;
+    async fn find_active_by_id(&self, id: String) -> Result<Option<SessionEntity>>;
+    async fn revoke_by_id(&self, id: String) -> Result<()>;
+}
@@ -1,17 +1,94 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime};
 use mockall::automock;
 
 use crate::domain::{
     entities::users::{RegisterUserEntity, UserEntity},
-    value_objects::roles::Roles,
+    value_objects::{
+        consents_model::RegistrationConsents,
+        roles::{Role, RoleGrantModel},
+        users_model::{RegisterUserResponseModel, UserListFilter, UserSearchHit},
+    },
 };
 
 #[async_trait::async_trait]
 #[automock]
 pub trait UsersRepository {
-    async fn register(&self, register_user_entity: RegisterUserEntity) -> Result<i32>;
+    /// Creates the user holding `role` at the hospital and gives them the hospital's next
+    /// hospital number. `registered_by` is the staff member registering on the user's behalf,
+    /// which is recorded in the audit log. Fails with `RepositoryError::Conflict` when the
+    /// citizen ID is already registered, or with `ConsentError` when the consents leave out a
+    /// purpose required at registration or name a version not in effect.
+    async fn register(
+        &self,
+        hospital_id: i32,
+        register_user_entity: RegisterUserEntity,
+        role: Role,
+        registered_by: Option<i32>,
+        registration_consents: RegistrationConsents,
+    ) -> Result<RegisterUserResponseModel>;
+    /// `register` for a user who already has an account, e.g. a patient first registered at
+    /// another hospital. `None` when the user is already registered at the hospital.
+    async fn register_existing(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        role: Role,
+        registered_by: Option<i32>,
+        registration_consents: RegistrationConsents,
+    ) -> Result<Option<RegisterUserResponseModel>>;
     async fn find_by_id(&self, id: i32) -> Result<UserEntity>;
+    /// Looks up by the blind index, so only exact (normalized) citizen IDs match.
+    async fn find_by_citizen_id(&self, citizen_id: String) -> Result<Option<UserEntity>>;
+    /// From the user's patient profile; `None` without one.
+    async fn find_date_of_birth(&self, user_id: i32) -> Result<Option<NaiveDate>>;
+    async fn find_by_hospital_number(
+        &self,
+        hospital_id: i32,
+        hospital_number: String,
+    ) -> Result<Option<UserEntity>>;
+    /// Hospital numbers at the hospital by user id; users not registered there are absent.
+    async fn find_hospital_numbers(
+        &self,
+        hospital_id: i32,
+        user_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, String>>;
+    /// Returns at most `filter.limit` users of `filter.hospital_id` after `filter.after`, in the
+    /// requested order.
+    async fn list(&self, filter: UserListFilter) -> Result<Vec<UserEntity>>;
+    /// Trigram similarity search over active users of the hospital, best match first.
+    /// `name_romanized` is matched against the romanized names when given.
+    async fn search_by_name(
+        &self,
+        hospital_id: i32,
+        name: String,
+        name_romanized: Option<String>,
+        limit: i64,
+    ) -> Result<Vec<UserSearchHit>>;
     async fn remove_by_id(&self, id: i32) -> Result<()>;
-    async fn add_role_to_user_by_id(&self, role: Roles, id: i32) -> Result<()>;
-    async fn remove_role_from_user_by_id(&self, role: Roles, id: i32) -> Result<()>;
+    /// Roles the user currently holds at the hospital; expired grants are left out.
+    async fn find_roles(&self, hospital_id: i32, user_id: i32) -> Result<Vec<Role>>;
+    async fn find_roles_by_user_ids(
+        &self,
+        hospital_id: i32,
+        user_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, Vec<Role>>>;
+    /// Grants `role` at the hospital for the given window, or replaces the grant metadata when
+    /// the user already holds it there.
+    async fn grant_role(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        role: Role,
+        granted_by: Option<i32>,
+        valid_from: Option<NaiveDateTime>,
+        valid_until: Option<NaiveDateTime>,
+    ) -> Result<()>;
+    /// Grants at the hospital that have not ended, including ones that start in the future.
+    async fn find_role_grants(&self, hospital_id: i32, user_id: i32)
+    -> Result<Vec<RoleGrantModel>>;
+    /// Returns `false` when the user did not hold the role at the hospital.
+    async fn revoke_role(&self, hospital_id: i32, user_id: i32, role: Role) -> Result<bool>;
 }
@@ -0,0 +1,54 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::entities::api_keys::ApiKeyEntity;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyModel {
+    pub name: String,
+    pub scopes: Vec<String>,
+    /// IP addresses or CIDR ranges allowed to use the key. Empty means any address.
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyResponseModel {
+    pub id: i32,
+    pub prefix: String,
+    /// The full key. It is not stored and cannot be retrieved again.
+    pub api_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyModel {
+    pub id: i32,
+    pub prefix: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub allowed_ips: Vec<String>,
+    pub created_by: Option<i32>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+impl From<ApiKeyEntity> for ApiKeyModel {
+    fn from(api_key: ApiKeyEntity) -> Self {
+        Self {
+            id: api_key.id,
+            prefix: api_key.prefix,
+            name: api_key.name,
+            scopes: api_key.scopes,
+            allowed_ips: api_key.allowed_ips,
+            created_by: api_key.created_by,
+            expires_at: api_key.expires_at,
+            last_used_at: api_key.last_used_at,
+            created_at: api_key.created_at,
+            revoked_at: api_key.revoked_at,
+        }
+    }
+}
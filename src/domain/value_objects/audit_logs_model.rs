@@ -0,0 +1,99 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::value_objects::priority::Priority;
+
+/// What an audit log entry records, stored by name in `audit_logs.action`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum AuditAction {
+    /// A staff member registered a patient account for someone else.
+    #[serde(rename = "user.registered_on_behalf")]
+    UserRegisteredOnBehalf,
+    /// A time-bounded role grant reached `valid_until` and the user's sessions were revoked.
+    #[serde(rename = "role_grant.expired")]
+    RoleGrantExpired,
+    /// Staff recorded a guardian-dependent relationship.
+    #[serde(rename = "guardianship.created")]
+    GuardianshipCreated,
+    /// Staff revoked a guardianship; delegated tokens stop working at once.
+    #[serde(rename = "guardianship.revoked")]
+    GuardianshipRevoked,
+    /// A guardian switched to acting for a dependent and was issued a delegated token.
+    #[serde(rename = "guardianship.switched")]
+    GuardianshipSwitched,
+    /// A request made with a delegated token. The actor is the guardian, the subject the
+    /// dependent.
+    #[serde(rename = "guardianship.acted_on_behalf")]
+    GuardianshipActedOnBehalf,
+    /// Someone broke the glass for time-limited access to a patient record.
+    #[serde(rename = "emergency_access.granted")]
+    EmergencyAccessGranted,
+    /// A reviewer signed off a break-the-glass use.
+    #[serde(rename = "emergency_access.reviewed")]
+    EmergencyAccessReviewed,
+    /// A user's personal data was scrubbed on an erasure request. The details carry the legal
+    /// basis.
+    #[serde(rename = "user.erased")]
+    UserErased,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 9] = [
+        AuditAction::UserRegisteredOnBehalf,
+        AuditAction::RoleGrantExpired,
+        AuditAction::GuardianshipCreated,
+        AuditAction::GuardianshipRevoked,
+        AuditAction::GuardianshipSwitched,
+        AuditAction::GuardianshipActedOnBehalf,
+        AuditAction::EmergencyAccessGranted,
+        AuditAction::EmergencyAccessReviewed,
+        AuditAction::UserErased,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditAction::UserRegisteredOnBehalf => "user.registered_on_behalf",
+            AuditAction::RoleGrantExpired => "role_grant.expired",
+            AuditAction::GuardianshipCreated => "guardianship.created",
+            AuditAction::GuardianshipRevoked => "guardianship.revoked",
+            AuditAction::GuardianshipSwitched => "guardianship.switched",
+            AuditAction::GuardianshipActedOnBehalf => "guardianship.acted_on_behalf",
+            AuditAction::EmergencyAccessGranted => "emergency_access.granted",
+            AuditAction::EmergencyAccessReviewed => "emergency_access.reviewed",
+            AuditAction::UserErased => "user.erased",
+        }
+    }
+
+    pub fn priority(self) -> Priority {
+        match self {
+            AuditAction::EmergencyAccessGranted => Priority::High,
+            AuditAction::UserRegisteredOnBehalf
+            | AuditAction::RoleGrantExpired
+            | AuditAction::GuardianshipCreated
+            | AuditAction::GuardianshipRevoked
+            | AuditAction::GuardianshipSwitched
+            | AuditAction::GuardianshipActedOnBehalf
+            | AuditAction::EmergencyAccessReviewed
+            | AuditAction::UserErased => Priority::Normal,
+        }
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        AuditAction::ALL
+            .into_iter()
+            .find(|action| action.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown audit action: {}", s))
+    }
+}
@@ -2,7 +2,11 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::{
-    domain::entities::users::UserEntity, infrastructure::jwt_authentication::jwt_model::Claims,
+    domain::{
+        entities::users::UserEntity,
+        value_objects::{patient_profiles_model::PatientProfileModel, roles::Role},
+    },
+    infrastructure::jwt_authentication::jwt_model::Claims,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -12,4 +16,8 @@ pub struct LoginResponseModel {}
 pub struct GetMeResponseModel {
     pub claims: Claims,
     pub me: UserEntity,
+    /// Roles currently held; the token's `role` is the one it was issued for.
+    pub roles: Vec<Role>,
+    /// Only for patients who have filled in their profile.
+    pub patient_profile: Option<PatientProfileModel>,
 }
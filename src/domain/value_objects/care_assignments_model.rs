@@ -0,0 +1,93 @@
+use std::fmt;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::entities::care_assignments::{CareAssignmentEntity, InsertCareAssignmentEntity};
+
+/// Failures of care team management, mapped to HTTP statuses by the router.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CareAssignmentError {
+    /// The user does not hold the Doctor role at the hospital.
+    DoctorNotFound,
+    /// The user does not hold the Patient role at the hospital.
+    PatientNotFound,
+    /// No such assignment at the hospital, or it has already ended.
+    NotFound,
+}
+
+impl fmt::Display for CareAssignmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CareAssignmentError::DoctorNotFound => write!(f, "Doctor not found"),
+            CareAssignmentError::PatientNotFound => write!(f, "Patient not found"),
+            CareAssignmentError::NotFound => {
+                write!(f, "Care assignment not found or already ended")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CareAssignmentError {}
+
+/// Body of `POST /care-assignments`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateCareAssignmentModel {
+    pub doctor_id: i32,
+    pub patient_id: i32,
+}
+
+impl CreateCareAssignmentModel {
+    pub fn to_entity(&self, hospital_id: i32, assigned_by: i32) -> InsertCareAssignmentEntity {
+        InsertCareAssignmentEntity {
+            hospital_id,
+            doctor_id: self.doctor_id,
+            patient_id: self.patient_id,
+            assigned_by: Some(assigned_by),
+            created_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CareAssignmentModel {
+    pub id: i32,
+    pub doctor_id: i32,
+    pub patient_id: i32,
+    pub assigned_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub ended_by: Option<i32>,
+    pub ended_at: Option<NaiveDateTime>,
+}
+
+impl From<CareAssignmentEntity> for CareAssignmentModel {
+    fn from(entity: CareAssignmentEntity) -> Self {
+        Self {
+            id: entity.id,
+            doctor_id: entity.doctor_id,
+            patient_id: entity.patient_id,
+            assigned_by: entity.assigned_by,
+            created_at: entity.created_at,
+            ended_by: entity.ended_by,
+            ended_at: entity.ended_at,
+        }
+    }
+}
+
+/// Query string of `GET /care-assignments`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CareAssignmentQueryModel {
+    pub doctor_id: Option<i32>,
+    pub patient_id: Option<i32>,
+}
+
+/// What `CareAssignmentsRepository::list` returns: the hospital's assignments that have not
+/// ended, newest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CareAssignmentFilter {
+    pub hospital_id: i32,
+    pub doctor_id: Option<i32>,
+    pub patient_id: Option<i32>,
+}
@@ -0,0 +1,318 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::domain::{
+    entities::consents::{
+        ConsentPurposeEntity, ConsentRecordEntity, ConsentVersionEntity, InsertConsentPurposeEntity,
+    },
+    value_objects::validation::{FREE_TEXT_MAX_LENGTH, NAME_MAX_LENGTH},
+};
+
+pub const CONSENT_CODE_MAX_LENGTH: usize = 64;
+pub const CONSENT_TEXT_MAX_LENGTH: u64 = 100_000;
+
+/// Stored by name in `consent_records.action`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentAction {
+    Granted,
+    Withdrawn,
+}
+
+impl fmt::Display for ConsentAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsentAction::Granted => write!(f, "granted"),
+            ConsentAction::Withdrawn => write!(f, "withdrawn"),
+        }
+    }
+}
+
+impl FromStr for ConsentAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "granted" => Ok(ConsentAction::Granted),
+            "withdrawn" => Ok(ConsentAction::Withdrawn),
+            _ => Err(anyhow::anyhow!("Unknown consent action: {}", s)),
+        }
+    }
+}
+
+/// How a consent decision reached us, stored by name in `consent_records.channel`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentChannel {
+    #[default]
+    Web,
+    Mobile,
+    /// Given to staff, e.g. at the registration desk.
+    InPerson,
+    /// A signed paper form, keyed in by staff.
+    Paper,
+}
+
+impl fmt::Display for ConsentChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsentChannel::Web => write!(f, "web"),
+            ConsentChannel::Mobile => write!(f, "mobile"),
+            ConsentChannel::InPerson => write!(f, "in_person"),
+            ConsentChannel::Paper => write!(f, "paper"),
+        }
+    }
+}
+
+impl FromStr for ConsentChannel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "web" => Ok(ConsentChannel::Web),
+            "mobile" => Ok(ConsentChannel::Mobile),
+            "in_person" => Ok(ConsentChannel::InPerson),
+            "paper" => Ok(ConsentChannel::Paper),
+            _ => Err(anyhow::anyhow!("Unknown consent channel: {}", s)),
+        }
+    }
+}
+
+/// Failures of consent management, mapped to HTTP statuses by the routers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsentError {
+    PurposeNotFound,
+    VersionNotFound,
+    /// Only the current version of a purpose can be granted.
+    VersionNotCurrent,
+    /// Withdrawing a purpose the user has not granted.
+    NotGranted,
+    /// Registration did not accept the current version of these purposes (by code).
+    RequiredNotAccepted {
+        purposes: Vec<String>,
+    },
+}
+
+impl fmt::Display for ConsentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsentError::PurposeNotFound => write!(f, "Consent purpose not found"),
+            ConsentError::VersionNotFound => write!(f, "Consent version not found"),
+            ConsentError::VersionNotCurrent => {
+                write!(f, "Only the current version of a consent can be granted")
+            }
+            ConsentError::NotGranted => write!(f, "This consent has not been granted"),
+            ConsentError::RequiredNotAccepted { purposes } => {
+                write!(
+                    f,
+                    "Registration requires consent to: {}",
+                    purposes.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConsentError {}
+
+/// Lowercase letters, digits and underscores, e.g. `terms_of_service`.
+fn validate_consent_code(code: &str) -> Result<(), ValidationError> {
+    let valid = !code.is_empty()
+        && code.len() <= CONSENT_CODE_MAX_LENGTH
+        && code
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if !valid {
+        return Err(ValidationError::new("consent_code")
+            .with_message("Code must be 1 to 64 lowercase letters, digits or underscores".into()));
+    }
+
+    Ok(())
+}
+
+/// The consents a new user accepts while registering.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegistrationConsents {
+    pub consent_version_ids: Vec<i32>,
+    pub channel: ConsentChannel,
+}
+
+/// Body of `POST /admin/consent-purposes`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateConsentPurposeModel {
+    #[validate(custom(function = validate_consent_code))]
+    pub code: String,
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub name: String,
+    #[validate(length(max = FREE_TEXT_MAX_LENGTH))]
+    pub description: Option<String>,
+    /// Registration fails unless the current version is accepted. Defaults to `false`.
+    pub required_at_registration: Option<bool>,
+}
+
+impl CreateConsentPurposeModel {
+    pub fn to_entity(&self) -> InsertConsentPurposeEntity {
+        InsertConsentPurposeEntity {
+            code: self.code.clone(),
+            name: self.name.trim().to_string(),
+            description: self
+                .description
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            required_at_registration: self.required_at_registration.unwrap_or(false),
+            created_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+/// Body of `POST /admin/consent-purposes/{purpose_id}/versions`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct PublishConsentVersionModel {
+    #[validate(length(min = 1, max = CONSENT_TEXT_MAX_LENGTH))]
+    pub text: String,
+    /// When the version becomes current. Defaults to now.
+    pub effective_from: Option<NaiveDateTime>,
+}
+
+/// Body of `POST /users/me/consents`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecordConsentModel {
+    /// For `granted`, the purpose's current version. For `withdrawn`, any version of the
+    /// purpose; the withdrawal applies to the whole purpose.
+    pub consent_version_id: i32,
+    pub action: ConsentAction,
+    pub channel: ConsentChannel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConsentPurposeModel {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    pub description: String,
+    pub required_at_registration: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<ConsentPurposeEntity> for ConsentPurposeModel {
+    fn from(entity: ConsentPurposeEntity) -> Self {
+        Self {
+            id: entity.id,
+            code: entity.code,
+            name: entity.name,
+            description: entity.description,
+            required_at_registration: entity.required_at_registration,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConsentVersionModel {
+    pub id: i32,
+    pub purpose_id: i32,
+    pub version: i32,
+    pub text: String,
+    pub effective_from: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<ConsentVersionEntity> for ConsentVersionModel {
+    fn from(entity: ConsentVersionEntity) -> Self {
+        Self {
+            id: entity.id,
+            purpose_id: entity.purpose_id,
+            version: entity.version,
+            text: entity.text,
+            effective_from: entity.effective_from,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+/// The text a user is asked to accept for a purpose today.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CurrentConsentModel {
+    pub purpose_code: String,
+    pub purpose_name: String,
+    pub description: String,
+    pub required_at_registration: bool,
+    pub consent_version_id: i32,
+    pub version: i32,
+    pub text: String,
+    pub effective_from: NaiveDateTime,
+}
+
+impl CurrentConsentModel {
+    pub fn new(purpose: ConsentPurposeEntity, version: ConsentVersionEntity) -> Self {
+        Self {
+            purpose_code: purpose.code,
+            purpose_name: purpose.name,
+            description: purpose.description,
+            required_at_registration: purpose.required_at_registration,
+            consent_version_id: version.id,
+            version: version.version,
+            text: version.text,
+            effective_from: version.effective_from,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConsentRecordModel {
+    pub id: i32,
+    pub consent_version_id: i32,
+    pub version: i32,
+    pub action: String,
+    pub channel: String,
+    /// Staff member who recorded it for the user, if any.
+    pub recorded_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+impl ConsentRecordModel {
+    pub fn new(record: ConsentRecordEntity, version: &ConsentVersionEntity) -> Self {
+        Self {
+            id: record.id,
+            consent_version_id: record.consent_version_id,
+            version: version.version,
+            action: record.action,
+            channel: record.channel,
+            recorded_by: record.recorded_by,
+            created_at: record.created_at,
+        }
+    }
+}
+
+/// A purpose as seen by the signed-in user.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserConsentModel {
+    pub current: CurrentConsentModel,
+    /// The user's latest decision on the purpose, on whichever version.
+    pub latest: Option<ConsentRecordModel>,
+    /// Whether the current version is granted. Turns `false` when a new version takes effect,
+    /// until the user grants it.
+    pub granted_current: bool,
+}
+
+/// Where a hospital's patients stand on one purpose.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ConsentReportRowModel {
+    pub purpose_code: String,
+    pub purpose_name: String,
+    pub current_version: i32,
+    pub granted_current: i64,
+    /// Granted an earlier version and not yet the current one.
+    pub granted_outdated: i64,
+    pub withdrawn: i64,
+    /// Registered at the hospital with no decision on the purpose.
+    pub undecided: i64,
+}
@@ -0,0 +1,301 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::{
+    entities::{
+        audit_logs::AuditLogEntity, data_exports::DataExportEntity,
+        hospitals::HospitalMembershipEntity, sessions::SessionEntity, users::UserEntity,
+    },
+    value_objects::{
+        doctor_profiles_model::DoctorProfileModel, patient_profiles_model::PatientProfileModel,
+    },
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportFormat {
+    #[default]
+    Json,
+    /// The JSON bundle as `export.json` in a zip archive.
+    Zip,
+}
+
+impl DataExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            DataExportFormat::Json => "application/json",
+            DataExportFormat::Zip => "application/zip",
+        }
+    }
+}
+
+impl fmt::Display for DataExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataExportFormat::Json => write!(f, "json"),
+            DataExportFormat::Zip => write!(f, "zip"),
+        }
+    }
+}
+
+impl FromStr for DataExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(DataExportFormat::Json),
+            "zip" => Ok(DataExportFormat::Zip),
+            _ => Err(anyhow::anyhow!("Unknown data export format: {}", s)),
+        }
+    }
+}
+
+/// Stored by name in `data_exports.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportStatus {
+    Pending,
+    Processing,
+    Ready,
+    Failed,
+}
+
+impl fmt::Display for DataExportStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataExportStatus::Pending => write!(f, "pending"),
+            DataExportStatus::Processing => write!(f, "processing"),
+            DataExportStatus::Ready => write!(f, "ready"),
+            DataExportStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl FromStr for DataExportStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(DataExportStatus::Pending),
+            "processing" => Ok(DataExportStatus::Processing),
+            "ready" => Ok(DataExportStatus::Ready),
+            "failed" => Ok(DataExportStatus::Failed),
+            _ => Err(anyhow::anyhow!("Unknown data export status: {}", s)),
+        }
+    }
+}
+
+/// Failures of data export downloads, mapped to HTTP statuses by the router.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataExportError {
+    NotFound,
+    /// Still queued or generating, or generation failed.
+    NotReady,
+    Expired,
+}
+
+impl fmt::Display for DataExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataExportError::NotFound => write!(f, "Data export not found"),
+            DataExportError::NotReady => write!(f, "Data export is not ready"),
+            DataExportError::Expired => write!(f, "Data export link has expired"),
+        }
+    }
+}
+
+impl std::error::Error for DataExportError {}
+
+/// Query string of `GET /users/me/export`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DataExportQueryModel {
+    /// `json` (default) or `zip`.
+    pub format: Option<DataExportFormat>,
+}
+
+/// The user row as exported: everything but the password hash.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedUserModel {
+    pub id: i32,
+    pub citizen_id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub phone_number: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl From<UserEntity> for ExportedUserModel {
+    fn from(entity: UserEntity) -> Self {
+        Self {
+            id: entity.id,
+            citizen_id: entity.citizen_id,
+            first_name: entity.first_name,
+            last_name: entity.last_name,
+            phone_number: entity.phone_number,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            deleted_at: entity.deleted_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedMembershipModel {
+    pub hospital_id: i32,
+    pub hospital_number: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<HospitalMembershipEntity> for ExportedMembershipModel {
+    fn from(entity: HospitalMembershipEntity) -> Self {
+        Self {
+            hospital_id: entity.hospital_id,
+            hospital_number: entity.hospital_number,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+/// A role grant at a hospital, ended ones included.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedRoleGrantModel {
+    pub hospital_id: i32,
+    pub role: String,
+    pub granted_by: Option<i32>,
+    pub granted_at: NaiveDateTime,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_until: Option<NaiveDateTime>,
+    pub expired_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedConsentRecordModel {
+    pub purpose_code: String,
+    pub version: i32,
+    pub action: String,
+    pub channel: String,
+    pub recorded_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A sign-in. The session id is a credential and is left out.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedSessionModel {
+    /// The OAuth client signed in to, if not this service.
+    pub client_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+impl From<SessionEntity> for ExportedSessionModel {
+    fn from(entity: SessionEntity) -> Self {
+        Self {
+            client_id: entity.client_id,
+            created_at: entity.created_at,
+            expires_at: entity.expires_at,
+            revoked_at: entity.revoked_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedAuditLogModel {
+    pub id: i32,
+    pub actor_id: Option<i32>,
+    pub action: String,
+    pub subject_user_id: Option<i32>,
+    pub details: serde_json::Value,
+    pub priority: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<AuditLogEntity> for ExportedAuditLogModel {
+    fn from(entity: AuditLogEntity) -> Self {
+        Self {
+            id: entity.id,
+            actor_id: entity.actor_id,
+            action: entity.action,
+            subject_user_id: entity.subject_user_id,
+            details: entity.details,
+            priority: entity.priority,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+/// Everything held on a user, as returned by `GET /users/me/export`. Lists are oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DataExportBundleModel {
+    pub generated_at: NaiveDateTime,
+    pub user: ExportedUserModel,
+    pub hospital_memberships: Vec<ExportedMembershipModel>,
+    pub patient_profile: Option<PatientProfileModel>,
+    pub doctor_profile: Option<DoctorProfileModel>,
+    pub role_grants: Vec<ExportedRoleGrantModel>,
+    /// Every grant and withdrawal.
+    pub consents: Vec<ExportedConsentRecordModel>,
+    /// Every session, ended ones included, so also the login history.
+    pub sessions: Vec<ExportedSessionModel>,
+    /// Audit log entries about the user or by the user, including access to their record.
+    pub access_log: Vec<ExportedAuditLogModel>,
+}
+
+/// A queued export.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DataExportModel {
+    pub id: String,
+    pub format: String,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+    /// The export is deleted after this time.
+    pub expires_at: NaiveDateTime,
+    /// Set once ready; requires signing in as the same user.
+    pub download_url: Option<String>,
+}
+
+impl From<DataExportEntity> for DataExportModel {
+    fn from(entity: DataExportEntity) -> Self {
+        let download_url = (entity.status == DataExportStatus::Ready.to_string())
+            .then(|| format!("/users/me/exports/{}/download", entity.id));
+
+        Self {
+            id: entity.id,
+            format: entity.format,
+            status: entity.status,
+            created_at: entity.created_at,
+            completed_at: entity.completed_at,
+            expires_at: entity.expires_at,
+            download_url,
+        }
+    }
+}
+
+/// A generated export ready to send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataExportFileModel {
+    pub format: DataExportFormat,
+    pub content: Vec<u8>,
+}
+
+impl DataExportFileModel {
+    pub fn filename(&self, user_id: i32) -> String {
+        format!("medbook-export-{}.{}", user_id, self.format)
+    }
+}
+
+/// What `GET /users/me/export` produced.
+#[derive(Debug, Clone)]
+pub enum DataExportOutcome {
+    Ready(DataExportFileModel),
+    /// Too large to generate during the request.
+    Queued(DataExportModel),
+}
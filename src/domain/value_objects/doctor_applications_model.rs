@@ -0,0 +1,312 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::domain::{
+    entities::doctor_applications::{
+        DoctorApplicationEntity, DoctorApplicationTransitionEntity, InsertDoctorApplicationEntity,
+    },
+    value_objects::validation::{NAME_MAX_LENGTH, validate_license_number, validate_text_entries},
+};
+
+pub const DOCUMENTS_MAX: u64 = 10;
+pub const REVIEW_NOTES_MAX_LENGTH: u64 = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorApplicationStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Revoked,
+}
+
+impl DoctorApplicationStatus {
+    /// pending → approved | rejected, approved → revoked. Rejected and revoked are final; the
+    /// user submits a new application instead.
+    pub fn can_transition_to(self, to: DoctorApplicationStatus) -> bool {
+        matches!(
+            (self, to),
+            (
+                DoctorApplicationStatus::Pending,
+                DoctorApplicationStatus::Approved | DoctorApplicationStatus::Rejected
+            ) | (
+                DoctorApplicationStatus::Approved,
+                DoctorApplicationStatus::Revoked
+            )
+        )
+    }
+}
+
+impl fmt::Display for DoctorApplicationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DoctorApplicationStatus::Pending => write!(f, "pending"),
+            DoctorApplicationStatus::Approved => write!(f, "approved"),
+            DoctorApplicationStatus::Rejected => write!(f, "rejected"),
+            DoctorApplicationStatus::Revoked => write!(f, "revoked"),
+        }
+    }
+}
+
+impl FromStr for DoctorApplicationStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(DoctorApplicationStatus::Pending),
+            "approved" => Ok(DoctorApplicationStatus::Approved),
+            "rejected" => Ok(DoctorApplicationStatus::Rejected),
+            "revoked" => Ok(DoctorApplicationStatus::Revoked),
+            _ => Err(anyhow::anyhow!("Unknown application status: {}", s)),
+        }
+    }
+}
+
+/// Failures of the onboarding workflow, mapped to HTTP statuses by the router.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DoctorApplicationError {
+    NotFound,
+    InvalidTransition {
+        from: DoctorApplicationStatus,
+        to: DoctorApplicationStatus,
+    },
+}
+
+impl fmt::Display for DoctorApplicationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DoctorApplicationError::NotFound => write!(f, "Doctor application not found"),
+            DoctorApplicationError::InvalidTransition { from, to } => {
+                write!(f, "Cannot move a {} application to {}", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DoctorApplicationError {}
+
+/// A supporting document the applicant has already uploaded, e.g. a scan of the license.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ApplicationDocumentModel {
+    /// e.g. `medical_license`, `specialty_certificate`, `citizen_id_card`.
+    #[validate(length(min = 1, max = 50))]
+    pub kind: String,
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub file_name: String,
+    #[validate(url, length(max = 255))]
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct SubmitDoctorApplicationModel {
+    /// Medical council license number, e.g. `ว.12345`.
+    #[validate(custom(function = validate_license_number))]
+    pub license_number: String,
+    #[serde(default)]
+    #[validate(length(max = 10), custom(function = validate_text_entries))]
+    pub specialties: Vec<String>,
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub department: Option<String>,
+    #[validate(length(min = 1, max = DOCUMENTS_MAX), nested)]
+    pub documents: Vec<ApplicationDocumentModel>,
+}
+
+impl SubmitDoctorApplicationModel {
+    pub fn to_entity(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<InsertDoctorApplicationEntity> {
+        let now = chrono::Utc::now().naive_utc();
+
+        Ok(InsertDoctorApplicationEntity {
+            hospital_id,
+            user_id,
+            license_number: self.license_number.trim().to_string(),
+            specialties: self
+                .specialties
+                .iter()
+                .map(|specialty| specialty.trim().to_lowercase())
+                .collect(),
+            department: self.department.as_ref().map(|d| d.trim().to_string()),
+            documents: serde_json::to_value(&self.documents)?,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+/// Body of `POST /doctor-applications/{id}/review`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ReviewDoctorApplicationModel {
+    /// `approved`, `rejected` or `revoked`.
+    pub status: DoctorApplicationStatus,
+    /// Required when rejecting or revoking; shown to the applicant.
+    #[validate(length(max = REVIEW_NOTES_MAX_LENGTH))]
+    pub notes: Option<String>,
+    /// UTC; only when approving, e.g. for a locum whose `Doctor` role should end with their
+    /// contract. Omitted for a role that does not expire.
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+impl ReviewDoctorApplicationModel {
+    /// `validate()` plus the rules that rejecting or revoking needs notes, reported on `notes`,
+    /// and that `valid_until` is a future time given only when approving.
+    pub fn validate_review(&self) -> Result<(), ValidationErrors> {
+        self.validate()?;
+
+        if let Some(valid_until) = self.valid_until {
+            let mut errors = ValidationErrors::new();
+            if self.status != DoctorApplicationStatus::Approved {
+                errors.add(
+                    "valid_until",
+                    ValidationError::new("valid_until_not_approving")
+                        .with_message("Expiry can only be set when approving".into()),
+                );
+            } else if valid_until <= chrono::Utc::now().naive_utc() {
+                errors.add(
+                    "valid_until",
+                    ValidationError::new("valid_until_past")
+                        .with_message("Expiry must be in the future".into()),
+                );
+            }
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
+
+        let needs_notes = matches!(
+            self.status,
+            DoctorApplicationStatus::Rejected | DoctorApplicationStatus::Revoked
+        );
+        let has_notes = self
+            .notes
+            .as_deref()
+            .is_some_and(|notes| !notes.trim().is_empty());
+
+        if needs_notes && !has_notes {
+            let mut errors = ValidationErrors::new();
+            errors.add(
+                "notes",
+                ValidationError::new("notes_required")
+                    .with_message("Notes are required when rejecting or revoking".into()),
+            );
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DoctorApplicationTransitionModel {
+    pub from_status: Option<DoctorApplicationStatus>,
+    pub to_status: DoctorApplicationStatus,
+    pub actor_id: i32,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<DoctorApplicationTransitionEntity> for DoctorApplicationTransitionModel {
+    type Error = anyhow::Error;
+
+    fn try_from(entity: DoctorApplicationTransitionEntity) -> Result<Self> {
+        Ok(Self {
+            from_status: entity.from_status.as_deref().map(str::parse).transpose()?,
+            to_status: entity.to_status.parse()?,
+            actor_id: entity.actor_id,
+            notes: entity.notes,
+            created_at: entity.created_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DoctorApplicationModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub license_number: String,
+    pub specialties: Vec<String>,
+    pub department: Option<String>,
+    pub documents: Vec<ApplicationDocumentModel>,
+    pub status: DoctorApplicationStatus,
+    pub review_notes: Option<String>,
+    pub reviewed_by: Option<i32>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    /// Every status change, oldest first. Only filled in when a single application is fetched.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<DoctorApplicationTransitionModel>,
+}
+
+impl TryFrom<DoctorApplicationEntity> for DoctorApplicationModel {
+    type Error = anyhow::Error;
+
+    fn try_from(entity: DoctorApplicationEntity) -> Result<Self> {
+        Ok(Self {
+            id: entity.id,
+            user_id: entity.user_id,
+            license_number: entity.license_number,
+            specialties: entity.specialties,
+            department: entity.department,
+            documents: serde_json::from_value(entity.documents)?,
+            status: entity.status.parse()?,
+            review_notes: entity.review_notes,
+            reviewed_by: entity.reviewed_by,
+            reviewed_at: entity.reviewed_at,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+            history: Vec::new(),
+        })
+    }
+}
+
+/// Query string of the admin review queue.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListDoctorApplicationsQueryModel {
+    /// Defaults to `pending`.
+    pub status: Option<DoctorApplicationStatus>,
+}
+
+/// A reviewer's decision, as handed to `DoctorApplicationsRepository::transition`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoctorApplicationTransition {
+    pub application_id: i32,
+    pub from_status: DoctorApplicationStatus,
+    pub to_status: DoctorApplicationStatus,
+    pub actor_id: i32,
+    pub notes: Option<String>,
+    /// End of the `Doctor` grant made on approval.
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+/// What approving or revoking does beyond the status change; applied in the same
+/// transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DoctorApplicationEffect {
+    None,
+    /// Grant the `Doctor` role and create or refresh a verified doctor profile from the
+    /// application.
+    GrantDoctor,
+    /// Remove the `Doctor` role and withdraw the profile's verification.
+    RevokeDoctor,
+}
+
+impl DoctorApplicationEffect {
+    pub fn of(to: DoctorApplicationStatus) -> Self {
+        match to {
+            DoctorApplicationStatus::Approved => DoctorApplicationEffect::GrantDoctor,
+            DoctorApplicationStatus::Revoked => DoctorApplicationEffect::RevokeDoctor,
+            DoctorApplicationStatus::Pending | DoctorApplicationStatus::Rejected => {
+                DoctorApplicationEffect::None
+            }
+        }
+    }
+}
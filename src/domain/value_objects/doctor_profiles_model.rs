@@ -0,0 +1,179 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::domain::{
+    entities::doctor_profiles::{
+        DoctorDirectoryEntity, DoctorProfileEntity, UpsertDoctorProfileEntity,
+    },
+    value_objects::validation::{
+        NAME_MAX_LENGTH, validate_language_codes, validate_license_number, validate_text_entries,
+    },
+};
+
+pub const SPECIALTIES_MAX: u64 = 10;
+pub const LANGUAGES_MAX: u64 = 10;
+pub const BIO_MAX_LENGTH: u64 = 2000;
+
+/// Body of `POST` and `PUT /doctors/me/profile`. `PUT` replaces the whole profile; changing
+/// `license_number` sends the profile back for verification.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpsertDoctorProfileModel {
+    /// Medical council license number, e.g. `ว.12345`.
+    #[validate(custom(function = validate_license_number))]
+    pub license_number: String,
+    /// e.g. `cardiology`; stored lower-cased.
+    #[serde(default)]
+    #[validate(length(max = SPECIALTIES_MAX), custom(function = validate_text_entries))]
+    pub specialties: Vec<String>,
+    /// ISO 639-1 codes of languages spoken with patients, e.g. `th`, `en`.
+    #[serde(default)]
+    #[validate(length(max = LANGUAGES_MAX), custom(function = validate_language_codes))]
+    pub languages: Vec<String>,
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub department: Option<String>,
+    #[validate(length(max = BIO_MAX_LENGTH))]
+    pub bio: Option<String>,
+    #[validate(url, length(max = 255))]
+    pub photo_url: Option<String>,
+}
+
+impl UpsertDoctorProfileModel {
+    /// Canonical form of a model that has passed `validate()`.
+    pub fn normalized(mut self) -> Self {
+        self.license_number = self.license_number.trim().to_string();
+        self.specialties = normalize_codes(self.specialties);
+        self.languages = normalize_codes(self.languages);
+        self.department = self.department.map(|d| d.trim().to_string());
+        self.bio = self
+            .bio
+            .map(|bio| bio.trim().to_string())
+            .filter(|bio| !bio.is_empty());
+        self
+    }
+
+    pub fn to_entity(&self, user_id: i32) -> UpsertDoctorProfileEntity {
+        UpsertDoctorProfileEntity {
+            user_id,
+            license_number: self.license_number.clone(),
+            specialties: self.specialties.clone(),
+            languages: self.languages.clone(),
+            department: self.department.clone(),
+            bio: self.bio.clone(),
+            photo_url: self.photo_url.clone(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+/// Trimmed, lower-cased and de-duplicated so directory filters match exactly.
+fn normalize_codes(values: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(values.len());
+    for value in values {
+        let value = value.trim().to_lowercase();
+        if !value.is_empty() && !normalized.contains(&value) {
+            normalized.push(value);
+        }
+    }
+    normalized
+}
+
+/// Full profile, shown to the doctor and to admins.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DoctorProfileModel {
+    pub user_id: i32,
+    pub license_number: String,
+    pub specialties: Vec<String>,
+    pub languages: Vec<String>,
+    pub department: Option<String>,
+    pub bio: Option<String>,
+    pub photo_url: Option<String>,
+    pub verified: bool,
+    pub verified_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<DoctorProfileEntity> for DoctorProfileModel {
+    fn from(entity: DoctorProfileEntity) -> Self {
+        Self {
+            user_id: entity.user_id,
+            license_number: entity.license_number,
+            specialties: entity.specialties,
+            languages: entity.languages,
+            department: entity.department,
+            bio: entity.bio,
+            photo_url: entity.photo_url,
+            verified: entity.verified_at.is_some(),
+            verified_at: entity.verified_at,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+/// Body of `PUT /doctors/{user_id}/verification`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetDoctorVerificationModel {
+    pub verified: bool,
+}
+
+/// Public directory entry. Leaves out the license number, contact details and anything else
+/// that is not meant for patients browsing doctors.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DoctorDirectoryEntryModel {
+    pub user_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub specialties: Vec<String>,
+    pub languages: Vec<String>,
+    pub department: Option<String>,
+    pub bio: Option<String>,
+    pub photo_url: Option<String>,
+}
+
+impl From<DoctorDirectoryEntity> for DoctorDirectoryEntryModel {
+    fn from(entity: DoctorDirectoryEntity) -> Self {
+        Self {
+            user_id: entity.profile.user_id,
+            first_name: entity.first_name,
+            last_name: entity.last_name,
+            specialties: entity.profile.specialties,
+            languages: entity.profile.languages,
+            department: entity.profile.department,
+            bio: entity.profile.bio,
+            photo_url: entity.profile.photo_url,
+        }
+    }
+}
+
+/// Query string of `GET /doctors`. All filters are combined with AND.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct ListDoctorsQueryModel {
+    /// Specialty, e.g. `cardiology` (case-insensitive).
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub specialty: Option<String>,
+    /// Exact department name.
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub department: Option<String>,
+    /// ISO 639-1 code of a language the doctor speaks.
+    #[validate(length(equal = 2))]
+    pub language: Option<String>,
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Page size, 1 to 100 (default 20).
+    pub limit: Option<i64>,
+}
+
+/// Criteria for `DoctorProfilesRepository::list_directory`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoctorDirectoryFilter {
+    pub hospital_id: i32,
+    pub specialty: Option<String>,
+    pub department: Option<String>,
+    pub language: Option<String>,
+    pub after_user_id: Option<i32>,
+    pub limit: i64,
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+use crate::domain::entities::emergency_accesses::{
+    EmergencyAccessEntity, InsertEmergencyAccessEntity,
+};
+
+pub const EMERGENCY_ACCESS_DEFAULT_MINUTES: i64 = 60;
+pub const EMERGENCY_ACCESS_MAX_MINUTES: i64 = 240;
+pub const EMERGENCY_REASON_MIN_LENGTH: u64 = 10;
+pub const EMERGENCY_REASON_MAX_LENGTH: u64 = 2000;
+
+/// Failures of break-the-glass access, mapped to HTTP statuses by the router.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmergencyAccessError {
+    PatientNotFound,
+    /// No such access at the hospital, or it has already been reviewed.
+    NotFound,
+}
+
+impl fmt::Display for EmergencyAccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmergencyAccessError::PatientNotFound => write!(f, "Patient not found"),
+            EmergencyAccessError::NotFound => {
+                write!(f, "Emergency access not found or already reviewed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EmergencyAccessError {}
+
+/// A reason a reviewer can act on: 10 to 2000 characters once trimmed.
+fn validate_reason(reason: &str) -> Result<(), ValidationError> {
+    let length = reason.trim().chars().count();
+
+    if !(EMERGENCY_REASON_MIN_LENGTH..=EMERGENCY_REASON_MAX_LENGTH).contains(&(length as u64)) {
+        return Err(ValidationError::new("reason_length")
+            .with_message("Reason must be 10 to 2000 characters".into()));
+    }
+
+    Ok(())
+}
+
+/// Body of `POST /emergency-access`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateEmergencyAccessModel {
+    pub patient_id: i32,
+    /// Why normal access is not enough, e.g. the patient arrived unconscious. Read by the
+    /// reviewers.
+    #[validate(custom(function = validate_reason))]
+    pub reason: String,
+    /// Defaults to 60; at most 240.
+    #[validate(range(min = 1, max = EMERGENCY_ACCESS_MAX_MINUTES))]
+    pub duration_minutes: Option<i64>,
+}
+
+impl CreateEmergencyAccessModel {
+    pub fn to_entity(&self, hospital_id: i32, user_id: i32) -> InsertEmergencyAccessEntity {
+        let now = chrono::Utc::now().naive_utc();
+        let duration_minutes = self
+            .duration_minutes
+            .unwrap_or(EMERGENCY_ACCESS_DEFAULT_MINUTES);
+
+        InsertEmergencyAccessEntity {
+            hospital_id,
+            user_id,
+            patient_id: self.patient_id,
+            reason: self.reason.trim().to_string(),
+            created_at: now,
+            expires_at: now + Duration::minutes(duration_minutes),
+        }
+    }
+}
+
+/// Body of `POST /emergency-access/{id}/review`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ReviewEmergencyAccessModel {
+    /// The reviewer's finding, e.g. whether the access was justified.
+    #[validate(length(min = 1, max = EMERGENCY_REASON_MAX_LENGTH))]
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EmergencyAccessModel {
+    pub id: i32,
+    /// Who broke the glass.
+    pub user_id: i32,
+    pub patient_id: i32,
+    pub reason: String,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub reviewed_by: Option<i32>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub review_notes: Option<String>,
+}
+
+impl From<EmergencyAccessEntity> for EmergencyAccessModel {
+    fn from(entity: EmergencyAccessEntity) -> Self {
+        Self {
+            id: entity.id,
+            user_id: entity.user_id,
+            patient_id: entity.patient_id,
+            reason: entity.reason,
+            created_at: entity.created_at,
+            expires_at: entity.expires_at,
+            reviewed_by: entity.reviewed_by,
+            reviewed_at: entity.reviewed_at,
+            review_notes: entity.review_notes,
+        }
+    }
+}
+
+/// Query string of the break-the-glass report.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EmergencyAccessReportQueryModel {
+    /// Accesses granted at or after this time.
+    pub from: Option<NaiveDateTime>,
+    /// Accesses granted before this time.
+    pub to: Option<NaiveDateTime>,
+    /// Only accesses not yet reviewed.
+    pub unreviewed: Option<bool>,
+}
+
+/// What `EmergencyAccessRepository::list` returns, newest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmergencyAccessFilter {
+    pub hospital_id: i32,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    pub unreviewed: bool,
+}
@@ -0,0 +1,125 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::domain::entities::erasures::{ErasureEntity, InsertErasureEntity};
+
+pub const ERASURE_NOTES_MAX_LENGTH: u64 = 2000;
+
+/// Why the data subject's personal data is erased, stored by name in `erasures.legal_basis`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LegalBasis {
+    /// The user withdrew the consent the processing relied on.
+    ConsentWithdrawn,
+    /// The data is no longer needed for the purpose it was collected for.
+    NoLongerNecessary,
+    /// The user objected to the processing and no overriding ground remains.
+    Objection,
+    UnlawfulProcessing,
+    CourtOrder,
+}
+
+impl fmt::Display for LegalBasis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LegalBasis::ConsentWithdrawn => write!(f, "consent_withdrawn"),
+            LegalBasis::NoLongerNecessary => write!(f, "no_longer_necessary"),
+            LegalBasis::Objection => write!(f, "objection"),
+            LegalBasis::UnlawfulProcessing => write!(f, "unlawful_processing"),
+            LegalBasis::CourtOrder => write!(f, "court_order"),
+        }
+    }
+}
+
+impl FromStr for LegalBasis {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "consent_withdrawn" => Ok(LegalBasis::ConsentWithdrawn),
+            "no_longer_necessary" => Ok(LegalBasis::NoLongerNecessary),
+            "objection" => Ok(LegalBasis::Objection),
+            "unlawful_processing" => Ok(LegalBasis::UnlawfulProcessing),
+            "court_order" => Ok(LegalBasis::CourtOrder),
+            _ => Err(anyhow::anyhow!("Unknown legal basis: {}", s)),
+        }
+    }
+}
+
+/// Failures of right-to-erasure, mapped to HTTP statuses by the router.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErasureError {
+    UserNotFound,
+    /// The user's medical record must still be kept.
+    RetentionPeriod {
+        retained_until: NaiveDateTime,
+    },
+}
+
+impl fmt::Display for ErasureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErasureError::UserNotFound => write!(f, "User not found"),
+            ErasureError::RetentionPeriod { retained_until } => write!(
+                f,
+                "The user's medical record must be kept until {}",
+                retained_until
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErasureError {}
+
+/// Body of `POST /admin/users/{user_id}/erase`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct EraseUserModel {
+    pub legal_basis: LegalBasis,
+    /// E.g. the reference of the data subject's request.
+    #[serde(default)]
+    #[validate(length(max = ERASURE_NOTES_MAX_LENGTH))]
+    pub notes: String,
+}
+
+impl EraseUserModel {
+    pub fn to_entity(&self, hospital_id: i32, user_id: i32, erased_by: i32) -> InsertErasureEntity {
+        InsertErasureEntity {
+            user_id,
+            hospital_id,
+            legal_basis: self.legal_basis.to_string(),
+            notes: self.notes.trim().to_string(),
+            erased_by: Some(erased_by),
+            created_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErasureModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub legal_basis: LegalBasis,
+    pub notes: String,
+    pub erased_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<ErasureEntity> for ErasureModel {
+    type Error = anyhow::Error;
+
+    fn try_from(entity: ErasureEntity) -> Result<Self> {
+        Ok(Self {
+            id: entity.id,
+            user_id: entity.user_id,
+            legal_basis: entity.legal_basis.parse()?,
+            notes: entity.notes,
+            erased_by: entity.erased_by,
+            created_at: entity.created_at,
+        })
+    }
+}
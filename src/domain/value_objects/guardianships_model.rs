@@ -0,0 +1,199 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::domain::{
+    entities::guardianships::{GuardianshipEntity, InsertGuardianshipEntity},
+    value_objects::validation::NAME_MAX_LENGTH,
+};
+
+/// Longest a guardian's delegated token lasts; never past the guardianship's `valid_until`.
+pub const DELEGATED_TOKEN_TTL_MINUTES: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardianRelationship {
+    Parent,
+    LegalGuardian,
+    Spouse,
+    Child,
+    Relative,
+    Caregiver,
+}
+
+impl fmt::Display for GuardianRelationship {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuardianRelationship::Parent => write!(f, "parent"),
+            GuardianRelationship::LegalGuardian => write!(f, "legal_guardian"),
+            GuardianRelationship::Spouse => write!(f, "spouse"),
+            GuardianRelationship::Child => write!(f, "child"),
+            GuardianRelationship::Relative => write!(f, "relative"),
+            GuardianRelationship::Caregiver => write!(f, "caregiver"),
+        }
+    }
+}
+
+impl FromStr for GuardianRelationship {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "parent" => Ok(GuardianRelationship::Parent),
+            "legal_guardian" => Ok(GuardianRelationship::LegalGuardian),
+            "spouse" => Ok(GuardianRelationship::Spouse),
+            "child" => Ok(GuardianRelationship::Child),
+            "relative" => Ok(GuardianRelationship::Relative),
+            "caregiver" => Ok(GuardianRelationship::Caregiver),
+            _ => Err(anyhow::anyhow!("Unknown guardian relationship: {}", s)),
+        }
+    }
+}
+
+/// Failures of guardianship management and switching, mapped to HTTP statuses by the router.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardianshipError {
+    NotFound,
+    /// The guardian or the dependent is not registered at the hospital.
+    UserNotFound,
+    /// No active guardianship over the dependent, or the dependent is not a patient at the
+    /// hospital.
+    NotGuardian,
+}
+
+impl fmt::Display for GuardianshipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuardianshipError::NotFound => write!(f, "Guardianship not found"),
+            GuardianshipError::UserNotFound => {
+                write!(
+                    f,
+                    "Guardian or dependent is not registered at this hospital"
+                )
+            }
+            GuardianshipError::NotGuardian => {
+                write!(f, "You are not an active guardian of this patient")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GuardianshipError {}
+
+/// What the front desk checked before recording the guardianship.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ConsentEvidenceModel {
+    /// e.g. `birth_certificate`, `power_of_attorney`, `court_order`, `signed_consent_form`.
+    #[validate(length(min = 1, max = 50))]
+    pub kind: String,
+    /// Number or other reference of the document.
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub reference: String,
+    /// Scan of the document, when one was uploaded.
+    #[validate(url, length(max = 255))]
+    pub url: Option<String>,
+}
+
+/// Body of `POST /guardianships`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateGuardianshipModel {
+    pub guardian_id: i32,
+    pub dependent_id: i32,
+    pub relationship: GuardianRelationship,
+    #[validate(nested)]
+    pub consent_evidence: ConsentEvidenceModel,
+    /// e.g. the day a child comes of age. `None` for no end date.
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+impl CreateGuardianshipModel {
+    /// `validate()` plus the rules that nobody is their own guardian, reported on
+    /// `guardian_id`, and that `valid_until` is in the future.
+    pub fn validate_guardianship(&self) -> Result<(), ValidationErrors> {
+        self.validate()?;
+
+        let mut errors = ValidationErrors::new();
+
+        if self.guardian_id == self.dependent_id {
+            errors.add(
+                "guardian_id",
+                ValidationError::new("guardian_self")
+                    .with_message("A user cannot be their own guardian".into()),
+            );
+        }
+        if self
+            .valid_until
+            .is_some_and(|valid_until| valid_until <= chrono::Utc::now().naive_utc())
+        {
+            errors.add(
+                "valid_until",
+                ValidationError::new("valid_until_past")
+                    .with_message("valid_until must be in the future".into()),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn to_entity(&self, created_by: i32) -> Result<InsertGuardianshipEntity> {
+        Ok(InsertGuardianshipEntity {
+            guardian_id: self.guardian_id,
+            dependent_id: self.dependent_id,
+            relationship: self.relationship.to_string(),
+            consent_evidence: serde_json::to_value(&self.consent_evidence)?,
+            valid_until: self.valid_until,
+            created_by,
+            created_at: chrono::Utc::now().naive_utc(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GuardianshipModel {
+    pub id: i32,
+    pub guardian_id: i32,
+    pub dependent_id: i32,
+    pub relationship: GuardianRelationship,
+    pub consent_evidence: ConsentEvidenceModel,
+    pub valid_until: Option<NaiveDateTime>,
+    pub created_by: i32,
+    pub created_at: NaiveDateTime,
+    pub revoked_by: Option<i32>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+impl TryFrom<GuardianshipEntity> for GuardianshipModel {
+    type Error = anyhow::Error;
+
+    fn try_from(entity: GuardianshipEntity) -> Result<Self> {
+        Ok(Self {
+            id: entity.id,
+            guardian_id: entity.guardian_id,
+            dependent_id: entity.dependent_id,
+            relationship: entity.relationship.parse()?,
+            consent_evidence: serde_json::from_value(entity.consent_evidence)?,
+            valid_until: entity.valid_until,
+            created_by: entity.created_by,
+            created_at: entity.created_at,
+            revoked_by: entity.revoked_by,
+            revoked_at: entity.revoked_at,
+        })
+    }
+}
+
+/// Response of `POST /guardianships/dependents/{dependent_id}/act`; the delegated token itself
+/// is set as the `act` cookie.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ActingAsModel {
+    pub guardianship_id: i32,
+    pub dependent_id: i32,
+    pub expires_at: NaiveDateTime,
+}
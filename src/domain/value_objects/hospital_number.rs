@@ -0,0 +1,194 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::domain::entities::hospitals::HospitalEntity;
+
+/// How a hospital writes hospital numbers: prefix, two-digit year, zero-padded sequence that
+/// restarts every year, then a check character, e.g. `MB` `69` `000123` `1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HospitalNumberFormat {
+    pub prefix: String,
+    pub sequence_digits: usize,
+    /// Counts years in the Buddhist era (2569 rather than 2026).
+    pub buddhist_year: bool,
+}
+
+impl HospitalNumberFormat {
+    /// Two-digit year carried by numbers issued on `date`.
+    pub fn year_code(&self, date: NaiveDate) -> i16 {
+        let year = if self.buddhist_year {
+            date.year() + 543
+        } else {
+            date.year()
+        };
+
+        (year % 100) as i16
+    }
+
+    /// Sequences longer than `sequence_digits` are written in full rather than truncated.
+    pub fn format(&self, year_code: i16, sequence: i32) -> String {
+        let body = format!(
+            "{:02}{:0width$}",
+            year_code,
+            sequence,
+            width = self.sequence_digits
+        );
+
+        format!("{}{}{}", self.prefix, body, check_character(&body))
+    }
+}
+
+impl From<&HospitalEntity> for HospitalNumberFormat {
+    fn from(hospital: &HospitalEntity) -> Self {
+        Self {
+            prefix: hospital.hn_prefix.clone(),
+            sequence_digits: hospital.hn_sequence_digits as usize,
+            buddhist_year: hospital.hn_buddhist_year,
+        }
+    }
+}
+
+/// Check values 0 to 10; 10 is written `X`.
+const CHECK_CHARACTERS: &[u8; 11] = b"0123456789X";
+
+/// Mod-11 check character over `digits`. Weights 2 to 10 repeat from the right, so at any
+/// length every weight is non-zero mod 11 and neighbouring weights differ: a single mistyped
+/// digit or two swapped neighbours are always caught.
+pub fn check_character(digits: &str) -> char {
+    let sum: u32 = digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| digit * (2 + i as u32 % 9))
+        .sum();
+
+    CHECK_CHARACTERS[((11 - sum % 11) % 11) as usize] as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::validation::validate_hospital_number;
+
+    fn format(sequence_digits: usize) -> HospitalNumberFormat {
+        HospitalNumberFormat {
+            prefix: "MB".to_string(),
+            sequence_digits,
+            buddhist_year: true,
+        }
+    }
+
+    #[test]
+    fn check_character_is_mod_11_with_x_for_ten() {
+        // 2*2 + 1*3 + 9*8 + 6*9 = 133, 133 % 11 = 1, 11 - 1 = 10
+        assert_eq!(check_character("69000012"), 'X');
+        // 0*2 + 1*3 = 3, 11 - 3 = 8
+        assert_eq!(check_character("10"), '8');
+        // 5*2 = 10, 11 - 10 = 1
+        assert_eq!(check_character("5"), '1');
+        // ผลรวมหาร 11 ลงตัว
+        assert_eq!(check_character("0"), '0');
+        // น้ำหนักวนกลับเป็น 2 ที่หลักที่ 10 จากขวา: 1*2 + 1*2 = 4, 11 - 4 = 7
+        assert_eq!(check_character("1000000001"), '7');
+    }
+
+    #[test]
+    fn check_character_catches_single_digit_errors_at_any_length() {
+        for sequence_digits in 3..=12 {
+            let body = "69".to_string() + &"7".repeat(sequence_digits);
+            let check = check_character(&body);
+
+            for position in 0..body.len() {
+                for replacement in '0'..='9' {
+                    let mut typo: Vec<char> = body.chars().collect();
+                    if typo[position] == replacement {
+                        continue;
+                    }
+                    typo[position] = replacement;
+                    let typo: String = typo.into_iter().collect();
+
+                    assert_ne!(check_character(&typo), check, "{} vs {}", body, typo);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn check_character_catches_swapped_neighbours() {
+        let body = "6901234567890";
+        let check = check_character(body);
+
+        for position in 0..body.len() - 1 {
+            let mut swapped: Vec<char> = body.chars().collect();
+            swapped.swap(position, position + 1);
+            let swapped: String = swapped.into_iter().collect();
+
+            assert_ne!(check_character(&swapped), check, "{}", swapped);
+        }
+    }
+
+    #[test]
+    fn year_code_counts_buddhist_or_common_era() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let common_era = HospitalNumberFormat {
+            buddhist_year: false,
+            ..format(6)
+        };
+
+        assert_eq!(format(6).year_code(date), 69);
+        assert_eq!(common_era.year_code(date), 26);
+    }
+
+    #[test]
+    fn format_pads_the_sequence_and_appends_the_check_character() {
+        assert_eq!(format(6).format(69, 12), "MB69000012X");
+        assert_eq!(format(3).format(5, 7), "MB050075");
+    }
+
+    #[test]
+    fn format_writes_long_sequences_in_full() {
+        let hospital_number = format(3).format(69, 12345);
+
+        assert!(hospital_number.starts_with("MB6912345"));
+        assert_eq!(hospital_number.len(), "MB6912345".len() + 1);
+    }
+
+    #[test]
+    fn formatted_numbers_validate() {
+        for sequence_digits in 3..=9 {
+            for sequence in [1, 42, 999, 123_456] {
+                let hospital_number = format(sequence_digits).format(69, sequence);
+
+                assert_eq!(validate_hospital_number(&hospital_number), Ok(()));
+            }
+        }
+    }
+
+    #[test]
+    fn validate_accepts_separators_and_lower_case() {
+        let hospital_number = format(6).format(69, 12);
+        let written = format!("mb-{}", &hospital_number[2..]).to_lowercase();
+
+        assert_eq!(validate_hospital_number(&written), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_a_wrong_check_character() {
+        let hospital_number = format(6).format(69, 12);
+        let (body, check) = hospital_number.split_at(hospital_number.len() - 1);
+        let wrong = if check == "0" { "1" } else { "0" };
+
+        let error = validate_hospital_number(&format!("{}{}", body, wrong)).unwrap_err();
+
+        assert_eq!(error.code, "hospital_number_checksum");
+    }
+
+    #[test]
+    fn validate_rejects_malformed_numbers() {
+        for hospital_number in ["", "MB", "MB69001", "MB69A001234", "MB6900012X4"] {
+            let error = validate_hospital_number(hospital_number).unwrap_err();
+
+            assert_eq!(error.code, "hospital_number_format", "{}", hospital_number);
+        }
+    }
+}
@@ -1,3 +1,23 @@
 pub mod roles;
 pub mod users_model;
-pub mod authentication_model;
\ No newline at end of file
+pub mod authentication_model;
+pub mod oauth_model;
+pub mod principal;
+pub mod api_keys_model;
+pub mod validation;
+pub mod pagination;
+pub mod patient_profiles_model;
+pub mod doctor_profiles_model;
+pub mod doctor_applications_model;
+pub mod policy;
+pub mod audit_logs_model;
+pub mod notifications_model;
+pub mod tenant;
+pub mod hospital_number;
+pub mod guardianships_model;
+pub mod priority;
+pub mod emergency_accesses_model;
+pub mod consents_model;
+pub mod data_exports_model;
+pub mod erasures_model;
+pub mod care_assignments_model;
@@ -0,0 +1,95 @@
+use std::{fmt, str::FromStr};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::{
+    entities::notifications::NotificationEntity, value_objects::priority::Priority,
+};
+
+/// Stored by name in `notifications.kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum NotificationKind {
+    /// A time-bounded role grant ends soon. Payload: `{ role, valid_until }`.
+    #[serde(rename = "role_grant.expiring")]
+    RoleGrantExpiring,
+    /// Someone broke the glass at the hospital. Payload:
+    /// `{ emergency_access_id, user_id, reason, expires_at, hospital_id }`.
+    #[serde(rename = "emergency_access.granted")]
+    EmergencyAccessGranted,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 2] = [
+        NotificationKind::RoleGrantExpiring,
+        NotificationKind::EmergencyAccessGranted,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationKind::RoleGrantExpiring => "role_grant.expiring",
+            NotificationKind::EmergencyAccessGranted => "emergency_access.granted",
+        }
+    }
+
+    pub fn priority(self) -> Priority {
+        match self {
+            NotificationKind::RoleGrantExpiring => Priority::Normal,
+            NotificationKind::EmergencyAccessGranted => Priority::High,
+        }
+    }
+}
+
+impl fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for NotificationKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        NotificationKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown notification kind: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NotificationModel {
+    pub id: i32,
+    /// e.g. `role_grant.expiring`; kept as text so kinds added later still list.
+    pub kind: String,
+    /// The user the notification is about.
+    pub subject_user_id: Option<i32>,
+    pub payload: serde_json::Value,
+    /// `normal` or `high`.
+    pub priority: String,
+    pub created_at: NaiveDateTime,
+    pub read_at: Option<NaiveDateTime>,
+}
+
+impl From<NotificationEntity> for NotificationModel {
+    fn from(entity: NotificationEntity) -> Self {
+        Self {
+            id: entity.id,
+            kind: entity.kind,
+            subject_user_id: entity.subject_user_id,
+            payload: entity.payload,
+            priority: entity.priority,
+            created_at: entity.created_at,
+            read_at: entity.read_at,
+        }
+    }
+}
+
+/// Query string of `GET /notifications`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListNotificationsQueryModel {
+    /// Only notifications not yet marked as read.
+    pub unread: Option<bool>,
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use utoipa::ToSchema;
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 20;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Page envelope for cursor-paginated lists. Pass `next_cursor` back as `cursor` to get the
+/// following page; it is `None` on the last page.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PageModel<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+    pub limit: i64,
+}
+
+pub fn clamp_limit(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+}
+
+/// Cursors are opaque to clients: base64url of the JSON position of the last item.
+pub fn encode_cursor<C: Serialize>(cursor: &C) -> Result<String> {
+    Ok(URL_SAFE_NO_PAD.encode(serde_json::to_vec(cursor)?))
+}
+
+pub fn decode_cursor<C: DeserializeOwned>(cursor: &str) -> Result<C> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(cursor)
+        .map_err(|_| anyhow::anyhow!("Invalid cursor"))?;
+
+    serde_json::from_slice(&bytes).map_err(|_| anyhow::anyhow!("Invalid cursor"))
+}
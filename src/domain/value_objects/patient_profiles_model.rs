@@ -0,0 +1,242 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::domain::{
+    entities::patient_profiles::{PatientProfileEntity, UpsertPatientProfileEntity},
+    value_objects::validation::{
+        FREE_TEXT_MAX_LENGTH, NAME_MAX_LENGTH, normalize_phone_number, validate_date_of_birth,
+        validate_name, validate_phone_number, validate_postal_code, validate_text_entries,
+    },
+};
+
+pub const LIST_MAX_ENTRIES: u64 = 50;
+pub const EMERGENCY_CONTACTS_MAX: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Sex {
+    Male,
+    Female,
+    Other,
+    Unknown,
+}
+
+impl fmt::Display for Sex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sex::Male => write!(f, "male"),
+            Sex::Female => write!(f, "female"),
+            Sex::Other => write!(f, "other"),
+            Sex::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Sex {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "male" => Ok(Sex::Male),
+            "female" => Ok(Sex::Female),
+            "other" => Ok(Sex::Other),
+            "unknown" => Ok(Sex::Unknown),
+            _ => Err(anyhow::anyhow!("Unknown sex: {}", s)),
+        }
+    }
+}
+
+/// ABO group with Rh factor, written as on a blood card (`AB+`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum BloodGroup {
+    #[serde(rename = "A+")]
+    APositive,
+    #[serde(rename = "A-")]
+    ANegative,
+    #[serde(rename = "B+")]
+    BPositive,
+    #[serde(rename = "B-")]
+    BNegative,
+    #[serde(rename = "AB+")]
+    AbPositive,
+    #[serde(rename = "AB-")]
+    AbNegative,
+    #[serde(rename = "O+")]
+    OPositive,
+    #[serde(rename = "O-")]
+    ONegative,
+}
+
+impl fmt::Display for BloodGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BloodGroup::APositive => write!(f, "A+"),
+            BloodGroup::ANegative => write!(f, "A-"),
+            BloodGroup::BPositive => write!(f, "B+"),
+            BloodGroup::BNegative => write!(f, "B-"),
+            BloodGroup::AbPositive => write!(f, "AB+"),
+            BloodGroup::AbNegative => write!(f, "AB-"),
+            BloodGroup::OPositive => write!(f, "O+"),
+            BloodGroup::ONegative => write!(f, "O-"),
+        }
+    }
+}
+
+impl FromStr for BloodGroup {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "A+" => Ok(BloodGroup::APositive),
+            "A-" => Ok(BloodGroup::ANegative),
+            "B+" => Ok(BloodGroup::BPositive),
+            "B-" => Ok(BloodGroup::BNegative),
+            "AB+" => Ok(BloodGroup::AbPositive),
+            "AB-" => Ok(BloodGroup::AbNegative),
+            "O+" => Ok(BloodGroup::OPositive),
+            "O-" => Ok(BloodGroup::ONegative),
+            _ => Err(anyhow::anyhow!("Unknown blood group: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+pub struct AddressModel {
+    #[validate(length(min = 1, max = FREE_TEXT_MAX_LENGTH))]
+    pub address_line: String,
+    /// ตำบล / แขวง
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub subdistrict: String,
+    /// อำเภอ / เขต
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub district: String,
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub province: String,
+    #[validate(custom(function = validate_postal_code))]
+    pub postal_code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+pub struct EmergencyContactModel {
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH), custom(function = validate_name))]
+    pub name: String,
+    /// e.g. `mother`, `spouse`.
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub relationship: String,
+    /// Stored in E.164; local Thai numbers are accepted.
+    #[validate(custom(function = validate_phone_number))]
+    pub phone_number: String,
+}
+
+/// Body of `POST` and `PUT /users/me/profile`. `PUT` replaces the whole profile, so omitted
+/// optional fields are cleared.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpsertPatientProfileModel {
+    #[validate(custom(function = validate_date_of_birth))]
+    pub date_of_birth: NaiveDate,
+    pub sex: Sex,
+    pub blood_group: Option<BloodGroup>,
+    /// Known allergies, e.g. `penicillin`.
+    #[serde(default)]
+    #[validate(length(max = LIST_MAX_ENTRIES), custom(function = validate_text_entries))]
+    pub allergies: Vec<String>,
+    /// Chronic conditions, e.g. `type 2 diabetes`.
+    #[serde(default)]
+    #[validate(length(max = LIST_MAX_ENTRIES), custom(function = validate_text_entries))]
+    pub chronic_conditions: Vec<String>,
+    #[validate(nested)]
+    pub address: Option<AddressModel>,
+    #[serde(default)]
+    #[validate(length(max = EMERGENCY_CONTACTS_MAX), nested)]
+    pub emergency_contacts: Vec<EmergencyContactModel>,
+}
+
+impl UpsertPatientProfileModel {
+    /// Canonical form of a model that has passed `validate()`.
+    pub fn normalized(mut self) -> Self {
+        self.allergies = normalize_entries(self.allergies);
+        self.chronic_conditions = normalize_entries(self.chronic_conditions);
+        if let Some(address) = self.address.as_mut() {
+            address.address_line = address.address_line.trim().to_string();
+            address.subdistrict = address.subdistrict.trim().to_string();
+            address.district = address.district.trim().to_string();
+            address.province = address.province.trim().to_string();
+        }
+        for contact in self.emergency_contacts.iter_mut() {
+            contact.name = contact.name.trim().to_string();
+            contact.relationship = contact.relationship.trim().to_string();
+            if let Some(phone_number) = normalize_phone_number(&contact.phone_number) {
+                contact.phone_number = phone_number;
+            }
+        }
+        self
+    }
+
+    pub fn to_entity(&self, user_id: i32) -> Result<UpsertPatientProfileEntity> {
+        Ok(UpsertPatientProfileEntity {
+            user_id,
+            date_of_birth: self.date_of_birth,
+            sex: self.sex.to_string(),
+            blood_group: self.blood_group.map(|blood_group| blood_group.to_string()),
+            allergies: self.allergies.clone(),
+            chronic_conditions: self.chronic_conditions.clone(),
+            address: self
+                .address
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()?,
+            emergency_contacts: serde_json::to_value(&self.emergency_contacts)?,
+            updated_at: chrono::Utc::now().naive_utc(),
+        })
+    }
+}
+
+/// Trimmed, without blanks or case-insensitive duplicates, in input order.
+fn normalize_entries(entries: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let entry = entry.trim().to_string();
+        if !entry.is_empty() && !normalized.iter().any(|e| e.eq_ignore_ascii_case(&entry)) {
+            normalized.push(entry);
+        }
+    }
+    normalized
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PatientProfileModel {
+    pub user_id: i32,
+    pub date_of_birth: NaiveDate,
+    pub sex: Sex,
+    pub blood_group: Option<BloodGroup>,
+    pub allergies: Vec<String>,
+    pub chronic_conditions: Vec<String>,
+    pub address: Option<AddressModel>,
+    pub emergency_contacts: Vec<EmergencyContactModel>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<PatientProfileEntity> for PatientProfileModel {
+    type Error = anyhow::Error;
+
+    fn try_from(entity: PatientProfileEntity) -> Result<Self> {
+        Ok(Self {
+            user_id: entity.user_id,
+            date_of_birth: entity.date_of_birth,
+            sex: entity.sex.parse()?,
+            blood_group: entity.blood_group.as_deref().map(str::parse).transpose()?,
+            allergies: entity.allergies,
+            chronic_conditions: entity.chronic_conditions,
+            address: entity.address.map(serde_json::from_value).transpose()?,
+            emergency_contacts: serde_json::from_value(entity.emergency_contacts)?,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        })
+    }
+}
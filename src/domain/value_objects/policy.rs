@@ -0,0 +1,416 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::value_objects::{principal::Principal, roles::Role};
+
+/// Something a route can require, stored by name in the `permissions` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Permission {
+    #[serde(rename = "user.read")]
+    UserRead,
+    #[serde(rename = "patient.read")]
+    PatientRead,
+    #[serde(rename = "patient.update")]
+    PatientUpdate,
+    #[serde(rename = "patient.register")]
+    PatientRegister,
+    #[serde(rename = "doctor.read")]
+    DoctorRead,
+    #[serde(rename = "doctor.verify")]
+    DoctorVerify,
+    #[serde(rename = "doctor_application.review")]
+    DoctorApplicationReview,
+    #[serde(rename = "role.assign")]
+    RoleAssign,
+    #[serde(rename = "policy.explain")]
+    PolicyExplain,
+    #[serde(rename = "guardianship.manage")]
+    GuardianshipManage,
+    #[serde(rename = "emergency_access.invoke")]
+    EmergencyAccessInvoke,
+    #[serde(rename = "emergency_access.review")]
+    EmergencyAccessReview,
+    #[serde(rename = "consent.manage")]
+    ConsentManage,
+    #[serde(rename = "user.erase")]
+    UserErase,
+    #[serde(rename = "care_team.manage")]
+    CareTeamManage,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 15] = [
+        Permission::UserRead,
+        Permission::PatientRead,
+        Permission::PatientUpdate,
+        Permission::PatientRegister,
+        Permission::DoctorRead,
+        Permission::DoctorVerify,
+        Permission::DoctorApplicationReview,
+        Permission::RoleAssign,
+        Permission::PolicyExplain,
+        Permission::GuardianshipManage,
+        Permission::EmergencyAccessInvoke,
+        Permission::EmergencyAccessReview,
+        Permission::ConsentManage,
+        Permission::UserErase,
+        Permission::CareTeamManage,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Permission::UserRead => "user.read",
+            Permission::PatientRead => "patient.read",
+            Permission::PatientUpdate => "patient.update",
+            Permission::PatientRegister => "patient.register",
+            Permission::DoctorRead => "doctor.read",
+            Permission::DoctorVerify => "doctor.verify",
+            Permission::DoctorApplicationReview => "doctor_application.review",
+            Permission::RoleAssign => "role.assign",
+            Permission::PolicyExplain => "policy.explain",
+            Permission::GuardianshipManage => "guardianship.manage",
+            Permission::EmergencyAccessInvoke => "emergency_access.invoke",
+            Permission::EmergencyAccessReview => "emergency_access.review",
+            Permission::ConsentManage => "consent.manage",
+            Permission::UserErase => "user.erase",
+            Permission::CareTeamManage => "care_team.manage",
+        }
+    }
+
+    /// Whether users hold this permission on their own records without any role.
+    pub fn granted_to_owner(self) -> bool {
+        matches!(
+            self,
+            Permission::UserRead
+                | Permission::PatientRead
+                | Permission::PatientUpdate
+                | Permission::DoctorRead
+        )
+    }
+
+    /// Whether caring for a patient, through a care assignment or by breaking the glass,
+    /// grants this permission on their records.
+    pub fn granted_to_care_team(self) -> bool {
+        matches!(
+            self,
+            Permission::UserRead | Permission::PatientRead | Permission::PatientUpdate
+        )
+    }
+
+    /// Whether a service account or API key scope carries this permission.
+    pub fn granted_by_scope(self, scope: &str) -> bool {
+        matches!(
+            (scope, self),
+            ("users:read", Permission::UserRead | Permission::PatientRead)
+                | ("users:write", Permission::PatientUpdate)
+        )
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Permission::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown permission: {}", s))
+    }
+}
+
+/// A permission and the role it comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RolePermission {
+    pub role: Role,
+    pub permission: Permission,
+}
+
+/// What the policy knows about the caller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicySubject {
+    pub principal: Principal,
+    /// The hospital the request is for.
+    pub hospital_id: i32,
+    /// Roles currently held; empty for services and API keys.
+    pub roles: Vec<Role>,
+    pub role_permissions: Vec<RolePermission>,
+    /// Patients the user is assigned to care for at the hospital; empty for everyone else.
+    pub care_patient_ids: Vec<i32>,
+    /// Patients the user broke the glass for at the hospital and whose access has not expired;
+    /// empty for everyone else.
+    pub emergency_patient_ids: Vec<i32>,
+    /// `false` for soft-deleted users.
+    pub active: bool,
+}
+
+/// The rule that decided, in evaluation order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum PolicyReason {
+    InactiveUser,
+    /// A service account or API key used at another hospital than its own.
+    OtherHospital,
+    RoleGrant {
+        role: Role,
+    },
+    ScopeGrant {
+        scope: String,
+    },
+    ResourceOwner,
+    CareTeam,
+    EmergencyAccess,
+    NotGranted,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PolicyDecision {
+    pub allowed: bool,
+    pub permission: Permission,
+    pub reason: PolicyReason,
+    /// What the decision was made from.
+    pub roles: Vec<Role>,
+    pub scopes: Vec<String>,
+    pub resource_owner_id: Option<i32>,
+}
+
+/// Inactive users, and services and API keys of another hospital, are denied; otherwise a role
+/// grant, a scope, owning the resource, caring for its owner or, last, a break-the-glass access
+/// to its owner allows. Doctors hold no role grant on patient records, so only the last two let
+/// them at a patient they do not own.
+pub fn evaluate(
+    subject: &PolicySubject,
+    permission: Permission,
+    resource_owner_id: Option<i32>,
+) -> PolicyDecision {
+    let scopes = match &subject.principal {
+        Principal::User { .. } | Principal::Delegate { .. } => Vec::new(),
+        Principal::Service { scopes, .. } | Principal::ApiKey { scopes, .. } => scopes.clone(),
+    };
+
+    let reason = if !subject.active {
+        PolicyReason::InactiveUser
+    } else if subject
+        .principal
+        .hospital_id()
+        .is_some_and(|hospital_id| hospital_id != subject.hospital_id)
+    {
+        PolicyReason::OtherHospital
+    } else if let Some(grant) = subject
+        .role_permissions
+        .iter()
+        .find(|grant| grant.permission == permission)
+    {
+        PolicyReason::RoleGrant { role: grant.role }
+    } else if let Some(scope) = scopes
+        .iter()
+        .find(|scope| permission.granted_by_scope(scope))
+    {
+        PolicyReason::ScopeGrant {
+            scope: scope.clone(),
+        }
+    } else if permission.granted_to_owner()
+        && matches!(
+            (&subject.principal, resource_owner_id),
+            (
+                Principal::User { user_id } | Principal::Delegate { user_id, .. },
+                Some(owner_id)
+            ) if *user_id == owner_id
+        )
+    {
+        PolicyReason::ResourceOwner
+    } else if permission.granted_to_care_team()
+        && resource_owner_id.is_some_and(|owner_id| subject.care_patient_ids.contains(&owner_id))
+    {
+        PolicyReason::CareTeam
+    } else if permission.granted_to_care_team()
+        && resource_owner_id
+            .is_some_and(|owner_id| subject.emergency_patient_ids.contains(&owner_id))
+    {
+        PolicyReason::EmergencyAccess
+    } else {
+        PolicyReason::NotGranted
+    };
+
+    PolicyDecision {
+        allowed: !matches!(
+            reason,
+            PolicyReason::InactiveUser | PolicyReason::OtherHospital | PolicyReason::NotGranted
+        ),
+        permission,
+        reason,
+        roles: subject.roles.clone(),
+        scopes,
+        resource_owner_id,
+    }
+}
+
+/// Body of `POST /admin/policy/explain`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExplainPolicyModel {
+    pub user_id: i32,
+    pub permission: Permission,
+    /// Owner of the record being accessed, e.g. the `user_id` in the path.
+    pub resource_owner_id: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOSPITAL_ID: i32 = 1;
+    const DOCTOR_ID: i32 = 10;
+    const PATIENT_ID: i32 = 20;
+
+    /// A doctor as seeded: `user.read` and `doctor.read` through the role, nothing on patients.
+    fn doctor() -> PolicySubject {
+        PolicySubject {
+            principal: Principal::User { user_id: DOCTOR_ID },
+            hospital_id: HOSPITAL_ID,
+            roles: vec![Role::Doctor],
+            role_permissions: [Permission::UserRead, Permission::DoctorRead]
+                .into_iter()
+                .map(|permission| RolePermission {
+                    role: Role::Doctor,
+                    permission,
+                })
+                .collect(),
+            care_patient_ids: Vec::new(),
+            emergency_patient_ids: Vec::new(),
+            active: true,
+        }
+    }
+
+    #[test]
+    fn doctor_cannot_read_a_patient_outside_their_care() {
+        let decision = evaluate(&doctor(), Permission::PatientRead, Some(PATIENT_ID));
+
+        assert!(!decision.allowed);
+        assert_eq!(decision.reason, PolicyReason::NotGranted);
+    }
+
+    #[test]
+    fn care_assignment_grants_the_patient_records() {
+        let subject = PolicySubject {
+            care_patient_ids: vec![PATIENT_ID],
+            ..doctor()
+        };
+
+        for permission in [Permission::PatientRead, Permission::PatientUpdate] {
+            let decision = evaluate(&subject, permission, Some(PATIENT_ID));
+
+            assert!(decision.allowed);
+            assert_eq!(decision.reason, PolicyReason::CareTeam);
+        }
+
+        assert!(!evaluate(&subject, Permission::PatientRead, Some(PATIENT_ID + 1)).allowed);
+    }
+
+    #[test]
+    fn breaking_the_glass_grants_the_patient_records() {
+        let subject = PolicySubject {
+            emergency_patient_ids: vec![PATIENT_ID],
+            ..doctor()
+        };
+
+        for permission in [Permission::PatientRead, Permission::PatientUpdate] {
+            let decision = evaluate(&subject, permission, Some(PATIENT_ID));
+
+            assert!(decision.allowed);
+            assert_eq!(decision.reason, PolicyReason::EmergencyAccess);
+        }
+
+        assert!(!evaluate(&subject, Permission::PatientRead, Some(PATIENT_ID + 1)).allowed);
+        assert!(!evaluate(&subject, Permission::DoctorVerify, Some(PATIENT_ID)).allowed);
+    }
+
+    #[test]
+    fn care_assignment_is_preferred_over_breaking_the_glass() {
+        let subject = PolicySubject {
+            care_patient_ids: vec![PATIENT_ID],
+            emergency_patient_ids: vec![PATIENT_ID],
+            ..doctor()
+        };
+
+        let decision = evaluate(&subject, Permission::PatientRead, Some(PATIENT_ID));
+
+        assert_eq!(decision.reason, PolicyReason::CareTeam);
+    }
+
+    #[test]
+    fn inactive_user_is_denied_despite_breaking_the_glass() {
+        let subject = PolicySubject {
+            emergency_patient_ids: vec![PATIENT_ID],
+            active: false,
+            ..doctor()
+        };
+
+        let decision = evaluate(&subject, Permission::PatientRead, Some(PATIENT_ID));
+
+        assert!(!decision.allowed);
+        assert_eq!(decision.reason, PolicyReason::InactiveUser);
+    }
+
+    #[test]
+    fn patient_reads_their_own_records_without_a_role() {
+        let subject = PolicySubject {
+            principal: Principal::User {
+                user_id: PATIENT_ID,
+            },
+            hospital_id: HOSPITAL_ID,
+            roles: vec![Role::Patient],
+            role_permissions: Vec::new(),
+            care_patient_ids: Vec::new(),
+            emergency_patient_ids: Vec::new(),
+            active: true,
+        };
+
+        let decision = evaluate(&subject, Permission::PatientRead, Some(PATIENT_ID));
+
+        assert_eq!(decision.reason, PolicyReason::ResourceOwner);
+    }
+
+    #[test]
+    fn api_key_is_denied_at_another_hospital() {
+        let api_key = |hospital_id| PolicySubject {
+            principal: Principal::ApiKey {
+                api_key_id: 1,
+                hospital_id,
+                scopes: vec!["users:read".to_string()],
+            },
+            hospital_id: HOSPITAL_ID,
+            roles: Vec::new(),
+            role_permissions: Vec::new(),
+            care_patient_ids: Vec::new(),
+            emergency_patient_ids: Vec::new(),
+            active: true,
+        };
+
+        let decision = evaluate(
+            &api_key(HOSPITAL_ID),
+            Permission::PatientRead,
+            Some(PATIENT_ID),
+        );
+        assert_eq!(
+            decision.reason,
+            PolicyReason::ScopeGrant {
+                scope: "users:read".to_string()
+            }
+        );
+
+        let decision = evaluate(
+            &api_key(HOSPITAL_ID + 1),
+            Permission::PatientRead,
+            Some(PATIENT_ID),
+        );
+        assert!(!decision.allowed);
+        assert_eq!(decision.reason, PolicyReason::OtherHospital);
+    }
+}
@@ -0,0 +1,47 @@
+/// The authenticated caller of a request, inserted into request extensions by the
+/// authorization middlewares.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Principal {
+    User {
+        user_id: i32,
+    },
+    /// A guardian acting for a dependent with a delegated token. Judged as the dependent's own
+    /// records only: none of the dependent's roles carry over.
+    Delegate {
+        user_id: i32,
+        guardian_id: i32,
+    },
+    Service {
+        client_id: String,
+        hospital_id: i32,
+        scopes: Vec<String>,
+    },
+    ApiKey {
+        api_key_id: i32,
+        hospital_id: i32,
+        scopes: Vec<String>,
+    },
+}
+
+impl Principal {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        match self {
+            // end-user tokens are not scoped
+            Principal::User { .. } | Principal::Delegate { .. } => true,
+            Principal::Service { scopes, .. } | Principal::ApiKey { scopes, .. } => {
+                scopes.iter().any(|s| s == scope)
+            }
+        }
+    }
+
+    /// The hospital a service account or API key was created at, the only one it works at.
+    /// `None` for users, who are judged on the roles they hold at each hospital instead.
+    pub fn hospital_id(&self) -> Option<i32> {
+        match self {
+            Principal::User { .. } | Principal::Delegate { .. } => None,
+            Principal::Service { hospital_id, .. } | Principal::ApiKey { hospital_id, .. } => {
+                Some(*hospital_id)
+            }
+        }
+    }
+}
@@ -1,20 +1,131 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{ValidationError, ValidationErrors};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum Roles {
+/// A role as stored in the `roles` table and carried in access token claims.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Role {
     Patient,
     Doctor,
+    Admin,
+    Nurse,
+    Pharmacist,
+    Receptionist,
 }
 
-//finding a way to derive string from this enum
+impl Role {
+    pub const ALL: [Role; 6] = [
+        Role::Patient,
+        Role::Doctor,
+        Role::Admin,
+        Role::Nurse,
+        Role::Pharmacist,
+        Role::Receptionist,
+    ];
 
-impl fmt::Display for Roles {
+    /// Roles that sign in through the staff logins and carry staff tokens.
+    pub fn is_clinical_staff(self) -> bool {
+        matches!(self, Role::Nurse | Role::Pharmacist | Role::Receptionist)
+    }
+}
+
+impl fmt::Display for Role {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Roles::Patient => write!(f, "Patient"),
-            Roles::Doctor => write!(f, "Doctor"),
+            Role::Patient => write!(f, "Patient"),
+            Role::Doctor => write!(f, "Doctor"),
+            Role::Admin => write!(f, "Admin"),
+            Role::Nurse => write!(f, "Nurse"),
+            Role::Pharmacist => write!(f, "Pharmacist"),
+            Role::Receptionist => write!(f, "Receptionist"),
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Role::ALL
+            .into_iter()
+            .find(|role| role.to_string() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown role: {}", s))
+    }
+}
+
+/// Body of `POST /admin/users/{user_id}/roles`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssignRoleModel {
+    pub role: Role,
+    /// UTC; omitted for a grant that takes effect immediately.
+    pub valid_from: Option<NaiveDateTime>,
+    /// UTC; omitted for a grant that does not expire.
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+impl AssignRoleModel {
+    /// Also rejects `Doctor`, which is granted only by approving a doctor application.
+    pub fn validate_assignment(&self) -> Result<(), ValidationErrors> {
+        let now = chrono::Utc::now().naive_utc();
+        let mut errors = ValidationErrors::new();
+
+        if self.role == Role::Doctor {
+            errors.add(
+                "role",
+                ValidationError::new("role_requires_application")
+                    .with_message("Doctor is granted by approving a doctor application".into()),
+            );
+        }
+
+        if self
+            .valid_until
+            .is_some_and(|valid_until| valid_until <= now)
+        {
+            errors.add(
+                "valid_until",
+                ValidationError::new("valid_until_past")
+                    .with_message("Expiry must be in the future".into()),
+            );
+        }
+
+        if self
+            .valid_from
+            .zip(self.valid_until)
+            .is_some_and(|(valid_from, valid_until)| valid_from >= valid_until)
+        {
+            errors.add(
+                "valid_from",
+                ValidationError::new("valid_range")
+                    .with_message("Start must be before expiry".into()),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
 }
+
+/// A role grant that has not ended yet, including grants that start in the future.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RoleGrantModel {
+    pub role: Role,
+    pub granted_by: Option<i32>,
+    pub granted_at: NaiveDateTime,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+/// What one run of the role grant expiry task did.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RoleGrantSweepModel {
+    /// Grants admins were warned about.
+    pub notified: usize,
+    pub expired: usize,
+    pub revoked_sessions: usize,
+}
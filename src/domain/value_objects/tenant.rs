@@ -0,0 +1,18 @@
+use crate::domain::entities::hospitals::HospitalEntity;
+
+/// The hospital a request is served for, inserted into request extensions by
+/// `tenant_resolution`. Users, role grants and hospital numbers are looked up within it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tenant {
+    pub hospital_id: i32,
+    pub code: String,
+}
+
+impl From<HospitalEntity> for Tenant {
+    fn from(hospital: HospitalEntity) -> Self {
+        Self {
+            hospital_id: hospital.id,
+            code: hospital.code,
+        }
+    }
+}
@@ -1,19 +1,72 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
-use crate::domain::{entities::users::RegisterUserEntity, value_objects::roles::Roles};
+use crate::domain::{
+    entities::users::{RegisterUserEntity, UserEntity},
+    value_objects::{
+        consents_model::{ConsentChannel, RegistrationConsents},
+        pagination::SortDirection,
+        roles::Role,
+        validation::{
+            NAME_MAX_LENGTH, PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH, normalize_citizen_id,
+            normalize_phone_number, validate_citizen_id, validate_citizen_id_prefix,
+            validate_date_of_birth, validate_name, validate_password, validate_phone_number,
+            validate_role,
+        },
+    },
+};
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct RegisterUserModel {
+    /// 13-digit Thai citizen ID; dashes and spaces are ignored.
+    #[validate(custom(function = validate_citizen_id))]
     pub citizen_id: String,
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH), custom(function = validate_name))]
     pub first_name: String,
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH), custom(function = validate_name))]
     pub last_name: String,
+    /// Stored in E.164; local Thai numbers such as `081-234-5678` are accepted.
+    #[validate(custom(function = validate_phone_number))]
     pub phone_number: String,
+    #[validate(
+        length(min = PASSWORD_MIN_LENGTH, max = PASSWORD_MAX_LENGTH),
+        custom(function = validate_password)
+    )]
     pub password: String,
+    /// As on the patient's ID card. Only used at the front desk, to confirm a patient who
+    /// already has an account against their profile.
+    #[validate(custom(function = validate_date_of_birth))]
+    pub date_of_birth: Option<NaiveDate>,
+    /// Consent versions accepted, from `GET /consents/current`. Must include the current
+    /// version of every purpose required at registration.
+    #[serde(default)]
+    #[validate(length(max = 50))]
+    pub consent_version_ids: Vec<i32>,
+    /// How the consents were given. Defaults to `web`.
+    pub consent_channel: Option<ConsentChannel>,
 }
 
 impl RegisterUserModel {
+    /// Canonical form of a model that has passed `validate()`.
+    pub fn normalized(mut self) -> Self {
+        self.citizen_id = normalize_citizen_id(&self.citizen_id);
+        self.first_name = self.first_name.trim().to_string();
+        self.last_name = self.last_name.trim().to_string();
+        if let Some(phone_number) = normalize_phone_number(&self.phone_number) {
+            self.phone_number = phone_number;
+        }
+        self
+    }
+
+    pub fn to_registration_consents(&self) -> RegistrationConsents {
+        RegistrationConsents {
+            consent_version_ids: self.consent_version_ids.clone(),
+            channel: self.consent_channel.unwrap_or_default(),
+        }
+    }
+
     pub fn to_entity(&self) -> RegisterUserEntity {
         RegisterUserEntity {
             citizen_id: self.citizen_id.clone(),
@@ -21,28 +74,187 @@ impl RegisterUserModel {
             last_name: self.last_name.clone(),
             phone_number: self.phone_number.clone(),
             password: self.password.clone(),
-            role: vec![Roles::Patient.to_string()],
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: chrono::Utc::now().naive_utc(),
             deleted_at: None,
+            citizen_id_hash: None,
+            phone_number_hash: None,
+            citizen_id_prefix_hashes: Vec::new(),
+            name_romanized: String::new(),
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RegisterUserResponseModel {
-    pub hospital_number: i32,
+    pub user_id: i32,
+    /// Numbered per hospital in its own format, so the same person has a different number at
+    /// each hospital.
+    pub hospital_number: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FindUserByIdResponseModel {
     pub id: i32,
+    /// Hospital number at the hospital the request was made to.
+    pub hospital_number: Option<String>,
     pub citizen_id: String,
     pub first_name: String,
     pub last_name: String,
     pub phone_number: String,
-    pub role: Vec<String>,
+    pub role: Vec<Role>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
 }
+
+impl FindUserByIdResponseModel {
+    pub fn new(user_entity: UserEntity, hospital_number: Option<String>, role: Vec<Role>) -> Self {
+        Self {
+            id: user_entity.id,
+            hospital_number,
+            citizen_id: user_entity.citizen_id,
+            first_name: user_entity.first_name,
+            last_name: user_entity.last_name,
+            phone_number: user_entity.phone_number,
+            role,
+            created_at: user_entity.created_at,
+            updated_at: user_entity.updated_at,
+            deleted_at: user_entity.deleted_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortField {
+    #[default]
+    Id,
+    CreatedAt,
+    FirstName,
+    LastName,
+}
+
+/// Query string of `GET /users`. All filters are combined with AND.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct ListUsersQueryModel {
+    /// Only users holding this role, e.g. `Doctor`.
+    #[validate(custom(function = validate_role))]
+    pub role: Option<String>,
+    /// `true` lists only soft-deleted users; omitted or `false` lists active users.
+    pub deleted: Option<bool>,
+    /// Inclusive lower bound on `created_at`.
+    pub created_from: Option<NaiveDateTime>,
+    /// Exclusive upper bound on `created_at`.
+    pub created_to: Option<NaiveDateTime>,
+    /// Case-insensitive substring of the first or last name.
+    #[validate(length(min = 1, max = NAME_MAX_LENGTH))]
+    pub name: Option<String>,
+    /// Exact phone number; local Thai format is accepted.
+    #[validate(custom(function = validate_phone_number))]
+    pub phone_number: Option<String>,
+    /// Leading digits of the citizen ID (at least 4).
+    #[validate(custom(function = validate_citizen_id_prefix))]
+    pub citizen_id_prefix: Option<String>,
+    pub sort: Option<UserSortField>,
+    pub direction: Option<SortDirection>,
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Page size, 1 to 100 (default 20).
+    pub limit: Option<i64>,
+}
+
+/// Position of the last row of a page, for the sort it was produced with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "sort", content = "value", rename_all = "snake_case")]
+pub enum UserCursorKey {
+    Id,
+    CreatedAt(NaiveDateTime),
+    FirstName(String),
+    LastName(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserCursor {
+    pub id: i32,
+    pub key: UserCursorKey,
+}
+
+impl UserCursor {
+    pub fn from_user(sort: UserSortField, user_entity: &UserEntity) -> Self {
+        let key = match sort {
+            UserSortField::Id => UserCursorKey::Id,
+            UserSortField::CreatedAt => UserCursorKey::CreatedAt(user_entity.created_at),
+            UserSortField::FirstName => UserCursorKey::FirstName(user_entity.first_name.clone()),
+            UserSortField::LastName => UserCursorKey::LastName(user_entity.last_name.clone()),
+        };
+
+        Self {
+            id: user_entity.id,
+            key,
+        }
+    }
+
+    pub fn sort(&self) -> UserSortField {
+        match self.key {
+            UserCursorKey::Id => UserSortField::Id,
+            UserCursorKey::CreatedAt(_) => UserSortField::CreatedAt,
+            UserCursorKey::FirstName(_) => UserSortField::FirstName,
+            UserCursorKey::LastName(_) => UserSortField::LastName,
+        }
+    }
+}
+
+/// Criteria for `UsersRepository::list`. Search values are normalized plaintext; the
+/// repository maps phone and citizen ID onto their blind indexes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserListFilter {
+    pub hospital_id: i32,
+    pub role: Option<Role>,
+    pub deleted: bool,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub name: Option<String>,
+    pub phone_number: Option<String>,
+    pub citizen_id_prefix: Option<String>,
+    pub sort: UserSortField,
+    pub direction: SortDirection,
+    pub after: Option<UserCursor>,
+    pub limit: i64,
+}
+
+/// Query string of `GET /users/search`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct SearchUsersQueryModel {
+    /// Name to match approximately, in Thai or Latin script.
+    #[validate(length(min = 2, max = NAME_MAX_LENGTH))]
+    pub q: String,
+    /// Also match the romanized form of Thai names, so `somchai` finds `สมชาย` (default `true`).
+    pub transliterate: Option<bool>,
+    /// Number of results, 1 to 100 (default 20).
+    pub limit: Option<i64>,
+}
+
+/// A user matched by `UsersRepository::search_by_name` and its trigram similarity (0 to 1).
+#[derive(Debug, Clone)]
+pub struct UserSearchHit {
+    pub user: UserEntity,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserSearchResultModel {
+    pub user: FindUserByIdResponseModel,
+    pub score: f32,
+}
+
+impl UserSearchResultModel {
+    pub fn new(hit: UserSearchHit, hospital_number: Option<String>, role: Vec<Role>) -> Self {
+        Self {
+            user: FindUserByIdResponseModel::new(hit.user, hospital_number, role),
+            score: hit.score,
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use validator::ValidationError;
+
+use crate::domain::value_objects::{hospital_number::check_character, roles::Role};
+
+pub const NAME_MAX_LENGTH: u64 = 100;
+pub const PASSWORD_MIN_LENGTH: u64 = 8;
+pub const PASSWORD_MAX_LENGTH: u64 = 128;
+/// Passwords this long pass with a single character class.
+pub const PASSPHRASE_MIN_LENGTH: usize = 16;
+pub const CITIZEN_ID_PREFIX_MIN_LENGTH: usize = 4;
+/// Longest accepted allergy, condition or address line.
+pub const FREE_TEXT_MAX_LENGTH: u64 = 200;
+
+/// Country code assumed for local numbers written with a leading `0`.
+const DEFAULT_COUNTRY_CODE: &str = "66";
+
+fn error(code: &'static str, message: &'static str) -> ValidationError {
+    ValidationError::new(code).with_message(message.into())
+}
+
+/// Strips the dashes and spaces people type into citizen IDs (`1-2345-67890-12-1`).
+pub fn normalize_citizen_id(citizen_id: &str) -> String {
+    citizen_id
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .collect()
+}
+
+/// Thai citizen ID: 13 digits, the last one a mod-11 check digit over the first twelve.
+pub fn validate_citizen_id(citizen_id: &str) -> Result<(), ValidationError> {
+    let digits = normalize_citizen_id(citizen_id)
+        .chars()
+        .map(|c| c.to_digit(10))
+        .collect::<Option<Vec<u32>>>()
+        .filter(|digits| digits.len() == 13)
+        .ok_or_else(|| error("citizen_id_format", "Citizen ID must be 13 digits"))?;
+
+    let sum: u32 = digits[..12]
+        .iter()
+        .enumerate()
+        .map(|(i, digit)| digit * (13 - i as u32))
+        .sum();
+
+    if (11 - sum % 11) % 10 != digits[12] {
+        return Err(error(
+            "citizen_id_checksum",
+            "Citizen ID check digit does not match",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Leading digits of a citizen ID, as used by staff search.
+pub fn validate_citizen_id_prefix(prefix: &str) -> Result<(), ValidationError> {
+    let prefix = normalize_citizen_id(prefix);
+
+    if !(CITIZEN_ID_PREFIX_MIN_LENGTH..=13).contains(&prefix.len())
+        || !prefix.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(error(
+            "citizen_id_prefix_format",
+            "Citizen ID prefix must be 4 to 13 digits",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Strips the dashes, slashes and spaces people type into hospital numbers and upper-cases the
+/// prefix.
+pub fn normalize_hospital_number(hospital_number: &str) -> String {
+    hospital_number
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' ' | '/'))
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Hospital number as written by `HospitalNumberFormat`: letter prefix, then year, sequence
+/// and check character. Whether the prefix belongs to the hospital is left to the lookup.
+pub fn validate_hospital_number(hospital_number: &str) -> Result<(), ValidationError> {
+    let normalized = normalize_hospital_number(hospital_number);
+    let digits = normalized.trim_start_matches(|c: char| c.is_ascii_uppercase());
+
+    // ปี 2 หลัก + ลำดับอย่างน้อย 3 หลัก + check character (ตัวเลขหรือ X)
+    let mut body = digits.chars().collect::<Vec<_>>();
+    let check = body.pop();
+    if !(5..=23).contains(&body.len())
+        || !body.iter().all(|c| c.is_ascii_digit())
+        || !check.is_some_and(|c| c.is_ascii_digit() || c == 'X')
+    {
+        return Err(error(
+            "hospital_number_format",
+            "Hospital number must be a letter prefix followed by at least 6 digits",
+        ));
+    }
+
+    if check != Some(check_character(&body.into_iter().collect::<String>())) {
+        return Err(error(
+            "hospital_number_checksum",
+            "Hospital number check character does not match",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Thai (consonants, vowels and tone marks) or Latin letters, plus the spaces, hyphens,
+/// apostrophes and dots found in real names.
+pub fn validate_name(name: &str) -> Result<(), ValidationError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(error("name_empty", "Name must not be blank"));
+    }
+
+    let is_allowed = |c: char| {
+        c.is_ascii_alphabetic()
+            || ('\u{00C0}'..='\u{024F}').contains(&c)
+            || ('\u{0E01}'..='\u{0E3A}').contains(&c)
+            || ('\u{0E40}'..='\u{0E4E}').contains(&c)
+            || matches!(c, ' ' | '-' | '\'' | '.')
+    };
+
+    if !name.chars().all(is_allowed) {
+        return Err(error(
+            "name_charset",
+            "Name may only contain Thai or Latin letters, spaces, hyphens, apostrophes and dots",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Normalizes to E.164 (`+66812345678`). Local numbers (`081-234-5678`) get the Thai country
+/// code. Returns `None` when the result is not a plausible E.164 number, or when a Thai number
+/// keeps its leading `0` after `+66`.
+pub fn normalize_phone_number(phone_number: &str) -> Option<String> {
+    let compact: String = phone_number
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '(' | ')' | '.'))
+        .collect();
+
+    let digits = if let Some(international) = compact.strip_prefix('+') {
+        if international
+            .strip_prefix(DEFAULT_COUNTRY_CODE)
+            .is_some_and(|national| national.starts_with('0'))
+        {
+            return None;
+        }
+        international.to_string()
+    } else if let Some(local) = compact.strip_prefix('0') {
+        format!("{}{}", DEFAULT_COUNTRY_CODE, local)
+    } else {
+        compact
+    };
+
+    let is_e164 = (8..=15).contains(&digits.len())
+        && !digits.starts_with('0')
+        && digits.chars().all(|c| c.is_ascii_digit());
+
+    is_e164.then(|| format!("+{}", digits))
+}
+
+pub fn validate_phone_number(phone_number: &str) -> Result<(), ValidationError> {
+    normalize_phone_number(phone_number)
+        .map(|_| ())
+        .ok_or_else(|| error("phone_number_format", "Phone number is not a valid number"))
+}
+
+/// Length is checked separately; this requires two of letters, digits and symbols (spaces do
+/// not count), or a passphrase of `PASSPHRASE_MIN_LENGTH` characters. Thai has no case, so
+/// case is not a class.
+pub fn validate_password(password: &str) -> Result<(), ValidationError> {
+    let has_letter = password.chars().any(char::is_alphabetic);
+    let has_digit = password.chars().any(char::is_numeric);
+    let has_symbol = password
+        .chars()
+        .any(|c| !c.is_alphabetic() && !c.is_numeric() && !c.is_whitespace());
+    let classes = [has_letter, has_digit, has_symbol]
+        .into_iter()
+        .filter(|&has| has)
+        .count();
+
+    if classes < 2 && password.chars().count() < PASSPHRASE_MIN_LENGTH {
+        return Err(error(
+            "password_strength",
+            "Password must mix two of letters, digits and symbols, or be a longer passphrase",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Not in the future and not more than 150 years ago.
+pub fn validate_date_of_birth(date_of_birth: &NaiveDate) -> Result<(), ValidationError> {
+    let today = Utc::now().date_naive();
+
+    if *date_of_birth > today {
+        return Err(error(
+            "date_of_birth_future",
+            "Date of birth must not be in the future",
+        ));
+    }
+    if today.year() - date_of_birth.year() > 150 {
+        return Err(error(
+            "date_of_birth_range",
+            "Date of birth is too far in the past",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Thai postal code: 5 digits.
+pub fn validate_postal_code(postal_code: &str) -> Result<(), ValidationError> {
+    if postal_code.len() != 5 || !postal_code.chars().all(|c| c.is_ascii_digit()) {
+        return Err(error("postal_code_format", "Postal code must be 5 digits"));
+    }
+
+    Ok(())
+}
+
+/// Entries of free-text lists such as allergies: none blank, none longer than
+/// `FREE_TEXT_MAX_LENGTH`.
+pub fn validate_text_entries(entries: &[String]) -> Result<(), ValidationError> {
+    if entries.iter().any(|entry| entry.trim().is_empty()) {
+        return Err(error("entry_empty", "Entries must not be blank"));
+    }
+    if entries
+        .iter()
+        .any(|entry| entry.trim().chars().count() as u64 > FREE_TEXT_MAX_LENGTH)
+    {
+        return Err(ValidationError::new("entry_length").with_message(
+            format!(
+                "Entries must be at most {} characters",
+                FREE_TEXT_MAX_LENGTH
+            )
+            .into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Medical council license, e.g. `ว.12345`: Thai or Latin letters, digits, dots and hyphens.
+pub fn validate_license_number(license_number: &str) -> Result<(), ValidationError> {
+    let license_number = license_number.trim();
+
+    let is_allowed = |c: char| {
+        c.is_ascii_alphanumeric()
+            || ('\u{0E01}'..='\u{0E2E}').contains(&c)
+            || matches!(c, '.' | '-')
+    };
+
+    if !(3..=32).contains(&license_number.chars().count())
+        || !license_number.chars().all(is_allowed)
+        || !license_number.chars().any(|c| c.is_ascii_digit())
+    {
+        return Err(error(
+            "license_number_format",
+            "License number must be 3 to 32 letters, digits, dots or hyphens",
+        ));
+    }
+
+    Ok(())
+}
+
+/// ISO 639-1 language codes (`th`, `en`).
+pub fn validate_language_codes(languages: &[String]) -> Result<(), ValidationError> {
+    if !languages
+        .iter()
+        .all(|language| language.len() == 2 && language.chars().all(|c| c.is_ascii_alphabetic()))
+    {
+        return Err(error(
+            "language_code_format",
+            "Languages must be two-letter ISO 639-1 codes",
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_role(role: &str) -> Result<(), ValidationError> {
+    if role.parse::<Role>().is_err() {
+        return Err(error("role_unknown", "Unknown role"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CITIZEN_ID: &str = "1103700201238";
+
+    #[test]
+    fn citizen_id_accepts_a_valid_check_digit() {
+        assert_eq!(validate_citizen_id(CITIZEN_ID), Ok(()));
+        assert_eq!(validate_citizen_id("1-1037-00201-23-8"), Ok(()));
+    }
+
+    #[test]
+    fn citizen_id_rejects_a_check_digit_off_by_one() {
+        for citizen_id in ["1103700201237", "1103700201239"] {
+            let error = validate_citizen_id(citizen_id).unwrap_err();
+
+            assert_eq!(error.code, "citizen_id_checksum", "{}", citizen_id);
+        }
+    }
+
+    #[test]
+    fn citizen_id_rejects_a_wrong_length() {
+        for citizen_id in ["110370020123", "11037002012380", ""] {
+            let error = validate_citizen_id(citizen_id).unwrap_err();
+
+            assert_eq!(error.code, "citizen_id_format", "{}", citizen_id);
+        }
+    }
+
+    #[test]
+    fn phone_number_adds_the_country_code_to_local_numbers() {
+        assert_eq!(
+            normalize_phone_number("081-234-5678"),
+            Some("+66812345678".to_string())
+        );
+    }
+
+    #[test]
+    fn phone_number_keeps_international_numbers() {
+        assert_eq!(
+            normalize_phone_number("+66812345678"),
+            Some("+66812345678".to_string())
+        );
+    }
+
+    #[test]
+    fn phone_number_rejects_a_trunk_zero_after_the_country_code() {
+        assert_eq!(normalize_phone_number("+66 081-234-5678"), None);
+    }
+
+    #[test]
+    fn password_passes_with_two_classes() {
+        assert_eq!(validate_password("password1"), Ok(()));
+        assert_eq!(validate_password("รหัสผ่าน!!"), Ok(()));
+    }
+
+    #[test]
+    fn password_fails_with_one_class() {
+        let error = validate_password("password").unwrap_err();
+
+        assert_eq!(error.code, "password_strength");
+    }
+
+    #[test]
+    fn password_passes_as_a_long_passphrase() {
+        assert_eq!(validate_password("correcthorsebatterystaple"), Ok(()));
+    }
+
+    #[test]
+    fn password_does_not_count_whitespace_as_a_symbol() {
+        let error = validate_password("pass word").unwrap_err();
+
+        assert_eq!(error.code, "password_strength");
+    }
+
+    #[test]
+    fn name_rejects_thai_digits_and_symbols() {
+        assert_eq!(validate_name("สมชาย ใจดี"), Ok(()));
+        for name in ["สมชาย๑", "สมชาย฿", "๏สมชาย"] {
+            let error = validate_name(name).unwrap_err();
+
+            assert_eq!(error.code, "name_charset", "{}", name);
+        }
+    }
+}
@@ -1,8 +1,52 @@
 use serde::Serialize;
 use utoipa::ToSchema;
+use validator::{ValidationErrors, ValidationErrorsKind};
 
 #[derive(Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub data: Option<T>,
     pub message: Option<String>,
 }
+
+/// One failed rule on one input field, returned with `422 Unprocessable Entity`.
+#[derive(Serialize, ToSchema)]
+pub struct FieldErrorModel {
+    pub field: String,
+    pub code: String,
+    pub message: Option<String>,
+}
+
+impl FieldErrorModel {
+    /// Flattens nested errors into dotted paths, e.g. `address.postal_code` or
+    /// `emergency_contacts[0].phone_number`.
+    pub fn from_validation_errors(errors: &ValidationErrors) -> Vec<Self> {
+        let mut field_errors = Vec::new();
+        collect_field_errors(errors, "", &mut field_errors);
+        field_errors.sort_by(|a, b| a.field.cmp(&b.field));
+
+        field_errors
+    }
+}
+
+fn collect_field_errors(errors: &ValidationErrors, prefix: &str, out: &mut Vec<FieldErrorModel>) {
+    for (field, kind) in errors.errors() {
+        let path = format!("{}{}", prefix, field);
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                out.extend(errors.iter().map(|error| FieldErrorModel {
+                    field: path.clone(),
+                    code: error.code.to_string(),
+                    message: error.message.as_ref().map(|message| message.to_string()),
+                }));
+            }
+            ValidationErrorsKind::Struct(errors) => {
+                collect_field_errors(errors, &format!("{}.", path), out);
+            }
+            ValidationErrorsKind::List(errors) => {
+                for (index, errors) in errors {
+                    collect_field_errors(errors, &format!("{}[{}].", path, index), out);
+                }
+            }
+        }
+    }
+}
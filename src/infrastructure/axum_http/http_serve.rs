@@ -3,23 +3,42 @@ use std::{net::SocketAddr, sync::Arc, time::Duration};
 use anyhow::Result;
 use axum::{
     Router,
-    http::{HeaderValue, Method, header},
+    extract::{ConnectInfo, Request},
+    http::{HeaderName, HeaderValue, Method, header},
+    middleware::from_fn_with_state,
     routing::get,
 };
-use tokio::net::TcpListener;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto,
+    service::TowerToHyperService,
+};
+use tokio::{net::TcpListener, sync::watch, task::JoinSet};
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
 use tower_http::{
     cors::{Any, CorsLayer},
     limit::RequestBodyLimitLayer,
     timeout::TimeoutLayer,
     trace::TraceLayer,
 };
-use tracing::info;
+use tracing::{info, warn};
 use utoipa::openapi::InfoBuilder;
 
 use crate::{
-    config::{config_loader, config_model::DotEnvyConfig, stage::Stage},
+    config::{
+        config_loader,
+        config_model::{DotEnvyConfig, Tls},
+        stage::Stage,
+    },
     infrastructure::{
-        axum_http::{routers, swagger},
+        axum_http::{
+            middleware::{
+                DelegationAudit, SessionValidation, TenantResolution, delegation_audit,
+                session_validation, tenant_resolution,
+            },
+            routers, swagger, tls,
+        },
         postgres::postgres_connection::PgPoolSquad,
     },
 };
@@ -28,7 +47,30 @@ use super::default_routers;
 
 pub async fn start(config: Arc<DotEnvyConfig>, db_pool: PgPoolSquad) -> Result<()> {
     let routes = routers::authentication::routes_with_openapi(db_pool.clone())
-        .merge(routers::users::routes_with_openapi(db_pool.clone()));
+        .merge(routers::users::routes_with_openapi(db_pool.clone()))
+        .merge(routers::oauth::routes_with_openapi(db_pool.clone()))
+        .merge(routers::api_keys::routes_with_openapi(db_pool.clone()))
+        .merge(routers::doctor_profiles::routes_with_openapi(
+            db_pool.clone(),
+        ))
+        .merge(routers::patient_profiles::routes_with_openapi(
+            db_pool.clone(),
+        ))
+        .merge(routers::doctor_applications::routes_with_openapi(
+            db_pool.clone(),
+        ))
+        .merge(routers::admin::routes_with_openapi(db_pool.clone()))
+        .merge(routers::notifications::routes_with_openapi(db_pool.clone()))
+        .merge(routers::guardianships::routes_with_openapi(db_pool.clone()))
+        .merge(routers::care_assignments::routes_with_openapi(
+            db_pool.clone(),
+        ))
+        .merge(routers::emergency_accesses::routes_with_openapi(
+            db_pool.clone(),
+        ))
+        .merge(routers::consents::routes_with_openapi(db_pool.clone()))
+        .merge(routers::data_exports::routes_with_openapi(db_pool.clone()))
+        .merge(routers::erasures::routes_with_openapi(db_pool.clone()));
 
     let mut openapi = routes.get_openapi().clone();
     openapi.info = InfoBuilder::new()
@@ -37,6 +79,23 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: PgPoolSquad) -> Result<(
         .build();
     let swagger_ui = swagger::create_swagger_ui(openapi)?;
 
+    // ทุก route ของ API ทำงานในนามโรงพยาบาลหนึ่ง ส่วน swagger กับ health check ไม่ต้องมี
+    // delegation_audit ต้องอยู่ใน tenant_resolution เพื่อบันทึกโรงพยาบาลของ request ได้
+    // session_validation อยู่นอกสุด token ของ session ที่ถูก revoke จึงไม่ถูกใช้เลือกโรงพยาบาล
+    let routes = routes
+        .layer(from_fn_with_state(
+            DelegationAudit::new(db_pool.clone()),
+            delegation_audit,
+        ))
+        .layer(from_fn_with_state(
+            TenantResolution::new(db_pool.clone()),
+            tenant_resolution,
+        ))
+        .layer(from_fn_with_state(
+            SessionValidation::new(db_pool),
+            session_validation,
+        ));
+
     let mut app = Router::new()
         .fallback(default_routers::not_found)
         // .nest("/users", routers::users::routes(db_pool.clone()))
@@ -60,7 +119,12 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: PgPoolSquad) -> Result<(
             Method::PATCH,
             Method::DELETE,
         ])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            HeaderName::from_static("idempotency-key"),
+            HeaderName::from_static("x-hospital"),
+        ])
         .allow_credentials(true)
         .allow_origin(
             config
@@ -78,7 +142,12 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: PgPoolSquad) -> Result<(
             Method::PATCH,
             Method::DELETE,
         ])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            HeaderName::from_static("idempotency-key"),
+            HeaderName::from_static("x-hospital"),
+        ])
         .allow_credentials(true)
         .allow_origin(
             config
@@ -115,11 +184,113 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: PgPoolSquad) -> Result<(
 
     let listener = TcpListener::bind(addr).await?;
 
+    if let Some(tls_config) = &config.tls {
+        info!("Server is running on port {} (TLS)", config.server.port);
+        return serve_tls(listener, app, tls_config).await;
+    }
+
     info!("Server is running on port {}", config.server.port);
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    // ConnectInfo ใช้ตรวจ IP allowlist ของ API key
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
+    Ok(())
+}
+
+/// Terminates TLS in-process. Each request gets the same `ConnectInfo` as the plain listener,
+/// plus the verified `ClientCertificate` when the caller presented one. On shutdown it stops
+/// accepting, lets open connections finish their in-flight requests, then returns.
+async fn serve_tls(listener: TcpListener, app: Router, tls_config: &Tls) -> Result<()> {
+    let acceptor = TlsAcceptor::from(tls::load_server_config(tls_config)?);
+
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    let (shutdown_sender, shutdown_receiver) = watch::channel(());
+    let mut connections = JoinSet::new();
+
+    loop {
+        let (stream, peer_addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("Failed to accept connection: {}", e);
+                    continue;
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        let mut shutdown_receiver = shutdown_receiver.clone();
+
+        connections.spawn(async move {
+            let tls_stream = tokio::select! {
+                accepted = acceptor.accept(stream) => match accepted {
+                    Ok(tls_stream) => tls_stream,
+                    Err(e) => {
+                        warn!("TLS handshake with {} failed: {}", peer_addr, e);
+                        return;
+                    }
+                },
+                // ยังไม่มี request ค้างอยู่ ปิดได้เลย
+                _ = shutdown_receiver.changed() => return,
+            };
+
+            let client_certificate = tls_stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .and_then(tls::client_certificate);
+
+            let service = app.map_request(move |mut req: Request<hyper::body::Incoming>| {
+                req.extensions_mut().insert(ConnectInfo(peer_addr));
+                if let Some(client_certificate) = &client_certificate {
+                    req.extensions_mut().insert(client_certificate.clone());
+                }
+                req.map(axum::body::Body::new)
+            });
+
+            let builder = auto::Builder::new(TokioExecutor::new());
+            let connection = builder.serve_connection_with_upgrades(
+                TokioIo::new(tls_stream),
+                TowerToHyperService::new(service),
+            );
+            tokio::pin!(connection);
+
+            let result = tokio::select! {
+                result = connection.as_mut() => result,
+                _ = shutdown_receiver.changed() => {
+                    // ตอบ request ที่ค้างอยู่ให้จบแล้วปิด connection (HTTP/2 ส่ง GOAWAY)
+                    connection.as_mut().graceful_shutdown();
+                    connection.await
+                }
+            };
+
+            if let Err(e) = result {
+                warn!("Connection with {} closed with error: {}", peer_addr, e);
+            }
+        });
+
+        // เก็บเฉพาะ connection ที่ยังเปิดอยู่
+        while connections.try_join_next().is_some() {}
+    }
+
+    drop(listener);
+    let _ = shutdown_sender.send(());
+
+    info!(
+        "Waiting for {} open connections to finish",
+        connections.len()
+    );
+    while connections.join_next().await.is_some() {}
+
     Ok(())
 }
 
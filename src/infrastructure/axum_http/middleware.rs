@@ -1,55 +1,775 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
 use axum::{
-    extract::Request,
-    http::{StatusCode, header},
+    Json, RequestExt,
+    body::{Body, HttpBody, to_bytes},
+    extract::{ConnectInfo, MatchedPath, RawPathParams, Request, State},
+    http::{Extensions, HeaderMap, HeaderValue, Method, StatusCode, header},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use base64::{Engine, engine::general_purpose::STANDARD};
 
 use crate::{
-    config::config_loader::{get_doctors_secret_env, get_patients_secret_env},
-    infrastructure::jwt_authentication,
+    application::usecases::{
+        api_keys::ApiKeysUseCase,
+        idempotency::{IdempotencyOutcome, IdempotencyUseCase, is_valid_idempotency_key},
+        oauth::service_token_scopes,
+        policy::PolicyUseCase,
+    },
+    config::{
+        config_loader::{
+            get_doctors_secret_env, get_idempotency_env, get_oauth_secret_env,
+            get_patients_secret_env, get_staff_secret_env, get_tenancy_env,
+        },
+        config_model::Tenancy,
+    },
+    domain::{
+        repositories::{
+            guardianships::GuardianshipsRepository, hospitals::HospitalsRepository,
+            oauth::OAuthRepository, sessions::SessionsRepository, users::UsersRepository,
+        },
+        value_objects::{policy::Permission, principal::Principal, roles::Role, tenant::Tenant},
+    },
+    infrastructure::{
+        axum_http::{api_response::ApiResponse, tls::ClientCertificate},
+        jwt_authentication::{self, jwt_model::Claims},
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{
+                api_keys::ApiKeysPostgres, guardianships::GuardianshipsPostgres,
+                hospitals::HospitalsPostgres, idempotency_keys::IdempotencyKeysPostgres,
+                oauth::OAuthPostgres, permissions::PermissionsPostgres, sessions::SessionsPostgres,
+                users::UsersPostgres,
+            },
+        },
+    },
 };
 
+/// State for `tenant_resolution`.
+#[derive(Clone)]
+pub struct TenantResolution {
+    pub hospitals_repository: Arc<HospitalsPostgres>,
+    pub tenancy: Tenancy,
+}
+
+impl TenantResolution {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self {
+            hospitals_repository: Arc::new(HospitalsPostgres::new(db_pool)),
+            tenancy: get_tenancy_env().expect("Tenancy config is invalid"),
+        }
+    }
+}
+
+/// Resolves the hospital the request is for and inserts it as a `Tenant`: the `X-Hospital`
+/// header, else the subdomain under `TENANT_BASE_DOMAIN`, else the hospital the access token
+/// was issued at, else `DEFAULT_HOSPITAL_CODE`. Unknown hospitals get 404, and a token used
+/// at another hospital than its own gets 403.
+pub async fn tenant_resolution(
+    State(resolution): State<TenantResolution>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let requested_code =
+        get_requested_hospital_code(req.headers(), resolution.tenancy.base_domain.as_deref());
+    let token_hospital_id = get_claims_from_cookie(req.headers()).and_then(|claims| claims.hid);
+
+    let hospitals_repository = &resolution.hospitals_repository;
+    let hospital = match (requested_code, token_hospital_id) {
+        (Some(code), _) => hospitals_repository.find_by_code(code).await,
+        (None, Some(hospital_id)) => hospitals_repository.find_by_id(hospital_id).await,
+        (None, None) => {
+            hospitals_repository
+                .find_by_code(resolution.tenancy.default_hospital_code.clone())
+                .await
+        }
+    }
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    // role ถูก grant แยกตามโรงพยาบาล token ของที่หนึ่งจึงใช้กับอีกที่ไม่ได้ ต้อง login ใหม่
+    if token_hospital_id.is_some_and(|hospital_id| hospital_id != hospital.id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    req.extensions_mut().insert(Tenant::from(hospital));
+    Ok(next.run(req).await)
+}
+
+/// State for `delegation_audit`.
+#[derive(Clone)]
+pub struct DelegationAudit {
+    pub guardianships_repository: Arc<GuardianshipsPostgres>,
+}
+
+impl DelegationAudit {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self {
+            guardianships_repository: Arc::new(GuardianshipsPostgres::new(db_pool)),
+        }
+    }
+}
+
+/// For requests made with a guardian's delegated token: checks the guardianship is still
+/// active, so revoking it takes effect at once (403 otherwise), and audits the request with
+/// its outcome. Other requests pass straight through.
+pub async fn delegation_audit(
+    State(audit): State<DelegationAudit>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let Some((guardian_id, dependent_id)) = get_delegation_from_cookie(req.headers()) else {
+        return Ok(next.run(req).await);
+    };
+
+    audit
+        .guardianships_repository
+        .find_active(guardian_id, dependent_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::FORBIDDEN)?;
+
+    let method = req.method().to_string();
+    let path = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched_path| matched_path.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let hospital_id = req
+        .extensions()
+        .get::<Tenant>()
+        .map(|tenant| tenant.hospital_id);
+
+    let response = next.run(req).await;
+
+    if let Err(e) = audit
+        .guardianships_repository
+        .record_delegated_request(
+            guardian_id,
+            dependent_id,
+            serde_json::json!({
+                "method": method,
+                "path": path,
+                "status": response.status().as_u16(),
+                "hospital_id": hospital_id,
+            }),
+        )
+        .await
+    {
+        tracing::warn!("Failed to audit delegated request: {}", e);
+    }
+
+    Ok(response)
+}
+
+/// State for `session_validation`.
+#[derive(Clone)]
+pub struct SessionValidation {
+    pub sessions_repository: Arc<SessionsPostgres>,
+}
+
+impl SessionValidation {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self {
+            sessions_repository: Arc::new(SessionsPostgres::new(db_pool)),
+        }
+    }
+}
+
+/// Drops the access token cookie when its session has been revoked or has expired, e.g. on
+/// logout, role grant expiry or erasure, so every route treats the request as anonymous at
+/// once instead of when the token expires. Delegated tokens carry no session; `delegation_audit`
+/// checks their guardianship instead.
+pub async fn session_validation(
+    State(validation): State<SessionValidation>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let Some(claims) = get_claims_from_cookie(req.headers()) else {
+        return Ok(next.run(req).await);
+    };
+    if claims.act.is_some() {
+        return Ok(next.run(req).await);
+    }
+
+    let session = match claims.sid {
+        Some(sid) => validation
+            .sessions_repository
+            .find_active_by_id(sid)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => None,
+    };
+
+    if session.is_none_or(|session| session.user_id.to_string() != claims.sub) {
+        remove_cookie(req.headers_mut(), "act");
+    }
+
+    Ok(next.run(req).await)
+}
+
+fn get_requested_hospital_code(headers: &HeaderMap, base_domain: Option<&str>) -> Option<String> {
+    let header_code = headers
+        .get("x-hospital")
+        .and_then(|value| value.to_str().ok())
+        .map(|code| code.trim().to_lowercase())
+        .filter(|code| !code.is_empty());
+
+    header_code.or_else(|| {
+        let host = headers.get(header::HOST)?.to_str().ok()?;
+        let host = host.split(':').next()?.to_lowercase();
+        let subdomain = host.strip_suffix(base_domain?)?.strip_suffix('.')?;
+
+        // รับแค่ subdomain ชั้นเดียว เช่น north.medbook.example.com
+        (!subdomain.is_empty() && !subdomain.contains('.')).then(|| subdomain.to_string())
+    })
+}
+
 pub async fn patients_authorization(mut req: Request, next: Next) -> Result<Response, StatusCode> {
-    if let Some(cookie_header) = req.headers().get(header::COOKIE) {
-        if let Ok(cookie_str) = cookie_header.to_str() {
-            let access_token = get_cookie_value(cookie_str, "act");
-
-            if let Some(token) = access_token {
-                if let Ok(secret_env) = get_patients_secret_env() {
-                    if let Ok(claims) = jwt_authentication::verify_token(secret_env.secret, token) {
-                        if let Ok(patient_id) = claims.sub.parse::<i32>() {
-                            req.extensions_mut().insert(patient_id);
-                            return Ok(next.run(req).await);
-                        }
-                    }
-                }
-            }
+    let patient_id = req
+        .headers()
+        .get(header::COOKIE)
+        .and_then(|cookie_header| cookie_header.to_str().ok())
+        .and_then(|cookie_str| get_cookie_value(cookie_str, "act"))
+        .and_then(|token| {
+            let secret_env = get_patients_secret_env().ok()?;
+            jwt_authentication::verify_token(secret_env.secret, token).ok()
+        })
+        .and_then(|claims| claims.sub.parse::<i32>().ok())
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    req.extensions_mut().insert(patient_id);
+    Ok(next.run(req).await)
+}
+
+/// Requires `Doctor` at the hospital in the database, so a revoked or expired grant stops
+/// working before the token does.
+pub async fn doctors_authorization(
+    State(users_repository): State<Arc<UsersPostgres>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let hospital_id = get_tenant_hospital_id(&req)?;
+    let doctor_id = authorize_roles(
+        &users_repository,
+        hospital_id,
+        req.headers(),
+        &[Role::Doctor],
+    )
+    .await?;
+
+    req.extensions_mut().insert(doctor_id);
+    Ok(next.run(req).await)
+}
+
+/// Accepts an access token issued by the patient, doctor or staff login.
+pub async fn users_authorization(mut req: Request, next: Next) -> Result<Response, StatusCode> {
+    match get_user_id_from_cookie(req.headers()) {
+        Some(user_id) => {
+            req.extensions_mut().insert(user_id);
+            Ok(next.run(req).await)
         }
+        None => Err(StatusCode::UNAUTHORIZED),
     }
+}
+
+pub async fn admins_authorization(
+    State(users_repository): State<Arc<UsersPostgres>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let hospital_id = get_tenant_hospital_id(&req)?;
+    let user_id = authorize_roles(
+        &users_repository,
+        hospital_id,
+        req.headers(),
+        &[Role::Admin],
+    )
+    .await?;
 
-    Err(StatusCode::UNAUTHORIZED)
+    req.extensions_mut().insert(user_id);
+    Ok(next.run(req).await)
 }
 
-pub async fn doctors_authorization(mut req: Request, next: Next) -> Result<Response, StatusCode> {
-    if let Some(cookie_header) = req.headers().get(header::COOKIE) {
-        if let Ok(cookie_str) = cookie_header.to_str() {
-            let access_token = get_cookie_value(cookie_str, "act");
+/// Checks the cookie, then that the (not deleted) user holds one of `roles` at the hospital in
+/// the database.
+async fn authorize_roles(
+    users_repository: &UsersPostgres,
+    hospital_id: i32,
+    headers: &HeaderMap,
+    roles: &[Role],
+) -> Result<i32, StatusCode> {
+    let user_id = get_user_id_from_cookie(headers).ok_or(StatusCode::UNAUTHORIZED)?;
 
-            if let Some(token) = access_token {
-                if let Ok(secret_env) = get_doctors_secret_env() {
-                    if let Ok(claims) = jwt_authentication::verify_token(secret_env.secret, token) {
-                        if let Ok(doctor_id) = claims.sub.parse::<i32>() {
-                            req.extensions_mut().insert(doctor_id);
-                            return Ok(next.run(req).await);
-                        }
-                    }
-                }
+    let user = users_repository
+        .find_by_id(user_id)
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    if user.deleted_at.is_some() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let held_roles = users_repository
+        .find_roles(hospital_id, user_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !roles.iter().any(|role| held_roles.contains(role)) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(user_id)
+}
+
+/// State for `permission_authorization`: the permission a route requires, plus what is needed
+/// to identify the caller and evaluate the policy.
+#[derive(Clone)]
+pub struct PermissionAuthorization {
+    pub permission: Permission,
+    pub policy_use_case: Arc<PolicyUseCase<UsersPostgres, PermissionsPostgres>>,
+    pub api_keys_use_case: Arc<ApiKeysUseCase<ApiKeysPostgres>>,
+    pub oauth_repository: Arc<OAuthPostgres>,
+}
+
+impl PermissionAuthorization {
+    pub fn new(permission: Permission, db_pool: PgPoolSquad) -> Self {
+        Self {
+            permission,
+            policy_use_case: Arc::new(PolicyUseCase::new(
+                Arc::new(UsersPostgres::new(db_pool.clone())),
+                Arc::new(PermissionsPostgres::new(db_pool.clone())),
+            )),
+            api_keys_use_case: Arc::new(ApiKeysUseCase::new(Arc::new(ApiKeysPostgres::new(
+                db_pool.clone(),
+            )))),
+            oauth_repository: Arc::new(OAuthPostgres::new(db_pool)),
+        }
+    }
+}
+
+/// Identifies the caller (see `resolve_principal`) and lets the policy decide whether they
+/// hold the route's permission at the request's hospital. The resource owner is the `user_id`
+/// path parameter, when the route has one. Inserts the `Principal` and, for users, their id.
+pub async fn permission_authorization(
+    State(authorization): State<PermissionAuthorization>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let hospital_id = get_tenant_hospital_id(&req)?;
+    let client_certificate = req.extensions().get::<ClientCertificate>().cloned();
+    let client_ip = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    let principal = resolve_principal(
+        req.headers(),
+        client_certificate,
+        client_ip,
+        &authorization.api_keys_use_case,
+        &authorization.oauth_repository,
+    )
+    .await?;
+
+    let resource_owner_id = req
+        .extract_parts::<RawPathParams>()
+        .await
+        .ok()
+        .and_then(|params| {
+            params
+                .iter()
+                .find(|(name, _)| *name == "user_id")
+                .and_then(|(_, value)| value.parse::<i32>().ok())
+        });
+
+    let decision = authorization
+        .policy_use_case
+        .authorize(
+            hospital_id,
+            principal.clone(),
+            authorization.permission,
+            resource_owner_id,
+        )
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    if !decision.allowed {
+        tracing::debug!(
+            "Denied {} to {:?}: {:?}",
+            decision.permission,
+            principal,
+            decision.reason
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    if let Principal::User { user_id } | Principal::Delegate { user_id, .. } = principal {
+        req.extensions_mut().insert(user_id);
+    }
+    req.extensions_mut().insert(principal);
+    Ok(next.run(req).await)
+}
+
+/// Accepts a guardian's delegated cookie, an end-user cookie, an `X-Api-Key`, a verified client certificate whose CN is a
+/// service account client id, or a service token (client credentials grant).
+async fn resolve_principal(
+    headers: &HeaderMap,
+    client_certificate: Option<ClientCertificate>,
+    client_ip: Option<IpAddr>,
+    api_keys_use_case: &ApiKeysUseCase<ApiKeysPostgres>,
+    oauth_repository: &OAuthPostgres,
+) -> Result<Principal, StatusCode> {
+    if let Some((guardian_id, user_id)) = get_delegation_from_cookie(headers) {
+        return Ok(Principal::Delegate {
+            user_id,
+            guardian_id,
+        });
+    }
+
+    if let Some(user_id) = get_user_id_from_cookie(headers) {
+        return Ok(Principal::User { user_id });
+    }
+
+    let api_key = headers
+        .get("x-api-key")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    match (api_key, client_certificate) {
+        (Some(api_key), _) => api_keys_use_case
+            .authenticate(api_key, client_ip)
+            .await
+            .map_err(|_| StatusCode::UNAUTHORIZED),
+        (None, Some(client_certificate)) => {
+            // cert ผ่านการตรวจกับ CA แล้วตอน handshake เหลือแค่ map CN -> service account
+            let client_id = client_certificate
+                .common_name
+                .ok_or(StatusCode::UNAUTHORIZED)?;
+
+            let service_account = oauth_repository
+                .find_service_account_by_client_id(client_id)
+                .await
+                .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+            Ok(Principal::Service {
+                client_id: service_account.client_id,
+                hospital_id: service_account.hospital_id,
+                scopes: service_account.scopes,
+            })
+        }
+        (None, None) => get_service_principal(headers, oauth_repository)
+            .await
+            .ok_or(StatusCode::UNAUTHORIZED),
+    }
+}
+
+/// State for `idempotency`.
+#[derive(Clone)]
+pub struct IdempotencyState {
+    pub idempotency_use_case: Arc<IdempotencyUseCase<IdempotencyKeysPostgres>>,
+    pub body_limit: usize,
+}
+
+impl IdempotencyState {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        let idempotency_env = get_idempotency_env().expect("IDEMPOTENCY_TTL_HOURS is invalid");
+
+        Self {
+            idempotency_use_case: Arc::new(IdempotencyUseCase::new(
+                Arc::new(IdempotencyKeysPostgres::new(db_pool)),
+                idempotency_env.ttl_hours,
+            )),
+            body_limit: idempotency_env.body_limit,
+        }
+    }
+}
+
+/// Honours `Idempotency-Key` on mutating requests: the first response is stored and replayed
+/// for retries by the same caller with the same body; the same key with a different body gets
+/// 422. Requests without the header, and safe methods, pass straight through.
+///
+/// The response body is stored as is, so keep this off routes that return a secret only once,
+/// e.g. API keys and OAuth client secrets. Bodies of unknown size or over the body limit are
+/// never buffered: such requests and responses pass through without being stored.
+pub async fn idempotency(
+    State(idempotency): State<IdempotencyState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(next.run(req).await);
+    }
+
+    let Some(idempotency_key) = req.headers().get("idempotency-key") else {
+        return Ok(next.run(req).await);
+    };
+
+    let idempotency_key = idempotency_key
+        .to_str()
+        .ok()
+        .filter(|key| is_valid_idempotency_key(key))
+        .ok_or(StatusCode::BAD_REQUEST)?
+        .to_string();
+
+    let path = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched_path| matched_path.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let scope = format!(
+        "{} {} {}",
+        req.method(),
+        path,
+        get_idempotency_caller(req.extensions())
+    );
+
+    let (parts, body) = req.into_parts();
+    if !fits_body_limit(&body, idempotency.body_limit) {
+        return Ok(next.run(Request::from_parts(parts, body)).await);
+    }
+    let request_body = to_bytes(body, idempotency.body_limit)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE)?;
+
+    let outcome = idempotency
+        .idempotency_use_case
+        .begin(&idempotency_key, &scope, &request_body)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    match outcome {
+        IdempotencyOutcome::Proceed => {}
+        IdempotencyOutcome::Replay {
+            status_code,
+            content_type,
+            response_body,
+        } => {
+            let mut response = Response::new(Body::from(response_body));
+            *response.status_mut() =
+                StatusCode::from_u16(status_code).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            if let Some(content_type) = content_type.and_then(|c| HeaderValue::from_str(&c).ok()) {
+                response
+                    .headers_mut()
+                    .insert(header::CONTENT_TYPE, content_type);
             }
+            response
+                .headers_mut()
+                .insert("idempotent-replayed", HeaderValue::from_static("true"));
+
+            return Ok(response);
+        }
+        IdempotencyOutcome::InProgress => {
+            return Ok(idempotency_error_response(
+                StatusCode::CONFLICT,
+                "A request with this Idempotency-Key is still in progress",
+            ));
+        }
+        IdempotencyOutcome::Mismatch => {
+            return Ok(idempotency_error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Idempotency-Key was already used with a different request",
+            ));
         }
     }
 
-    Err(StatusCode::UNAUTHORIZED)
+    let response = next
+        .run(Request::from_parts(parts, Body::from(request_body)))
+        .await;
+
+    // 5xx ไม่เก็บ เพื่อให้ client retry ด้วย key เดิมได้ ส่วน body ที่ใหญ่เกินก็ไม่เก็บเช่นกัน
+    if response.status().is_server_error()
+        || !fits_body_limit(response.body(), idempotency.body_limit)
+    {
+        if let Err(e) = idempotency
+            .idempotency_use_case
+            .release(&idempotency_key, &scope)
+            .await
+        {
+            tracing::warn!("Failed to release idempotency key: {}", e);
+        }
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let response_body = to_bytes(body, idempotency.body_limit)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    if let Err(e) = idempotency
+        .idempotency_use_case
+        .complete(
+            &idempotency_key,
+            &scope,
+            parts.status.as_u16(),
+            content_type,
+            response_body.to_vec(),
+        )
+        .await
+    {
+        tracing::warn!("Failed to store idempotent response: {}", e);
+    }
+
+    Ok(Response::from_parts(parts, Body::from(response_body)))
+}
+
+/// Whether the body's size is known and within `limit`.
+fn fits_body_limit(body: &Body, limit: usize) -> bool {
+    body.size_hint()
+        .upper()
+        .is_some_and(|size| size <= limit as u64)
+}
+
+/// Who the key belongs to, so that one caller's key never replays another caller's response.
+/// Runs inside the authorization middlewares, which insert the principal or user id.
+fn get_idempotency_caller(extensions: &Extensions) -> String {
+    let hospital_id = extensions
+        .get::<Tenant>()
+        .map(|tenant| tenant.hospital_id.to_string())
+        .unwrap_or_default();
+
+    let caller = match (extensions.get::<Principal>(), extensions.get::<i32>()) {
+        (Some(Principal::User { user_id }), _) => format!("user:{}", user_id),
+        (
+            Some(Principal::Delegate {
+                user_id,
+                guardian_id,
+            }),
+            _,
+        ) => format!("delegate:{}:{}", guardian_id, user_id),
+        (Some(Principal::Service { client_id, .. }), _) => format!("service:{}", client_id),
+        (Some(Principal::ApiKey { api_key_id, .. }), _) => format!("api_key:{}", api_key_id),
+        (None, Some(user_id)) => format!("user:{}", user_id),
+        (None, None) => "anonymous".to_string(),
+    };
+
+    format!("{}@{}", caller, hospital_id)
+}
+
+fn idempotency_error_response(status_code: StatusCode, message: &str) -> Response {
+    (
+        status_code,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(message.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Like the mTLS path, the service account must still exist; see `service_token_scopes`.
+async fn get_service_principal(
+    headers: &HeaderMap,
+    oauth_repository: &OAuthPostgres,
+) -> Option<Principal> {
+    let token = get_bearer_token(headers)?;
+    let oauth_env = get_oauth_secret_env().ok()?;
+
+    let claims =
+        jwt_authentication::verify_oauth_token(oauth_env.secret, &oauth_env.issuer, token).ok()?;
+
+    if claims.gty.as_deref() != Some("client_credentials") {
+        return None;
+    }
+
+    let service_account = oauth_repository
+        .find_service_account_by_client_id(claims.sub)
+        .await
+        .ok()?;
+    let scopes = service_token_scopes(&service_account, &claims.scope);
+
+    Some(Principal::Service {
+        client_id: service_account.client_id,
+        hospital_id: service_account.hospital_id,
+        scopes,
+    })
+}
+
+/// The caller's own account. `None` for a guardian's delegated token, which only reaches
+/// `patients_authorization` and `permission_authorization` routes.
+pub fn get_user_id_from_cookie(headers: &HeaderMap) -> Option<i32> {
+    let claims = get_claims_from_cookie(headers)?;
+    if claims.act.is_some() {
+        return None;
+    }
+
+    claims.sub.parse::<i32>().ok()
+}
+
+/// `(guardian_id, dependent_id)` of a guardian's delegated token.
+fn get_delegation_from_cookie(headers: &HeaderMap) -> Option<(i32, i32)> {
+    let claims = get_claims_from_cookie(headers)?;
+    let guardian_id = claims.act?.sub.parse::<i32>().ok()?;
+    let dependent_id = claims.sub.parse::<i32>().ok()?;
+
+    Some((guardian_id, dependent_id))
+}
+
+/// Claims of the access token cookie issued by the patient, doctor or staff login.
+fn get_claims_from_cookie(headers: &HeaderMap) -> Option<Claims> {
+    let cookie_str = headers.get(header::COOKIE)?.to_str().ok()?;
+    let token = get_cookie_value(cookie_str, "act")?;
+
+    let patients_secret = get_patients_secret_env().ok()?;
+    let doctors_secret = get_doctors_secret_env().ok()?;
+    let staff_secret = get_staff_secret_env().ok()?;
+
+    jwt_authentication::verify_token(patients_secret.secret, token.clone())
+        .or_else(|_| jwt_authentication::verify_token(doctors_secret.secret, token.clone()))
+        .or_else(|_| jwt_authentication::verify_token(staff_secret.secret, token))
+        .ok()
+}
+
+/// The hospital `tenant_resolution` resolved for the request.
+fn get_tenant_hospital_id(req: &Request) -> Result<i32, StatusCode> {
+    req.extensions()
+        .get::<Tenant>()
+        .map(|tenant| tenant.hospital_id)
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+pub fn get_bearer_token(headers: &HeaderMap) -> Option<String> {
+    let authorization = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let token = authorization.strip_prefix("Bearer ")?.trim();
+
+    (!token.is_empty()).then(|| token.to_string())
+}
+
+pub fn get_basic_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let authorization = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let encoded = authorization.strip_prefix("Basic ")?.trim();
+    let decoded = String::from_utf8(STANDARD.decode(encoded).ok()?).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+
+    Some((username.to_string(), password.to_string()))
+}
+
+fn remove_cookie(headers: &mut HeaderMap, key: &str) {
+    let remaining: Vec<String> = headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookie_str| cookie_str.split("; "))
+        .filter(|cookie| cookie.split('=').next().map(str::trim) != Some(key))
+        .map(str::to_string)
+        .collect();
+
+    headers.remove(header::COOKIE);
+
+    let Ok(cookie_header) = HeaderValue::from_str(&remaining.join("; ")) else {
+        return;
+    };
+    if !remaining.is_empty() {
+        headers.insert(header::COOKIE, cookie_header);
+    }
 }
 
 fn get_cookie_value(cookie_header: &str, key: &str) -> Option<String> {
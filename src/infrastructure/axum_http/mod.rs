@@ -4,3 +4,4 @@ pub mod http_serve;
 pub mod middleware;
 pub mod routers;
 pub mod swagger;
+pub mod tls;
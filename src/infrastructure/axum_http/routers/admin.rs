@@ -1 +1,241 @@
+use std::sync::Arc;
 
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware::from_fn_with_state,
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
+
+use crate::{
+    application::usecases::{admin::AdminUseCase, policy::PolicyUseCase},
+    domain::{
+        repositories::{permissions::PermissionsRepository, users::UsersRepository},
+        value_objects::{
+            policy::{ExplainPolicyModel, Permission, PolicyDecision},
+            roles::{AssignRoleModel, Role, RoleGrantModel},
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{PermissionAuthorization, permission_authorization},
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{permissions::PermissionsPostgres, users::UsersPostgres},
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let users_repository = Arc::new(UsersPostgres::new(db_pool.clone()));
+    let admin_use_case = AdminUseCase::new(users_repository.clone());
+    let policy_use_case = PolicyUseCase::new(
+        users_repository,
+        Arc::new(PermissionsPostgres::new(db_pool.clone())),
+    );
+
+    let role_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(list_role_grants))
+        .routes(utoipa_axum::routes!(assign_role))
+        .routes(utoipa_axum::routes!(revoke_role))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::RoleAssign, db_pool.clone()),
+            permission_authorization,
+        ))
+        .with_state(Arc::new(admin_use_case));
+
+    let policy_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(explain))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::PolicyExplain, db_pool),
+            permission_authorization,
+        ))
+        .with_state(Arc::new(policy_use_case));
+
+    OpenApiRouter::new().nest(
+        "/admin",
+        OpenApiRouter::new().merge(role_routes).merge(policy_routes),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+fn not_found(message: &str) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(message.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Lists the user's grants at this hospital that have not ended, including ones that start
+/// later. Requires `role.assign`.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/roles",
+    tags = ["Admin"],
+    responses(
+        (status = 200, description = "List role grants successfully", body = ApiResponse<Vec<RoleGrantModel>>),
+        (status = 404, description = "User not found")
+    )
+)]
+pub async fn list_role_grants<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(user_id): Path<i32>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+{
+    match admin_use_case
+        .find_role_grants(tenant.hospital_id, user_id)
+        .await
+    {
+        Ok(Some(grants)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(grants),
+                message: Some("List role grants successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found("User not found"),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Grants a role at this hospital, optionally only between `valid_from` and `valid_until`, e.g.
+/// for a temporary contract. Granting a role the user already holds replaces its window. Users
+/// from another hospital are registered here and given a hospital number. `Doctor` is granted
+/// by approving a doctor application instead. Requires `role.assign`.
+#[utoipa::path(
+    post,
+    path = "/users/{user_id}/roles",
+    tags = ["Admin"],
+    request_body = AssignRoleModel,
+    responses(
+        (status = 200, description = "Assign role successfully", body = ApiResponse<Vec<RoleGrantModel>>),
+        (status = 404, description = "User not found"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn assign_role<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(admin_id): Extension<i32>,
+    Path(user_id): Path<i32>,
+    Json(assign_role_model): Json<AssignRoleModel>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+{
+    match admin_use_case
+        .assign_role(tenant.hospital_id, admin_id, user_id, assign_role_model)
+        .await
+    {
+        Ok(Some(grants)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(grants),
+                message: Some(format!("Assign role to user id: {} successfully", user_id)),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found("User not found"),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Revokes a role at this hospital. Requires `role.assign`.
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/roles/{role}",
+    tags = ["Admin"],
+    responses(
+        (status = 204, description = "Revoke role successfully"),
+        (status = 404, description = "The user does not hold this role")
+    )
+)]
+pub async fn revoke_role<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path((user_id, role)): Path<(i32, Role)>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+{
+    match admin_use_case
+        .revoke_role(tenant.hospital_id, user_id, role)
+        .await
+    {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => not_found("The user does not hold this role"),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Evaluates the policy for a user at this hospital as `permission_authorization` would and
+/// reports the rule that decided, for debugging access problems. Requires `policy.explain`.
+#[utoipa::path(
+    post,
+    path = "/policy/explain",
+    tags = ["Admin"],
+    request_body = ExplainPolicyModel,
+    responses(
+        (status = 200, description = "Explain policy decision successfully", body = ApiResponse<PolicyDecision>)
+    )
+)]
+pub async fn explain<U, P>(
+    State(policy_use_case): State<Arc<PolicyUseCase<U, P>>>,
+    Extension(tenant): Extension<Tenant>,
+    Json(explain_policy_model): Json<ExplainPolicyModel>,
+) -> Response
+where
+    U: UsersRepository + Send + Sync,
+    P: PermissionsRepository + Send + Sync,
+{
+    match policy_use_case
+        .explain(tenant.hospital_id, explain_policy_model)
+        .await
+    {
+        Ok(decision) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(decision),
+                message: Some("Explain policy decision successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
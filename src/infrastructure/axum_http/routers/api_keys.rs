@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware::from_fn_with_state,
+    response::IntoResponse,
+};
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    application::usecases::api_keys::ApiKeysUseCase,
+    domain::{
+        repositories::api_keys::ApiKeysRepository,
+        value_objects::{
+            api_keys_model::{ApiKeyModel, CreateApiKeyModel, CreateApiKeyResponseModel},
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{api_response::ApiResponse, middleware::admins_authorization},
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{api_keys::ApiKeysPostgres, users::UsersPostgres},
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let users_repository = UsersPostgres::new(db_pool.clone());
+    let api_keys_repository = ApiKeysPostgres::new(db_pool);
+    let api_keys_use_case = ApiKeysUseCase::new(Arc::new(api_keys_repository));
+
+    OpenApiRouter::new().nest(
+        "/api-keys",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(create, list))
+            .routes(utoipa_axum::routes!(revoke))
+            .route_layer(from_fn_with_state(
+                Arc::new(users_repository),
+                admins_authorization,
+            ))
+            .with_state(Arc::new(api_keys_use_case)),
+    )
+}
+
+/// Creates an API key for an integration partner. The key is only returned once, and only
+/// works at this hospital.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["API Keys"],
+    request_body = CreateApiKeyModel,
+    responses(
+        (status = 201, description = "API key created successfully", body = ApiResponse<CreateApiKeyResponseModel>)
+    )
+)]
+pub async fn create<K>(
+    State(api_keys_use_case): State<Arc<ApiKeysUseCase<K>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(admin_id): Extension<i32>,
+    Json(create_api_key_model): Json<CreateApiKeyModel>,
+) -> impl IntoResponse
+where
+    K: ApiKeysRepository + Send + Sync,
+{
+    match api_keys_use_case
+        .create(tenant.hospital_id, admin_id, create_api_key_model)
+        .await
+    {
+        Ok(api_key) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(api_key),
+                message: Some("Create API key successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<CreateApiKeyResponseModel> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Lists this hospital's API keys, including revoked and expired ones.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["API Keys"],
+    responses(
+        (status = 200, description = "Listed API keys successfully", body = ApiResponse<Vec<ApiKeyModel>>)
+    )
+)]
+pub async fn list<K>(
+    State(api_keys_use_case): State<Arc<ApiKeysUseCase<K>>>,
+    Extension(tenant): Extension<Tenant>,
+) -> impl IntoResponse
+where
+    K: ApiKeysRepository + Send + Sync,
+{
+    match api_keys_use_case.list(tenant.hospital_id).await {
+        Ok(api_keys) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(api_keys),
+                message: Some("List API keys successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<ApiKeyModel>> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Revokes one of this hospital's API keys.
+#[utoipa::path(
+    delete,
+    path = "/{api_key_id}",
+    tags = ["API Keys"],
+    responses(
+        (status = 200, description = "API key revoked successfully")
+    )
+)]
+pub async fn revoke<K>(
+    State(api_keys_use_case): State<Arc<ApiKeysUseCase<K>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(api_key_id): Path<i32>,
+) -> impl IntoResponse
+where
+    K: ApiKeysRepository + Send + Sync,
+{
+    match api_keys_use_case
+        .revoke(tenant.hospital_id, api_key_id)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("Revoke API key successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
@@ -1,15 +1,16 @@
 use std::sync::Arc;
 
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     extract::State,
     http::{HeaderMap, HeaderValue, StatusCode, header},
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     routing::{delete, get, post},
 };
 
 use axum_extra::extract::cookie::{Cookie, CookieJar};
 use cookie::time::Duration;
+use tracing::warn;
 use utoipa_axum::router::OpenApiRouter;
 
 use crate::{
@@ -19,20 +20,43 @@ use crate::{
         stage::Stage,
     },
     domain::{
-        repositories::users::UsersRepository,
-        value_objects::authentication_model::{GetMeResponseModel, LoginResponseModel},
+        repositories::{
+            patient_profiles::PatientProfilesRepository, sessions::SessionsRepository,
+            users::UsersRepository,
+        },
+        value_objects::{
+            authentication_model::{GetMeResponseModel, LoginResponseModel},
+            roles::Role,
+            tenant::Tenant,
+        },
     },
     infrastructure::{
         axum_http::api_response::ApiResponse,
-        jwt_authentication::{self, authentication_model::LoginModel, jwt_model::Claims},
-        postgres::{postgres_connection::PgPoolSquad, repositories::users::UsersPostgres},
+        jwt_authentication::{
+            self,
+            authentication_model::LoginModel,
+            jwt_model::{Claims, Passport},
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{
+                patient_profiles::PatientProfilesPostgres, sessions::SessionsPostgres,
+                users::UsersPostgres,
+            },
+        },
     },
 };
 
 #[deprecated]
 pub fn routes(db_pool: PgPoolSquad) -> Router {
-    let users_repository = UsersPostgres::new(db_pool);
-    let authentication_use_case = AuthenticationUseCase::new(Arc::new(users_repository));
+    let users_repository = UsersPostgres::new(db_pool.clone());
+    let sessions_repository = SessionsPostgres::new(db_pool.clone());
+    let patient_profiles_repository = PatientProfilesPostgres::new(db_pool);
+    let authentication_use_case = AuthenticationUseCase::new(
+        Arc::new(users_repository),
+        Arc::new(sessions_repository),
+        Arc::new(patient_profiles_repository),
+    );
 
     Router::new()
         .route("/patients/login", post(patients_login))
@@ -46,8 +70,14 @@ pub fn routes(db_pool: PgPoolSquad) -> Router {
 
 /// Defines routes with OpenAPI specs. Should be used over `routes()` where possible.
 pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
-    let users_repository = UsersPostgres::new(db_pool);
-    let authentication_use_case = AuthenticationUseCase::new(Arc::new(users_repository));
+    let users_repository = UsersPostgres::new(db_pool.clone());
+    let sessions_repository = SessionsPostgres::new(db_pool.clone());
+    let patient_profiles_repository = PatientProfilesPostgres::new(db_pool);
+    let authentication_use_case = AuthenticationUseCase::new(
+        Arc::new(users_repository),
+        Arc::new(sessions_repository),
+        Arc::new(patient_profiles_repository),
+    );
 
     OpenApiRouter::new().nest(
         "/authentication",
@@ -56,6 +86,10 @@ pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
             .routes(utoipa_axum::routes!(patients_refresh_token))
             .routes(utoipa_axum::routes!(doctors_login))
             .routes(utoipa_axum::routes!(doctors_refresh_token))
+            .routes(utoipa_axum::routes!(nurses_login))
+            .routes(utoipa_axum::routes!(pharmacists_login))
+            .routes(utoipa_axum::routes!(receptionists_login))
+            .routes(utoipa_axum::routes!(staff_refresh_token))
             .routes(utoipa_axum::routes!(get_me))
             .routes(utoipa_axum::routes!(logout))
             .with_state(Arc::new(authentication_use_case)),
@@ -72,14 +106,20 @@ pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
         (status = 200, description = "Login successfully", body = ApiResponse<LoginResponseModel>)
     )
 )]
-pub async fn patients_login<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
+pub async fn patients_login<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
     Json(login_model): Json<LoginModel>,
 ) -> impl IntoResponse
 where
     T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
 {
-    match authentication_use_case.patients_login(login_model).await {
+    match authentication_use_case
+        .patients_login(tenant.hospital_id, login_model)
+        .await
+    {
         Ok(passport) => {
             let mut act_cookie = Cookie::build(("act", passport.access_token.clone()))
                 .path("/")
@@ -139,17 +179,20 @@ where
         (status = 200, description = "Refreshed patient tokens successfully", body = ApiResponse<LoginResponseModel>)
     )
 )]
-pub async fn patients_refresh_token<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
+pub async fn patients_refresh_token<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
     jar: CookieJar,
 ) -> impl IntoResponse
 where
     T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
 {
     if let Some(rft) = jar.get("rft") {
         let refresh_token = rft.value().to_string();
         let response = match authentication_use_case
-            .patients_refresh_token(refresh_token)
+            .patients_refresh_token(tenant.hospital_id, refresh_token)
             .await
         {
             Ok(passport) => {
@@ -218,14 +261,20 @@ where
         (status = 200, description = "Login successfully", body = ApiResponse<LoginResponseModel>)
     )
 )]
-pub async fn doctors_login<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
+pub async fn doctors_login<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
     Json(login_model): Json<LoginModel>,
 ) -> impl IntoResponse
 where
     T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
 {
-    match authentication_use_case.doctors_login(login_model).await {
+    match authentication_use_case
+        .doctors_login(tenant.hospital_id, login_model)
+        .await
+    {
         Ok(passport) => {
             let mut act_cookie = Cookie::build(("act", passport.access_token.clone()))
                 .path("/")
@@ -286,17 +335,20 @@ where
         (status = 200, description = "Refreshed doctor tokens successfully", body = ApiResponse<LoginResponseModel>)
     )
 )]
-pub async fn doctors_refresh_token<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
+pub async fn doctors_refresh_token<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
     jar: CookieJar,
 ) -> impl IntoResponse
 where
     T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
 {
     if let Some(rft) = jar.get("rft") {
         let refresh_token = rft.value().to_string();
         let response = match authentication_use_case
-            .doctors_refresh_token(refresh_token)
+            .doctors_refresh_token(tenant.hospital_id, refresh_token)
             .await
         {
             Ok(passport) => {
@@ -362,6 +414,181 @@ where
         .into_response()
 }
 
+/// Logs in a nurse and sets authentication cookies.
+#[utoipa::path(
+    post,
+    path = "/nurses/login",
+    tags = ["Authentication"],
+    request_body = LoginModel,
+    responses(
+        (status = 200, description = "Login successfully", body = ApiResponse<LoginResponseModel>)
+    )
+)]
+pub async fn nurses_login<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
+    Json(login_model): Json<LoginModel>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
+{
+    passport_response(
+        authentication_use_case
+            .staff_login(tenant.hospital_id, login_model, Role::Nurse)
+            .await,
+    )
+}
+
+/// Logs in a pharmacist and sets authentication cookies.
+#[utoipa::path(
+    post,
+    path = "/pharmacists/login",
+    tags = ["Authentication"],
+    request_body = LoginModel,
+    responses(
+        (status = 200, description = "Login successfully", body = ApiResponse<LoginResponseModel>)
+    )
+)]
+pub async fn pharmacists_login<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
+    Json(login_model): Json<LoginModel>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
+{
+    passport_response(
+        authentication_use_case
+            .staff_login(tenant.hospital_id, login_model, Role::Pharmacist)
+            .await,
+    )
+}
+
+/// Logs in a receptionist and sets authentication cookies.
+#[utoipa::path(
+    post,
+    path = "/receptionists/login",
+    tags = ["Authentication"],
+    request_body = LoginModel,
+    responses(
+        (status = 200, description = "Login successfully", body = ApiResponse<LoginResponseModel>)
+    )
+)]
+pub async fn receptionists_login<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
+    Json(login_model): Json<LoginModel>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
+{
+    passport_response(
+        authentication_use_case
+            .staff_login(tenant.hospital_id, login_model, Role::Receptionist)
+            .await,
+    )
+}
+
+/// Refreshes a nurse, pharmacist or receptionist's tokens using the refresh cookie. Fails once
+/// the role has been revoked.
+#[utoipa::path(
+    post,
+    path = "/staff/refresh-token",
+    tags = ["Authentication"],
+    responses(
+        (status = 200, description = "Refreshed staff tokens successfully", body = ApiResponse<LoginResponseModel>)
+    )
+)]
+pub async fn staff_refresh_token<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
+    jar: CookieJar,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
+{
+    let Some(rft) = jar.get("rft") else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<LoginResponseModel> {
+                data: None,
+                message: Some("Refresh token not found".to_string()),
+            }),
+        )
+            .into_response();
+    };
+
+    passport_response(
+        authentication_use_case
+            .staff_refresh_token(tenant.hospital_id, rft.value().to_string())
+            .await,
+    )
+}
+
+/// Sets the `act` and `rft` cookies from a login or refresh, or answers 401.
+fn passport_response(result: anyhow::Result<Passport>) -> Response {
+    let passport = match result {
+        Ok(passport) => passport,
+        Err(e) => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(ApiResponse::<LoginResponseModel> {
+                    data: None,
+                    message: Some(e.to_string()),
+                }),
+            )
+                .into_response();
+        }
+    };
+
+    let mut act_cookie = Cookie::build(("act", passport.access_token))
+        .path("/")
+        .same_site(cookie::SameSite::Lax)
+        .http_only(true)
+        .max_age(Duration::days(14));
+
+    let mut rft_cookie = Cookie::build(("rft", passport.refresh_token))
+        .path("/")
+        .same_site(cookie::SameSite::Lax)
+        .http_only(true)
+        .max_age(Duration::days(14));
+
+    if get_stage() == Stage::Production {
+        act_cookie = act_cookie.secure(true);
+        rft_cookie = rft_cookie.secure(true);
+    }
+
+    let mut headers = HeaderMap::new();
+
+    headers.append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&act_cookie.to_string()).unwrap(),
+    );
+
+    headers.append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&rft_cookie.to_string()).unwrap(),
+    );
+
+    (
+        StatusCode::OK,
+        headers,
+        Json(ApiResponse::<LoginResponseModel> {
+            data: None,
+            message: Some("Login successfully".to_string()),
+        }),
+    )
+        .into_response()
+}
+
 /// Retrieves information about the currently authenticated user.
 #[utoipa::path(
     get,
@@ -371,19 +598,25 @@ where
         (status = 200, description = "Fetched current user successfully", body = ApiResponse<GetMeResponseModel>)
     )
 )]
-pub async fn get_me<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
+pub async fn get_me<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    Extension(tenant): Extension<Tenant>,
     jar: CookieJar,
 ) -> impl IntoResponse
 where
     T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
 {
     if let Some(act) = jar.get("act") {
         let act = act.value();
         let patients_secret = config_loader::get_patients_secret_env();
         let doctors_secret = config_loader::get_doctors_secret_env();
+        let staff_secret = config_loader::get_staff_secret_env();
 
-        if patients_secret.is_err() || doctors_secret.is_err() {
+        let (Ok(patients_secret), Ok(doctors_secret), Ok(staff_secret)) =
+            (patients_secret, doctors_secret, staff_secret)
+        else {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<GetMeResponseModel> {
@@ -391,46 +624,46 @@ where
                     message: Some("Internal server error".to_string()),
                 }),
             );
-        }
-
-        let patient_claims =
-            jwt_authentication::verify_token(patients_secret.unwrap().secret, act.into());
-
-        let doctor_claims =
-            jwt_authentication::verify_token(doctors_secret.unwrap().secret, act.into());
-
-        let claims: Claims;
-
-        if patient_claims.is_err() && doctor_claims.is_err() {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::<GetMeResponseModel> {
-                    data: None,
-                    message: Some("Internal server error".to_string()),
-                }),
-            );
-        }
+        };
 
-        if patient_claims.is_err() {
-            claims = doctor_claims.unwrap();
-        } else {
-            claims = patient_claims.unwrap();
-        }
+        let claims: Claims =
+            match jwt_authentication::verify_token(patients_secret.secret, act.into())
+                .or_else(|_| jwt_authentication::verify_token(doctors_secret.secret, act.into()))
+                .or_else(|_| jwt_authentication::verify_token(staff_secret.secret, act.into()))
+            {
+                Ok(claims) => claims,
+                Err(_) => {
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ApiResponse::<GetMeResponseModel> {
+                            data: None,
+                            message: Some("Internal server error".to_string()),
+                        }),
+                    );
+                }
+            };
 
         match claims.sub.parse::<i32>() {
             Ok(sub) => {
-                let me = authentication_use_case.get_me(sub).await;
+                let me = authentication_use_case
+                    .get_me(tenant.hospital_id, sub)
+                    .await;
                 match me {
-                    Ok(me) => {
+                    Ok((me, roles, patient_profile)) => {
                         return (
                             StatusCode::OK,
                             Json(ApiResponse::<GetMeResponseModel> {
-                                data: Some(GetMeResponseModel { claims, me }),
+                                data: Some(GetMeResponseModel {
+                                    claims,
+                                    me,
+                                    roles,
+                                    patient_profile,
+                                }),
                                 message: Some("Get me successfully".to_string()),
                             }),
                         );
                     }
-                    Err(err) => {
+                    Err(_) => {
                         return (
                             StatusCode::INTERNAL_SERVER_ERROR,
                             Json(ApiResponse::<GetMeResponseModel> {
@@ -441,7 +674,7 @@ where
                     }
                 }
             }
-            Err(err) => {
+            Err(_) => {
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(ApiResponse::<GetMeResponseModel> {
@@ -453,13 +686,13 @@ where
         }
     }
 
-    return (
+    (
         StatusCode::BAD_REQUEST,
         Json(ApiResponse::<GetMeResponseModel> {
             data: None,
             message: Some("Access token not found".to_string()),
         }),
-    );
+    )
 }
 
 /// Logs out the current user and clears authentication cookies.
@@ -471,12 +704,27 @@ where
         (status = 200, description = "Logged out successfully")
     )
 )]
-pub async fn logout<T>(
-    State(authentication_use_case): State<Arc<AuthenticationUseCase<T>>>,
+pub async fn logout<T, S, P>(
+    State(authentication_use_case): State<Arc<AuthenticationUseCase<T, S, P>>>,
+    jar: CookieJar,
 ) -> impl IntoResponse
 where
     T: UsersRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+    P: PatientProfilesRepository + Send + Sync,
 {
+    let revoked = match jar.get("rft") {
+        Some(rft) => {
+            authentication_use_case
+                .logout(rft.value().to_string())
+                .await
+        }
+        None => Ok(()),
+    };
+    if let Err(e) = revoked {
+        warn!("Failed to revoke session on logout: {}", e);
+    }
+
     let mut act_cookie = Cookie::build(("act", ""))
         .path("/")
         .same_site(cookie::SameSite::Lax)
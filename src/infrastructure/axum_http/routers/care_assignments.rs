@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware::from_fn_with_state,
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    application::usecases::care_assignments::CareAssignmentsUseCase,
+    domain::{
+        repositories::{
+            care_assignments::CareAssignmentsRepository, errors::RepositoryError,
+            users::UsersRepository,
+        },
+        value_objects::{
+            care_assignments_model::{
+                CareAssignmentError, CareAssignmentModel, CareAssignmentQueryModel,
+                CreateCareAssignmentModel,
+            },
+            policy::Permission,
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::ApiResponse,
+            middleware::{PermissionAuthorization, permission_authorization},
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{care_assignments::CareAssignmentsPostgres, users::UsersPostgres},
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let care_assignments_repository = CareAssignmentsPostgres::new(db_pool.clone());
+    let users_repository = UsersPostgres::new(db_pool.clone());
+    let care_assignments_use_case = CareAssignmentsUseCase::new(
+        Arc::new(care_assignments_repository),
+        Arc::new(users_repository),
+    );
+
+    OpenApiRouter::new().nest(
+        "/care-assignments",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(create, list))
+            .routes(utoipa_axum::routes!(end))
+            .route_layer(from_fn_with_state(
+                PermissionAuthorization::new(Permission::CareTeamManage, db_pool),
+                permission_authorization,
+            ))
+            .with_state(Arc::new(care_assignments_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(care_assignment_error) = err.downcast_ref::<CareAssignmentError>() {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(care_assignment_error.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(RepositoryError::Conflict { .. }) = err.downcast_ref::<RepositoryError>() {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("The doctor already cares for this patient".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Assigns a doctor to care for a patient at this hospital. Doctors can only read and update
+/// the records of patients assigned to them; anyone else's need breaking the glass. Requires
+/// `care_team.manage`.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["Care Team"],
+    request_body = CreateCareAssignmentModel,
+    responses(
+        (status = 201, description = "Create care assignment successfully", body = ApiResponse<CareAssignmentModel>),
+        (status = 404, description = "Doctor or patient not found at this hospital"),
+        (status = 409, description = "The doctor already cares for this patient")
+    )
+)]
+pub async fn create<C, U>(
+    State(care_assignments_use_case): State<Arc<CareAssignmentsUseCase<C, U>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Json(create_care_assignment_model): Json<CreateCareAssignmentModel>,
+) -> Response
+where
+    C: CareAssignmentsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    match care_assignments_use_case
+        .create(tenant.hospital_id, user_id, create_care_assignment_model)
+        .await
+    {
+        Ok(assignment) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(assignment),
+                message: Some("Create care assignment successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Lists this hospital's current care assignments, optionally of one doctor or patient.
+/// Requires `care_team.manage`.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Care Team"],
+    params(CareAssignmentQueryModel),
+    responses(
+        (status = 200, description = "List care assignments successfully", body = ApiResponse<Vec<CareAssignmentModel>>)
+    )
+)]
+pub async fn list<C, U>(
+    State(care_assignments_use_case): State<Arc<CareAssignmentsUseCase<C, U>>>,
+    Extension(tenant): Extension<Tenant>,
+    Query(query): Query<CareAssignmentQueryModel>,
+) -> Response
+where
+    C: CareAssignmentsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    match care_assignments_use_case
+        .list(tenant.hospital_id, query)
+        .await
+    {
+        Ok(assignments) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(assignments),
+                message: Some("List care assignments successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Ends a care assignment; the doctor loses access to the patient's records at once. Requires
+/// `care_team.manage`.
+#[utoipa::path(
+    delete,
+    path = "/{care_assignment_id}",
+    tags = ["Care Team"],
+    responses(
+        (status = 200, description = "End care assignment successfully", body = ApiResponse<CareAssignmentModel>),
+        (status = 404, description = "Care assignment not found or already ended")
+    )
+)]
+pub async fn end<C, U>(
+    State(care_assignments_use_case): State<Arc<CareAssignmentsUseCase<C, U>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Path(care_assignment_id): Path<i32>,
+) -> Response
+where
+    C: CareAssignmentsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    match care_assignments_use_case
+        .end(tenant.hospital_id, user_id, care_assignment_id)
+        .await
+    {
+        Ok(assignment) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(assignment),
+                message: Some("End care assignment successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,321 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware::{from_fn, from_fn_with_state},
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
+
+use crate::{
+    application::usecases::consents::ConsentsUseCase,
+    domain::{
+        repositories::{consents::ConsentsRepository, errors::RepositoryError},
+        value_objects::{
+            consents_model::{
+                ConsentError, ConsentPurposeModel, ConsentRecordModel, ConsentReportRowModel,
+                ConsentVersionModel, CreateConsentPurposeModel, CurrentConsentModel,
+                PublishConsentVersionModel, RecordConsentModel, UserConsentModel,
+            },
+            policy::Permission,
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{PermissionAuthorization, permission_authorization, users_authorization},
+        },
+        postgres::{postgres_connection::PgPoolSquad, repositories::consents::ConsentsPostgres},
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let consents_repository = ConsentsPostgres::new(db_pool.clone());
+    let consents_use_case = Arc::new(ConsentsUseCase::new(Arc::new(consents_repository)));
+
+    // เปิดให้ดูได้โดยไม่ต้อง login เพื่อแสดงในหน้าลงทะเบียน
+    let public_routes = OpenApiRouter::new().nest(
+        "/consents",
+        OpenApiRouter::new().routes(utoipa_axum::routes!(list_current)),
+    );
+
+    let user_routes = OpenApiRouter::new().nest(
+        "/users/me/consents",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(list_mine, record_mine))
+            .route_layer(from_fn(users_authorization)),
+    );
+
+    let admin_routes = OpenApiRouter::new().nest(
+        "/admin",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(create_purpose))
+            .routes(utoipa_axum::routes!(publish_version))
+            .routes(utoipa_axum::routes!(report))
+            .route_layer(from_fn_with_state(
+                PermissionAuthorization::new(Permission::ConsentManage, db_pool),
+                permission_authorization,
+            )),
+    );
+
+    OpenApiRouter::new()
+        .merge(public_routes)
+        .merge(user_routes)
+        .merge(admin_routes)
+        .with_state(consents_use_case)
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(consent_error) = err.downcast_ref::<ConsentError>() {
+        let status = match consent_error {
+            ConsentError::PurposeNotFound | ConsentError::VersionNotFound => StatusCode::NOT_FOUND,
+            ConsentError::NotGranted => StatusCode::CONFLICT,
+            ConsentError::VersionNotCurrent | ConsentError::RequiredNotAccepted { .. } => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+        };
+
+        return (
+            status,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(consent_error.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(RepositoryError::Conflict { .. }) = err.downcast_ref::<RepositoryError>() {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("A consent purpose with this code already exists".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Lists the consent texts in effect, to show before registration. Purposes marked
+/// `required_at_registration` must be accepted to register.
+#[utoipa::path(
+    get,
+    path = "/current",
+    tags = ["Consents"],
+    responses(
+        (status = 200, description = "List current consents successfully", body = ApiResponse<Vec<CurrentConsentModel>>)
+    )
+)]
+pub async fn list_current<C>(State(consents_use_case): State<Arc<ConsentsUseCase<C>>>) -> Response
+where
+    C: ConsentsRepository + Send + Sync,
+{
+    match consents_use_case.list_current().await {
+        Ok(consents) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(consents),
+                message: Some("List current consents successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Lists the consent purposes in effect with the signed-in user's latest decision on each.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Consents"],
+    responses(
+        (status = 200, description = "List consents successfully", body = ApiResponse<Vec<UserConsentModel>>)
+    )
+)]
+pub async fn list_mine<C>(
+    State(consents_use_case): State<Arc<ConsentsUseCase<C>>>,
+    Extension(user_id): Extension<i32>,
+) -> Response
+where
+    C: ConsentsRepository + Send + Sync,
+{
+    match consents_use_case.list_by_user_id(user_id).await {
+        Ok(consents) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(consents),
+                message: Some("List consents successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Grants the current version of a consent, or withdraws a purpose, for the signed-in user.
+/// Every decision is kept with its channel and time.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["Consents"],
+    request_body = RecordConsentModel,
+    responses(
+        (status = 201, description = "Record consent successfully", body = ApiResponse<ConsentRecordModel>),
+        (status = 404, description = "Consent version not found"),
+        (status = 409, description = "Withdrawing a consent that is not granted"),
+        (status = 422, description = "The version is not the current one")
+    )
+)]
+pub async fn record_mine<C>(
+    State(consents_use_case): State<Arc<ConsentsUseCase<C>>>,
+    Extension(user_id): Extension<i32>,
+    Json(record_consent_model): Json<RecordConsentModel>,
+) -> Response
+where
+    C: ConsentsRepository + Send + Sync,
+{
+    match consents_use_case
+        .record(user_id, record_consent_model)
+        .await
+    {
+        Ok(record) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(record),
+                message: Some("Record consent successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Creates a consent purpose. It takes effect once a version is published. Requires
+/// `consent.manage`.
+#[utoipa::path(
+    post,
+    path = "/consent-purposes",
+    tags = ["Consents"],
+    request_body = CreateConsentPurposeModel,
+    responses(
+        (status = 201, description = "Create consent purpose successfully", body = ApiResponse<ConsentPurposeModel>),
+        (status = 409, description = "A consent purpose with this code already exists"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn create_purpose<C>(
+    State(consents_use_case): State<Arc<ConsentsUseCase<C>>>,
+    Json(create_consent_purpose_model): Json<CreateConsentPurposeModel>,
+) -> Response
+where
+    C: ConsentsRepository + Send + Sync,
+{
+    match consents_use_case
+        .create_purpose(create_consent_purpose_model)
+        .await
+    {
+        Ok(purpose) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(purpose),
+                message: Some("Create consent purpose successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Publishes the next version of a purpose's consent text. Once it takes effect, users who
+/// granted an earlier version show as outdated until they grant it. Requires `consent.manage`.
+#[utoipa::path(
+    post,
+    path = "/consent-purposes/{purpose_id}/versions",
+    tags = ["Consents"],
+    request_body = PublishConsentVersionModel,
+    responses(
+        (status = 201, description = "Publish consent version successfully", body = ApiResponse<ConsentVersionModel>),
+        (status = 404, description = "Consent purpose not found"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn publish_version<C>(
+    State(consents_use_case): State<Arc<ConsentsUseCase<C>>>,
+    Extension(user_id): Extension<i32>,
+    Path(purpose_id): Path<i32>,
+    Json(publish_consent_version_model): Json<PublishConsentVersionModel>,
+) -> Response
+where
+    C: ConsentsRepository + Send + Sync,
+{
+    match consents_use_case
+        .publish_version(purpose_id, user_id, publish_consent_version_model)
+        .await
+    {
+        Ok(version) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(version),
+                message: Some("Publish consent version successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Counts, per purpose in effect, the hospital's users who granted the current version, an
+/// earlier one, withdrew, or never decided. Requires `consent.manage`.
+#[utoipa::path(
+    get,
+    path = "/consents/report",
+    tags = ["Consents"],
+    responses(
+        (status = 200, description = "Consent report", body = ApiResponse<Vec<ConsentReportRowModel>>)
+    )
+)]
+pub async fn report<C>(
+    State(consents_use_case): State<Arc<ConsentsUseCase<C>>>,
+    Extension(tenant): Extension<Tenant>,
+) -> Response
+where
+    C: ConsentsRepository + Send + Sync,
+{
+    match consents_use_case.report(tenant.hospital_id).await {
+        Ok(rows) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(rows),
+                message: Some("Consent report".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::from_fn,
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    application::usecases::data_exports::DataExportsUseCase,
+    config::config_loader::get_data_export_env,
+    domain::{
+        repositories::data_exports::DataExportsRepository,
+        value_objects::data_exports_model::{
+            DataExportBundleModel, DataExportError, DataExportFileModel, DataExportModel,
+            DataExportOutcome, DataExportQueryModel,
+        },
+    },
+    infrastructure::{
+        axum_http::{api_response::ApiResponse, middleware::users_authorization},
+        postgres::{
+            postgres_connection::PgPoolSquad, repositories::data_exports::DataExportsPostgres,
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let config = get_data_export_env().expect("DATA_EXPORT_* is invalid");
+    let data_exports_repository = DataExportsPostgres::new(db_pool);
+    let data_exports_use_case = DataExportsUseCase::new(Arc::new(data_exports_repository), config);
+
+    OpenApiRouter::new().nest(
+        "/users/me",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(export))
+            .routes(utoipa_axum::routes!(find_by_id))
+            .routes(utoipa_axum::routes!(download))
+            .route_layer(from_fn(users_authorization))
+            .with_state(Arc::new(data_exports_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(data_export_error) = err.downcast_ref::<DataExportError>() {
+        let status = match data_export_error {
+            DataExportError::NotFound => StatusCode::NOT_FOUND,
+            DataExportError::NotReady => StatusCode::CONFLICT,
+            DataExportError::Expired => StatusCode::GONE,
+        };
+
+        return (
+            status,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(data_export_error.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+fn file_response(user_id: i32, file: DataExportFileModel) -> Response {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(file.format.content_type()),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!(
+            "attachment; filename=\"{}\"",
+            file.filename(user_id)
+        ))
+        .unwrap(),
+    );
+
+    (StatusCode::OK, headers, file.content).into_response()
+}
+
+/// Exports everything held on the signed-in user: the user row without the password hash,
+/// profiles, roles, consents, sessions and access log entries. Small exports are returned at
+/// once as a file; larger ones are queued and answered with 202 and the export to poll.
+#[utoipa::path(
+    get,
+    path = "/export",
+    tags = ["Users"],
+    params(DataExportQueryModel),
+    responses(
+        (status = 200, description = "The export as a JSON file or a zip archive", body = DataExportBundleModel),
+        (status = 202, description = "Export queued", body = ApiResponse<DataExportModel>)
+    )
+)]
+pub async fn export<D>(
+    State(data_exports_use_case): State<Arc<DataExportsUseCase<D>>>,
+    Extension(user_id): Extension<i32>,
+    Query(data_export_query): Query<DataExportQueryModel>,
+) -> Response
+where
+    D: DataExportsRepository + Send + Sync,
+{
+    match data_exports_use_case
+        .export(user_id, data_export_query.format.unwrap_or_default())
+        .await
+    {
+        Ok(DataExportOutcome::Ready(file)) => file_response(user_id, file),
+        Ok(DataExportOutcome::Queued(export)) => (
+            StatusCode::ACCEPTED,
+            Json(ApiResponse {
+                data: Some(export),
+                message: Some("Data export queued".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Shows a queued export; `download_url` is set once it is ready.
+#[utoipa::path(
+    get,
+    path = "/exports/{export_id}",
+    tags = ["Users"],
+    responses(
+        (status = 200, description = "Fetched data export successfully", body = ApiResponse<DataExportModel>),
+        (status = 404, description = "Data export not found")
+    )
+)]
+pub async fn find_by_id<D>(
+    State(data_exports_use_case): State<Arc<DataExportsUseCase<D>>>,
+    Extension(user_id): Extension<i32>,
+    Path(export_id): Path<String>,
+) -> Response
+where
+    D: DataExportsRepository + Send + Sync,
+{
+    match data_exports_use_case.find_by_id(user_id, export_id).await {
+        Ok(export) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(export),
+                message: Some("Fetched data export successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Downloads a queued export until it expires.
+#[utoipa::path(
+    get,
+    path = "/exports/{export_id}/download",
+    tags = ["Users"],
+    responses(
+        (status = 200, description = "The export as a JSON file or a zip archive", body = DataExportBundleModel),
+        (status = 404, description = "Data export not found"),
+        (status = 409, description = "Data export is not ready"),
+        (status = 410, description = "Data export link has expired")
+    )
+)]
+pub async fn download<D>(
+    State(data_exports_use_case): State<Arc<DataExportsUseCase<D>>>,
+    Extension(user_id): Extension<i32>,
+    Path(export_id): Path<String>,
+) -> Response
+where
+    D: DataExportsRepository + Send + Sync,
+{
+    match data_exports_use_case.download(user_id, export_id).await {
+        Ok(file) => file_response(user_id, file),
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,307 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware::{from_fn, from_fn_with_state},
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
+
+use crate::{
+    application::usecases::doctor_applications::DoctorApplicationsUseCase,
+    domain::{
+        repositories::{
+            doctor_applications::DoctorApplicationsRepository, errors::RepositoryError,
+        },
+        value_objects::{
+            doctor_applications_model::{
+                DoctorApplicationError, DoctorApplicationModel, ListDoctorApplicationsQueryModel,
+                ReviewDoctorApplicationModel, SubmitDoctorApplicationModel,
+            },
+            policy::Permission,
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{PermissionAuthorization, permission_authorization, users_authorization},
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::doctor_applications::DoctorApplicationsPostgres,
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let doctor_applications_repository = DoctorApplicationsPostgres::new(db_pool.clone());
+    let doctor_applications_use_case =
+        DoctorApplicationsUseCase::new(Arc::new(doctor_applications_repository));
+
+    let applicant_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(submit))
+        .routes(utoipa_axum::routes!(list_mine))
+        .route_layer(from_fn(users_authorization));
+
+    let reviewer_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(list))
+        .routes(utoipa_axum::routes!(find_by_id))
+        .routes(utoipa_axum::routes!(review))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::DoctorApplicationReview, db_pool),
+            permission_authorization,
+        ));
+
+    OpenApiRouter::new().nest(
+        "/doctor-applications",
+        OpenApiRouter::new()
+            .merge(applicant_routes)
+            .merge(reviewer_routes)
+            .with_state(Arc::new(doctor_applications_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(application_error) = err.downcast_ref::<DoctorApplicationError>() {
+        let status = match application_error {
+            DoctorApplicationError::NotFound => StatusCode::NOT_FOUND,
+            DoctorApplicationError::InvalidTransition { .. } => StatusCode::CONFLICT,
+        };
+
+        return (
+            status,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(application_error.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(RepositoryError::Conflict { constraint }) = err.downcast_ref::<RepositoryError>() {
+        let message = if constraint == "doctor_profiles_license_number_key" {
+            "This license number is already registered to another doctor"
+        } else {
+            "You already have a pending or approved application"
+        };
+
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(message.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Applies to become a doctor. The application starts as `pending` until an admin reviews it.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["Doctor Applications"],
+    request_body = SubmitDoctorApplicationModel,
+    responses(
+        (status = 201, description = "Submit doctor application successfully", body = ApiResponse<DoctorApplicationModel>),
+        (status = 409, description = "Another application is pending or approved"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn submit<A>(
+    State(doctor_applications_use_case): State<Arc<DoctorApplicationsUseCase<A>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Json(submit_doctor_application_model): Json<SubmitDoctorApplicationModel>,
+) -> Response
+where
+    A: DoctorApplicationsRepository + Send + Sync,
+{
+    match doctor_applications_use_case
+        .submit(tenant.hospital_id, user_id, submit_doctor_application_model)
+        .await
+    {
+        Ok(application) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(application),
+                message: Some("Submit doctor application successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Lists the signed-in user's applications to this hospital, newest first.
+#[utoipa::path(
+    get,
+    path = "/me",
+    tags = ["Doctor Applications"],
+    responses(
+        (status = 200, description = "List doctor applications successfully", body = ApiResponse<Vec<DoctorApplicationModel>>)
+    )
+)]
+pub async fn list_mine<A>(
+    State(doctor_applications_use_case): State<Arc<DoctorApplicationsUseCase<A>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+) -> Response
+where
+    A: DoctorApplicationsRepository + Send + Sync,
+{
+    match doctor_applications_use_case
+        .list_by_user_id(tenant.hospital_id, user_id)
+        .await
+    {
+        Ok(applications) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(applications),
+                message: Some("List doctor applications successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Review queue: applications in one status, oldest first. Requires
+/// `doctor_application.review`.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Doctor Applications"],
+    params(ListDoctorApplicationsQueryModel),
+    responses(
+        (status = 200, description = "List doctor applications successfully", body = ApiResponse<Vec<DoctorApplicationModel>>)
+    )
+)]
+pub async fn list<A>(
+    State(doctor_applications_use_case): State<Arc<DoctorApplicationsUseCase<A>>>,
+    Extension(tenant): Extension<Tenant>,
+    Query(list_doctor_applications_query): Query<ListDoctorApplicationsQueryModel>,
+) -> Response
+where
+    A: DoctorApplicationsRepository + Send + Sync,
+{
+    match doctor_applications_use_case
+        .list_by_status(tenant.hospital_id, list_doctor_applications_query.status)
+        .await
+    {
+        Ok(applications) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(applications),
+                message: Some("List doctor applications successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Gets one application with its full status history.
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tags = ["Doctor Applications"],
+    responses(
+        (status = 200, description = "Get doctor application successfully", body = ApiResponse<DoctorApplicationModel>),
+        (status = 404, description = "Doctor application not found")
+    )
+)]
+pub async fn find_by_id<A>(
+    State(doctor_applications_use_case): State<Arc<DoctorApplicationsUseCase<A>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(id): Path<i32>,
+) -> Response
+where
+    A: DoctorApplicationsRepository + Send + Sync,
+{
+    match doctor_applications_use_case
+        .find_by_id(tenant.hospital_id, id)
+        .await
+    {
+        Ok(application) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(application),
+                message: Some(format!("Get doctor application id: {} successfully", id)),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Approves, rejects or revokes an application. Approval grants the `Doctor` role at this
+/// hospital, until `valid_until` when given, and lists the doctor as verified; revocation takes
+/// both away.
+#[utoipa::path(
+    post,
+    path = "/{id}/review",
+    tags = ["Doctor Applications"],
+    request_body = ReviewDoctorApplicationModel,
+    responses(
+        (status = 200, description = "Review doctor application successfully", body = ApiResponse<DoctorApplicationModel>),
+        (status = 404, description = "Doctor application not found"),
+        (status = 409, description = "The application cannot move to this status"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn review<A>(
+    State(doctor_applications_use_case): State<Arc<DoctorApplicationsUseCase<A>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(admin_id): Extension<i32>,
+    Path(id): Path<i32>,
+    Json(review_doctor_application_model): Json<ReviewDoctorApplicationModel>,
+) -> Response
+where
+    A: DoctorApplicationsRepository + Send + Sync,
+{
+    match doctor_applications_use_case
+        .review(
+            tenant.hospital_id,
+            admin_id,
+            id,
+            review_doctor_application_model,
+        )
+        .await
+    {
+        Ok(application) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(application),
+                message: Some(format!("Review doctor application id: {} successfully", id)),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
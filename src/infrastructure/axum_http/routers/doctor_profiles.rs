@@ -0,0 +1,388 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware::from_fn_with_state,
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
+
+use crate::{
+    application::usecases::doctor_profiles::DoctorProfilesUseCase,
+    domain::{
+        repositories::{doctor_profiles::DoctorProfilesRepository, errors::RepositoryError},
+        value_objects::{
+            doctor_profiles_model::{
+                DoctorDirectoryEntryModel, DoctorProfileModel, ListDoctorsQueryModel,
+                SetDoctorVerificationModel, UpsertDoctorProfileModel,
+            },
+            pagination::PageModel,
+            policy::Permission,
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{
+                PermissionAuthorization, doctors_authorization, permission_authorization,
+            },
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{doctor_profiles::DoctorProfilesPostgres, users::UsersPostgres},
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let doctor_profiles_repository = DoctorProfilesPostgres::new(db_pool.clone());
+    let doctor_profiles_use_case = DoctorProfilesUseCase::new(Arc::new(doctor_profiles_repository));
+
+    // directory เปิดให้ดูได้โดยไม่ต้อง login
+    let public_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(list))
+        .routes(utoipa_axum::routes!(find_by_user_id));
+
+    let me_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(
+            get_my_profile,
+            create_my_profile,
+            update_my_profile
+        ))
+        .route_layer(from_fn_with_state(
+            Arc::new(UsersPostgres::new(db_pool.clone())),
+            doctors_authorization,
+        ));
+
+    let read_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(get_profile))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::DoctorRead, db_pool.clone()),
+            permission_authorization,
+        ));
+
+    let verify_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(set_verification))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::DoctorVerify, db_pool),
+            permission_authorization,
+        ));
+
+    OpenApiRouter::new().nest(
+        "/doctors",
+        OpenApiRouter::new()
+            .merge(public_routes)
+            .merge(me_routes)
+            .merge(read_routes)
+            .merge(verify_routes)
+            .with_state(Arc::new(doctor_profiles_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(RepositoryError::Conflict { constraint }) = err.downcast_ref::<RepositoryError>() {
+        let message = if constraint == "doctor_profiles_license_number_key" {
+            "This license number is already registered to another doctor"
+        } else {
+            "Doctor profile already exists. Use PUT to update it"
+        };
+
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(message.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+fn not_found() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some("Doctor profile not found".to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Public directory of the hospital's verified doctors, filterable by specialty, department and
+/// language.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Doctors"],
+    params(ListDoctorsQueryModel),
+    responses(
+        (status = 200, description = "Listed doctors successfully", body = ApiResponse<PageModel<DoctorDirectoryEntryModel>>),
+        (status = 422, description = "Invalid filter or cursor", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn list<D>(
+    State(doctor_profiles_use_case): State<Arc<DoctorProfilesUseCase<D>>>,
+    Extension(tenant): Extension<Tenant>,
+    Query(list_doctors_query): Query<ListDoctorsQueryModel>,
+) -> Response
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    match doctor_profiles_use_case
+        .list_directory(tenant.hospital_id, list_doctors_query)
+        .await
+    {
+        Ok(page) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(page),
+                message: Some("List doctors successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Public directory entry of one verified doctor.
+#[utoipa::path(
+    get,
+    path = "/{user_id}",
+    tags = ["Doctors"],
+    responses(
+        (status = 200, description = "Get doctor successfully", body = ApiResponse<DoctorDirectoryEntryModel>),
+        (status = 404, description = "No verified doctor with this id")
+    )
+)]
+pub async fn find_by_user_id<D>(
+    State(doctor_profiles_use_case): State<Arc<DoctorProfilesUseCase<D>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(user_id): Path<i32>,
+) -> Response
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    match doctor_profiles_use_case
+        .find_in_directory(tenant.hospital_id, user_id)
+        .await
+    {
+        Ok(Some(doctor)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(doctor),
+                message: Some(format!("Get doctor id: {} successfully", user_id)),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Gets the signed-in doctor's profile, including verification status.
+#[utoipa::path(
+    get,
+    path = "/me/profile",
+    tags = ["Doctors"],
+    responses(
+        (status = 200, description = "Get doctor profile successfully", body = ApiResponse<DoctorProfileModel>),
+        (status = 404, description = "No profile yet")
+    )
+)]
+pub async fn get_my_profile<D>(
+    State(doctor_profiles_use_case): State<Arc<DoctorProfilesUseCase<D>>>,
+    Extension(user_id): Extension<i32>,
+) -> Response
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    match doctor_profiles_use_case.find_by_user_id(user_id).await {
+        Ok(Some(profile)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some("Get doctor profile successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Creates the signed-in doctor's profile. It is listed once an admin verifies it.
+#[utoipa::path(
+    post,
+    path = "/me/profile",
+    tags = ["Doctors"],
+    request_body = UpsertDoctorProfileModel,
+    responses(
+        (status = 201, description = "Create doctor profile successfully", body = ApiResponse<DoctorProfileModel>),
+        (status = 409, description = "Profile already exists or license number is taken"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn create_my_profile<D>(
+    State(doctor_profiles_use_case): State<Arc<DoctorProfilesUseCase<D>>>,
+    Extension(user_id): Extension<i32>,
+    Json(upsert_doctor_profile_model): Json<UpsertDoctorProfileModel>,
+) -> Response
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    match doctor_profiles_use_case
+        .create(user_id, upsert_doctor_profile_model)
+        .await
+    {
+        Ok(profile) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some("Create doctor profile successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Replaces the signed-in doctor's profile. Changing the license number removes the
+/// verification until an admin checks it again.
+#[utoipa::path(
+    put,
+    path = "/me/profile",
+    tags = ["Doctors"],
+    request_body = UpsertDoctorProfileModel,
+    responses(
+        (status = 200, description = "Update doctor profile successfully", body = ApiResponse<DoctorProfileModel>),
+        (status = 404, description = "No profile yet"),
+        (status = 409, description = "License number is taken"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn update_my_profile<D>(
+    State(doctor_profiles_use_case): State<Arc<DoctorProfilesUseCase<D>>>,
+    Extension(user_id): Extension<i32>,
+    Json(upsert_doctor_profile_model): Json<UpsertDoctorProfileModel>,
+) -> Response
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    match doctor_profiles_use_case
+        .update(user_id, upsert_doctor_profile_model)
+        .await
+    {
+        Ok(Some(profile)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some("Update doctor profile successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Gets a doctor's full profile, including the license number. Requires `doctor.read` unless
+/// it is the caller's own.
+#[utoipa::path(
+    get,
+    path = "/{user_id}/profile",
+    tags = ["Doctors"],
+    responses(
+        (status = 200, description = "Get doctor profile successfully", body = ApiResponse<DoctorProfileModel>),
+        (status = 404, description = "The user has no doctor profile")
+    )
+)]
+pub async fn get_profile<D>(
+    State(doctor_profiles_use_case): State<Arc<DoctorProfilesUseCase<D>>>,
+    Path(user_id): Path<i32>,
+) -> Response
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    match doctor_profiles_use_case.find_by_user_id(user_id).await {
+        Ok(Some(profile)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some(format!(
+                    "Get profile of doctor id: {} successfully",
+                    user_id
+                )),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Marks a doctor's license as checked (or withdraws it), which lists or unlists them in the
+/// public directory. Requires `doctor.verify`.
+#[utoipa::path(
+    put,
+    path = "/{user_id}/verification",
+    tags = ["Doctors"],
+    request_body = SetDoctorVerificationModel,
+    responses(
+        (status = 200, description = "Set verification successfully", body = ApiResponse<DoctorProfileModel>),
+        (status = 404, description = "The user has no doctor profile")
+    )
+)]
+pub async fn set_verification<D>(
+    State(doctor_profiles_use_case): State<Arc<DoctorProfilesUseCase<D>>>,
+    Extension(admin_id): Extension<i32>,
+    Path(user_id): Path<i32>,
+    Json(set_doctor_verification_model): Json<SetDoctorVerificationModel>,
+) -> Response
+where
+    D: DoctorProfilesRepository + Send + Sync,
+{
+    match doctor_profiles_use_case
+        .set_verification(admin_id, user_id, set_doctor_verification_model.verified)
+        .await
+    {
+        Ok(Some(profile)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some(format!(
+                    "Set verification of doctor id: {} successfully",
+                    user_id
+                )),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
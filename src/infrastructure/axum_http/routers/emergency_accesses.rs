@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware::from_fn_with_state,
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
+
+use crate::{
+    application::usecases::emergency_accesses::EmergencyAccessUseCase,
+    domain::{
+        repositories::emergency_accesses::EmergencyAccessRepository,
+        value_objects::{
+            emergency_accesses_model::{
+                CreateEmergencyAccessModel, EmergencyAccessError, EmergencyAccessModel,
+                EmergencyAccessReportQueryModel, ReviewEmergencyAccessModel,
+            },
+            policy::Permission,
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{PermissionAuthorization, permission_authorization},
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::emergency_accesses::EmergencyAccessPostgres,
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let emergency_access_repository = EmergencyAccessPostgres::new(db_pool.clone());
+    let emergency_access_use_case =
+        EmergencyAccessUseCase::new(Arc::new(emergency_access_repository));
+
+    let invoke_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(grant))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::EmergencyAccessInvoke, db_pool.clone()),
+            permission_authorization,
+        ));
+
+    let review_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(report))
+        .routes(utoipa_axum::routes!(review))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::EmergencyAccessReview, db_pool),
+            permission_authorization,
+        ));
+
+    OpenApiRouter::new().nest(
+        "/emergency-access",
+        OpenApiRouter::new()
+            .merge(invoke_routes)
+            .merge(review_routes)
+            .with_state(Arc::new(emergency_access_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(emergency_access_error) = err.downcast_ref::<EmergencyAccessError>() {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(emergency_access_error.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Breaks the glass: grants the caller read and update access to the records of a patient of
+/// this hospital who is not in their care, for a limited time. The reason is mandatory; the use
+/// is audited at high priority and the hospital's admins are notified. Requires
+/// `emergency_access.invoke`.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["Emergency Access"],
+    request_body = CreateEmergencyAccessModel,
+    responses(
+        (status = 201, description = "Emergency access granted", body = ApiResponse<EmergencyAccessModel>),
+        (status = 404, description = "Patient not registered at this hospital"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn grant<E>(
+    State(emergency_access_use_case): State<Arc<EmergencyAccessUseCase<E>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Json(create_emergency_access_model): Json<CreateEmergencyAccessModel>,
+) -> Response
+where
+    E: EmergencyAccessRepository + Send + Sync,
+{
+    match emergency_access_use_case
+        .grant(tenant.hospital_id, user_id, create_emergency_access_model)
+        .await
+    {
+        Ok(access) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(access),
+                message: Some("Emergency access granted".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Lists every break-the-glass use at this hospital, newest first, for review. Requires
+/// `emergency_access.review`.
+#[utoipa::path(
+    get,
+    path = "/report",
+    tags = ["Emergency Access"],
+    params(EmergencyAccessReportQueryModel),
+    responses(
+        (status = 200, description = "List emergency accesses successfully", body = ApiResponse<Vec<EmergencyAccessModel>>)
+    )
+)]
+pub async fn report<E>(
+    State(emergency_access_use_case): State<Arc<EmergencyAccessUseCase<E>>>,
+    Extension(tenant): Extension<Tenant>,
+    Query(report_query): Query<EmergencyAccessReportQueryModel>,
+) -> Response
+where
+    E: EmergencyAccessRepository + Send + Sync,
+{
+    match emergency_access_use_case
+        .report(tenant.hospital_id, report_query)
+        .await
+    {
+        Ok(accesses) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(accesses),
+                message: Some("List emergency accesses successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Records the outcome of reviewing a break-the-glass use. Each use is reviewed once.
+/// Requires `emergency_access.review`.
+#[utoipa::path(
+    post,
+    path = "/{emergency_access_id}/review",
+    tags = ["Emergency Access"],
+    request_body = ReviewEmergencyAccessModel,
+    responses(
+        (status = 200, description = "Review emergency access successfully", body = ApiResponse<EmergencyAccessModel>),
+        (status = 404, description = "Emergency access not found or already reviewed"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn review<E>(
+    State(emergency_access_use_case): State<Arc<EmergencyAccessUseCase<E>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Path(emergency_access_id): Path<i32>,
+    Json(review_emergency_access_model): Json<ReviewEmergencyAccessModel>,
+) -> Response
+where
+    E: EmergencyAccessRepository + Send + Sync,
+{
+    match emergency_access_use_case
+        .review(
+            tenant.hospital_id,
+            user_id,
+            emergency_access_id,
+            review_emergency_access_model,
+        )
+        .await
+    {
+        Ok(access) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(access),
+                message: Some("Review emergency access successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware::from_fn_with_state,
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
+
+use crate::{
+    application::usecases::erasures::ErasuresUseCase,
+    config::config_loader::get_erasure_env,
+    domain::{
+        repositories::{erasures::ErasuresRepository, errors::RepositoryError},
+        value_objects::{
+            erasures_model::{EraseUserModel, ErasureError, ErasureModel},
+            policy::Permission,
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{PermissionAuthorization, permission_authorization},
+        },
+        postgres::{postgres_connection::PgPoolSquad, repositories::erasures::ErasuresPostgres},
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let config = get_erasure_env().expect("MEDICAL_RECORD_RETENTION_YEARS is invalid");
+    let erasures_repository = ErasuresPostgres::new(db_pool.clone());
+    let erasures_use_case = ErasuresUseCase::new(Arc::new(erasures_repository), config);
+
+    OpenApiRouter::new().nest(
+        "/admin",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(erase))
+            .route_layer(from_fn_with_state(
+                PermissionAuthorization::new(Permission::UserErase, db_pool),
+                permission_authorization,
+            ))
+            .with_state(Arc::new(erasures_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(erasure_error) = err.downcast_ref::<ErasureError>() {
+        let status = match erasure_error {
+            ErasureError::UserNotFound => StatusCode::NOT_FOUND,
+            ErasureError::RetentionPeriod { .. } => StatusCode::CONFLICT,
+        };
+
+        return (
+            status,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(erasure_error.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(RepositoryError::Conflict { .. }) = err.downcast_ref::<RepositoryError>() {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("The user has already been erased".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Erases a user's personal data on a right-to-erasure request: their name, phone number,
+/// citizen ID and contact details are scrubbed and their sessions, OAuth grants and
+/// guardianships revoked. The account is kept, pseudonymized, for the records that refer to it.
+/// Refused while any hospital the user is registered at is within
+/// `MEDICAL_RECORD_RETENTION_YEARS` of their last clinical activity. Requires `user.erase`.
+#[utoipa::path(
+    post,
+    path = "/users/{user_id}/erase",
+    tags = ["Admin"],
+    request_body = EraseUserModel,
+    responses(
+        (status = 200, description = "Erase user successfully", body = ApiResponse<ErasureModel>),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "The user has already been erased or their medical record must still be kept"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn erase<E>(
+    State(erasures_use_case): State<Arc<ErasuresUseCase<E>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(admin_id): Extension<i32>,
+    Path(user_id): Path<i32>,
+    Json(erase_user_model): Json<EraseUserModel>,
+) -> Response
+where
+    E: ErasuresRepository + Send + Sync,
+{
+    match erasures_use_case
+        .erase(tenant.hospital_id, admin_id, user_id, erase_user_model)
+        .await
+    {
+        Ok(erasure) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(erasure),
+                message: Some(format!("Erase user id: {} successfully", user_id)),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
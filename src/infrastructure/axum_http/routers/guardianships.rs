@@ -0,0 +1,323 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::{from_fn, from_fn_with_state},
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::cookie::Cookie;
+use cookie::time::Duration;
+use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
+
+use crate::{
+    application::usecases::guardianships::GuardianshipsUseCase,
+    config::{config_loader::get_stage, stage::Stage},
+    domain::{
+        repositories::{
+            errors::RepositoryError, guardianships::GuardianshipsRepository, users::UsersRepository,
+        },
+        value_objects::{
+            guardianships_model::{
+                ActingAsModel, CreateGuardianshipModel, DELEGATED_TOKEN_TTL_MINUTES,
+                GuardianshipError, GuardianshipModel,
+            },
+            policy::Permission,
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{PermissionAuthorization, permission_authorization, users_authorization},
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{guardianships::GuardianshipsPostgres, users::UsersPostgres},
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let guardianships_repository = GuardianshipsPostgres::new(db_pool.clone());
+    let users_repository = UsersPostgres::new(db_pool.clone());
+    let guardianships_use_case = GuardianshipsUseCase::new(
+        Arc::new(guardianships_repository),
+        Arc::new(users_repository),
+    );
+
+    // token แบบ delegated ใช้ route กลุ่มนี้ไม่ได้ จึงสลับต่อเป็นคนที่สามไม่ได้
+    let guardian_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(list_dependents))
+        .routes(utoipa_axum::routes!(act_as))
+        .route_layer(from_fn(users_authorization));
+
+    let staff_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(create))
+        .routes(utoipa_axum::routes!(revoke))
+        .routes(utoipa_axum::routes!(list_by_user_id))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::GuardianshipManage, db_pool),
+            permission_authorization,
+        ));
+
+    OpenApiRouter::new().nest(
+        "/guardianships",
+        OpenApiRouter::new()
+            .merge(guardian_routes)
+            .merge(staff_routes)
+            .with_state(Arc::new(guardianships_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(guardianship_error) = err.downcast_ref::<GuardianshipError>() {
+        let status = match guardianship_error {
+            GuardianshipError::NotFound | GuardianshipError::UserNotFound => StatusCode::NOT_FOUND,
+            GuardianshipError::NotGuardian => StatusCode::FORBIDDEN,
+        };
+
+        return (
+            status,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(guardianship_error.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(RepositoryError::Conflict { .. }) = err.downcast_ref::<RepositoryError>() {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("This guardian already has an active guardianship".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Lists whom the signed-in user can act for.
+#[utoipa::path(
+    get,
+    path = "/dependents",
+    tags = ["Guardianships"],
+    responses(
+        (status = 200, description = "List dependents successfully", body = ApiResponse<Vec<GuardianshipModel>>)
+    )
+)]
+pub async fn list_dependents<G, U>(
+    State(guardianships_use_case): State<Arc<GuardianshipsUseCase<G, U>>>,
+    Extension(user_id): Extension<i32>,
+) -> Response
+where
+    G: GuardianshipsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    match guardianships_use_case.list_dependents(user_id).await {
+        Ok(guardianships) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(guardianships),
+                message: Some("List dependents successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Switches to acting for a dependent: replaces the `act` cookie with a short-lived token for
+/// the dependent that names the guardian as actor. Every request made with it is audited. To
+/// switch back, call the refresh-token endpoint of your own login; the `rft` cookie is
+/// unchanged.
+#[utoipa::path(
+    post,
+    path = "/dependents/{dependent_id}/act",
+    tags = ["Guardianships"],
+    responses(
+        (status = 200, description = "Acting for the dependent", body = ApiResponse<ActingAsModel>),
+        (status = 403, description = "Not an active guardian of this patient")
+    )
+)]
+pub async fn act_as<G, U>(
+    State(guardianships_use_case): State<Arc<GuardianshipsUseCase<G, U>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Path(dependent_id): Path<i32>,
+) -> Response
+where
+    G: GuardianshipsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    let (access_token, acting_as) = match guardianships_use_case
+        .act_as(tenant.hospital_id, user_id, dependent_id)
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => return error_response(e),
+    };
+
+    let mut act_cookie = Cookie::build(("act", access_token))
+        .path("/")
+        .same_site(cookie::SameSite::Lax)
+        .http_only(true)
+        .max_age(Duration::minutes(DELEGATED_TOKEN_TTL_MINUTES));
+
+    if get_stage() == Stage::Production {
+        act_cookie = act_cookie.secure(true);
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.append(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&act_cookie.to_string()).unwrap(),
+    );
+
+    (
+        StatusCode::OK,
+        headers,
+        Json(ApiResponse {
+            data: Some(acting_as),
+            message: Some(format!("Acting for user id: {}", dependent_id)),
+        }),
+    )
+        .into_response()
+}
+
+/// Records a guardian-dependent relationship after checking the consent evidence. Both must
+/// be registered at the hospital. Requires `guardianship.manage`.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["Guardianships"],
+    request_body = CreateGuardianshipModel,
+    responses(
+        (status = 201, description = "Create guardianship successfully", body = ApiResponse<GuardianshipModel>),
+        (status = 404, description = "Guardian or dependent not registered at this hospital"),
+        (status = 409, description = "The pair already has an active guardianship"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn create<G, U>(
+    State(guardianships_use_case): State<Arc<GuardianshipsUseCase<G, U>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Json(create_guardianship_model): Json<CreateGuardianshipModel>,
+) -> Response
+where
+    G: GuardianshipsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    match guardianships_use_case
+        .create(tenant.hospital_id, user_id, create_guardianship_model)
+        .await
+    {
+        Ok(guardianship) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(guardianship),
+                message: Some("Create guardianship successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Revokes a guardianship; the guardian's delegated tokens stop working at once. Requires
+/// `guardianship.manage`.
+#[utoipa::path(
+    delete,
+    path = "/{guardianship_id}",
+    tags = ["Guardianships"],
+    responses(
+        (status = 200, description = "Revoke guardianship successfully", body = ApiResponse<GuardianshipModel>),
+        (status = 404, description = "Guardianship not found or already revoked")
+    )
+)]
+pub async fn revoke<G, U>(
+    State(guardianships_use_case): State<Arc<GuardianshipsUseCase<G, U>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Path(guardianship_id): Path<i32>,
+) -> Response
+where
+    G: GuardianshipsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    match guardianships_use_case
+        .revoke(tenant.hospital_id, user_id, guardianship_id)
+        .await
+    {
+        Ok(guardianship) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(guardianship),
+                message: Some("Revoke guardianship successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Lists the user's guardianships as guardian or dependent, revoked ones included, with both
+/// sides registered at this hospital. Requires `guardianship.manage`.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}",
+    tags = ["Guardianships"],
+    responses(
+        (status = 200, description = "List guardianships successfully", body = ApiResponse<Vec<GuardianshipModel>>)
+    )
+)]
+pub async fn list_by_user_id<G, U>(
+    State(guardianships_use_case): State<Arc<GuardianshipsUseCase<G, U>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(user_id): Path<i32>,
+) -> Response
+where
+    G: GuardianshipsRepository + Send + Sync,
+    U: UsersRepository + Send + Sync,
+{
+    match guardianships_use_case
+        .list_by_user_id(tenant.hospital_id, user_id)
+        .await
+    {
+        Ok(guardianships) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(guardianships),
+                message: Some("List guardianships successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
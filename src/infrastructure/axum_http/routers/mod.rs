@@ -1,3 +1,15 @@
 pub mod admin;
+pub mod api_keys;
+pub mod consents;
+pub mod data_exports;
 pub mod authentication;
-pub mod users;
\ No newline at end of file
+pub mod doctor_applications;
+pub mod doctor_profiles;
+pub mod emergency_accesses;
+pub mod guardianships;
+pub mod notifications;
+pub mod oauth;
+pub mod patient_profiles;
+pub mod users;
+pub mod erasures;
+pub mod care_assignments;
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware::from_fn,
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    application::usecases::notifications::NotificationsUseCase,
+    domain::{
+        repositories::notifications::NotificationsRepository,
+        value_objects::notifications_model::{ListNotificationsQueryModel, NotificationModel},
+    },
+    infrastructure::{
+        axum_http::{api_response::ApiResponse, middleware::users_authorization},
+        postgres::{
+            postgres_connection::PgPoolSquad, repositories::notifications::NotificationsPostgres,
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let notifications_repository = NotificationsPostgres::new(db_pool);
+    let notifications_use_case = NotificationsUseCase::new(Arc::new(notifications_repository));
+
+    OpenApiRouter::new().nest(
+        "/notifications",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(list))
+            .routes(utoipa_axum::routes!(mark_read))
+            .route_layer(from_fn(users_authorization))
+            .with_state(Arc::new(notifications_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Lists the signed-in user's notifications, newest first.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Notifications"],
+    params(ListNotificationsQueryModel),
+    responses(
+        (status = 200, description = "List notifications successfully", body = ApiResponse<Vec<NotificationModel>>)
+    )
+)]
+pub async fn list<N>(
+    State(notifications_use_case): State<Arc<NotificationsUseCase<N>>>,
+    Extension(user_id): Extension<i32>,
+    Query(list_notifications_query): Query<ListNotificationsQueryModel>,
+) -> Response
+where
+    N: NotificationsRepository + Send + Sync,
+{
+    match notifications_use_case
+        .list(user_id, list_notifications_query.unread.unwrap_or(false))
+        .await
+    {
+        Ok(notifications) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(notifications),
+                message: Some("List notifications successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Marks a notification as read. Marking it again keeps the first read time.
+#[utoipa::path(
+    post,
+    path = "/{id}/read",
+    tags = ["Notifications"],
+    responses(
+        (status = 204, description = "Mark notification as read successfully"),
+        (status = 404, description = "Notification not found")
+    )
+)]
+pub async fn mark_read<N>(
+    State(notifications_use_case): State<Arc<NotificationsUseCase<N>>>,
+    Extension(user_id): Extension<i32>,
+    Path(id): Path<i32>,
+) -> Response
+where
+    N: NotificationsRepository + Send + Sync,
+{
+    match notifications_use_case.mark_read(id, user_id).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("Notification not found".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
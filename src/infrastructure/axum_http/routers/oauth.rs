@@ -0,0 +1,717 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Form, Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::{from_fn, from_fn_with_state},
+    response::{IntoResponse, Redirect, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    application::usecases::oauth::OAuthUseCase,
+    domain::{
+        repositories::{
+            oauth::OAuthRepository, sessions::SessionsRepository, users::UsersRepository,
+        },
+        value_objects::{
+            oauth_model::{
+                AuthorizeRequestModel, ConsentDecisionModel, ConsentDecisionResponseModel,
+                ConsentDetailsResponseModel, IntrospectionResponseModel, JsonWebKeySetModel,
+                OAuthClientModel, OAuthError, OAuthErrorResponseModel, OpenIdConfigurationModel,
+                RegisterOAuthClientModel, RegisterOAuthClientResponseModel,
+                RegisterServiceAccountModel, RegisterServiceAccountResponseModel,
+                ServiceAccountModel, TokenIntrospectionRequestModel, TokenRequestModel,
+                TokenResponseModel, TokenRevocationRequestModel, UserInfoResponseModel,
+            },
+            tenant::Tenant,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::ApiResponse,
+            middleware::{
+                admins_authorization, get_basic_credentials, get_bearer_token,
+                get_user_id_from_cookie, users_authorization,
+            },
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{
+                oauth::OAuthPostgres, sessions::SessionsPostgres, users::UsersPostgres,
+            },
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let users_repository = Arc::new(UsersPostgres::new(db_pool.clone()));
+    let oauth_repository = OAuthPostgres::new(db_pool.clone());
+    let sessions_repository = SessionsPostgres::new(db_pool);
+    let oauth_use_case = OAuthUseCase::new(
+        users_repository.clone(),
+        Arc::new(oauth_repository),
+        Arc::new(sessions_repository),
+    );
+
+    let consent_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(consent_details, decide_consent))
+        .routes(utoipa_axum::routes!(revoke_consent))
+        .route_layer(from_fn(users_authorization));
+
+    let client_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(register_client, list_clients))
+        .routes(utoipa_axum::routes!(remove_client))
+        .routes(utoipa_axum::routes!(
+            register_service_account,
+            list_service_accounts
+        ))
+        .routes(utoipa_axum::routes!(remove_service_account))
+        .route_layer(from_fn_with_state(users_repository, admins_authorization));
+
+    OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(openid_configuration))
+        .routes(utoipa_axum::routes!(userinfo))
+        .nest(
+            "/oauth",
+            OpenApiRouter::new()
+                .routes(utoipa_axum::routes!(authorize))
+                .routes(utoipa_axum::routes!(token))
+                .routes(utoipa_axum::routes!(introspect))
+                .routes(utoipa_axum::routes!(revoke))
+                .routes(utoipa_axum::routes!(jwks))
+                .merge(consent_routes)
+                .merge(client_routes),
+        )
+        .with_state(Arc::new(oauth_use_case))
+}
+
+/// OpenID Connect discovery document.
+#[utoipa::path(
+    get,
+    path = "/.well-known/openid-configuration",
+    tags = ["OAuth"],
+    responses(
+        (status = 200, description = "OpenID provider configuration", body = OpenIdConfigurationModel)
+    )
+)]
+pub async fn openid_configuration<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case.openid_configuration() {
+        Ok(configuration) => (StatusCode::OK, Json(configuration)).into_response(),
+        Err(e) => oauth_error_response(e),
+    }
+}
+
+/// Public keys that verify ID tokens, linked from the discovery document as `jwks_uri`.
+#[utoipa::path(
+    get,
+    path = "/jwks",
+    tags = ["OAuth"],
+    responses(
+        (status = 200, description = "JSON Web Key Set", body = JsonWebKeySetModel)
+    )
+)]
+pub async fn jwks<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case.jwks() {
+        Ok(jwks) => (StatusCode::OK, Json(jwks)).into_response(),
+        Err(e) => oauth_error_response(e),
+    }
+}
+
+/// Authorization endpoint (authorization code flow with PKCE).
+///
+/// Redirects back to the client with a code when the user is logged in and has already
+/// consented, otherwise redirects to the frontend consent page. Only clients registered at
+/// this hospital are accepted.
+#[utoipa::path(
+    get,
+    path = "/authorize",
+    tags = ["OAuth"],
+    params(AuthorizeRequestModel),
+    responses(
+        (status = 303, description = "Redirect to the client or to the consent page"),
+        (status = 400, description = "Invalid authorization request", body = OAuthErrorResponseModel)
+    )
+)]
+pub async fn authorize<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    headers: HeaderMap,
+    Query(authorize_model): Query<AuthorizeRequestModel>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    let redirect_to = match get_user_id_from_cookie(&headers) {
+        Some(user_id) => {
+            oauth_use_case
+                .authorize(tenant.hospital_id, user_id, authorize_model.clone())
+                .await
+        }
+        None => Ok(None),
+    };
+
+    match redirect_to {
+        Ok(Some(redirect_to)) => Redirect::to(&redirect_to).into_response(),
+        Ok(None) => match oauth_use_case.consent_url(&authorize_model) {
+            Ok(consent_url) => Redirect::to(&consent_url).into_response(),
+            Err(e) => oauth_error_response(e),
+        },
+        Err(e) => oauth_error_response(e),
+    }
+}
+
+/// Returns what the consent page should show for an authorization request.
+#[utoipa::path(
+    get,
+    path = "/consent",
+    tags = ["OAuth"],
+    params(AuthorizeRequestModel),
+    responses(
+        (status = 200, description = "Fetched consent details successfully", body = ApiResponse<ConsentDetailsResponseModel>)
+    )
+)]
+pub async fn consent_details<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Query(authorize_model): Query<AuthorizeRequestModel>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .consent_details(tenant.hospital_id, user_id, authorize_model)
+        .await
+    {
+        Ok(details) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(details),
+                message: Some("Get consent details successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<ConsentDetailsResponseModel> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Approves or denies an authorization request and returns where to send the browser next.
+#[utoipa::path(
+    post,
+    path = "/consent",
+    tags = ["OAuth"],
+    request_body = ConsentDecisionModel,
+    responses(
+        (status = 200, description = "Consent recorded successfully", body = ApiResponse<ConsentDecisionResponseModel>)
+    )
+)]
+pub async fn decide_consent<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(user_id): Extension<i32>,
+    Json(consent_model): Json<ConsentDecisionModel>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .decide_consent(tenant.hospital_id, user_id, consent_model)
+        .await
+    {
+        Ok(redirect_to) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(ConsentDecisionResponseModel { redirect_to }),
+                message: Some("Consent recorded successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<ConsentDecisionResponseModel> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Revokes the current user's consent for a client app.
+#[utoipa::path(
+    delete,
+    path = "/consents/{client_id}",
+    tags = ["OAuth"],
+    responses(
+        (status = 200, description = "Consent revoked successfully")
+    )
+)]
+pub async fn revoke_consent<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(user_id): Extension<i32>,
+    Path(client_id): Path<String>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case.revoke_consent(user_id, client_id).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("Consent revoked successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Token endpoint. Supports the authorization code (with PKCE) and client credentials grants,
+/// for clients and service accounts registered at this hospital.
+#[utoipa::path(
+    post,
+    path = "/token",
+    tags = ["OAuth"],
+    request_body(content = TokenRequestModel, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Issued tokens successfully", body = TokenResponseModel),
+        (status = 400, description = "Invalid token request", body = OAuthErrorResponseModel)
+    )
+)]
+pub async fn token<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    headers: HeaderMap,
+    Form(token_model): Form<TokenRequestModel>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .token(
+            tenant.hospital_id,
+            get_basic_credentials(&headers),
+            token_model,
+        )
+        .await
+    {
+        Ok(token_response) => (
+            StatusCode::OK,
+            [(header::CACHE_CONTROL, "no-store")],
+            Json(token_response),
+        )
+            .into_response(),
+        Err(e) => oauth_error_response(e),
+    }
+}
+
+/// Token introspection (RFC 7662). Requires confidential client credentials. Roles are those
+/// held at the hospital the request is made to.
+#[utoipa::path(
+    post,
+    path = "/introspect",
+    tags = ["OAuth"],
+    request_body(content = TokenIntrospectionRequestModel, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Token state", body = IntrospectionResponseModel),
+        (status = 401, description = "Invalid client credentials", body = OAuthErrorResponseModel)
+    )
+)]
+pub async fn introspect<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    headers: HeaderMap,
+    Form(introspection_model): Form<TokenIntrospectionRequestModel>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .introspect(
+            tenant.hospital_id,
+            get_basic_credentials(&headers),
+            introspection_model,
+        )
+        .await
+    {
+        Ok(introspection) => (StatusCode::OK, Json(introspection)).into_response(),
+        Err(e) => oauth_error_response(e),
+    }
+}
+
+/// Token revocation (RFC 7009). Revokes the session the token belongs to.
+#[utoipa::path(
+    post,
+    path = "/revoke",
+    tags = ["OAuth"],
+    request_body(content = TokenRevocationRequestModel, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Token revoked or already invalid"),
+        (status = 401, description = "Invalid client credentials", body = OAuthErrorResponseModel)
+    )
+)]
+pub async fn revoke<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    headers: HeaderMap,
+    Form(revocation_model): Form<TokenRevocationRequestModel>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .revoke(
+            tenant.hospital_id,
+            get_basic_credentials(&headers),
+            revocation_model,
+        )
+        .await
+    {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(e) => oauth_error_response(e),
+    }
+}
+
+/// Returns the claims of the user behind an OAuth access token.
+#[utoipa::path(
+    get,
+    path = "/userinfo",
+    tags = ["OAuth"],
+    responses(
+        (status = 200, description = "Fetched user info successfully", body = UserInfoResponseModel),
+        (status = 401, description = "Missing or invalid access token", body = OAuthErrorResponseModel)
+    )
+)]
+pub async fn userinfo<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    headers: HeaderMap,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    let Some(access_token) = get_bearer_token(&headers) else {
+        return invalid_token_response();
+    };
+
+    match oauth_use_case.userinfo(access_token).await {
+        Ok(user_info) => (StatusCode::OK, Json(user_info)).into_response(),
+        Err(_) => invalid_token_response(),
+    }
+}
+
+/// Registers a client app at this hospital. The client secret is only returned once.
+#[utoipa::path(
+    post,
+    path = "/clients",
+    tags = ["OAuth"],
+    request_body = RegisterOAuthClientModel,
+    responses(
+        (status = 201, description = "Client registered successfully", body = ApiResponse<RegisterOAuthClientResponseModel>)
+    )
+)]
+pub async fn register_client<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    Json(register_client_model): Json<RegisterOAuthClientModel>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .register_client(tenant.hospital_id, register_client_model)
+        .await
+    {
+        Ok(client) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(client),
+                message: Some("Register client successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<RegisterOAuthClientResponseModel> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Lists client apps registered at this hospital.
+#[utoipa::path(
+    get,
+    path = "/clients",
+    tags = ["OAuth"],
+    responses(
+        (status = 200, description = "Listed clients successfully", body = ApiResponse<Vec<OAuthClientModel>>)
+    )
+)]
+pub async fn list_clients<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case.list_clients(tenant.hospital_id).await {
+        Ok(clients) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(clients),
+                message: Some("List clients successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<OAuthClientModel>> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Removes one of this hospital's client apps.
+#[utoipa::path(
+    delete,
+    path = "/clients/{client_id}",
+    tags = ["OAuth"],
+    responses(
+        (status = 200, description = "Client removed successfully")
+    )
+)]
+pub async fn remove_client<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(client_id): Path<String>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .remove_client(tenant.hospital_id, client_id)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("Remove client successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Creates a service account at this hospital for the client credentials grant. The secret is
+/// only returned once.
+#[utoipa::path(
+    post,
+    path = "/service-accounts",
+    tags = ["OAuth"],
+    request_body = RegisterServiceAccountModel,
+    responses(
+        (status = 201, description = "Service account created successfully", body = ApiResponse<RegisterServiceAccountResponseModel>)
+    )
+)]
+pub async fn register_service_account<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    Extension(admin_id): Extension<i32>,
+    Json(register_service_account_model): Json<RegisterServiceAccountModel>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .register_service_account(tenant.hospital_id, admin_id, register_service_account_model)
+        .await
+    {
+        Ok(service_account) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(service_account),
+                message: Some("Create service account successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<RegisterServiceAccountResponseModel> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Lists this hospital's service accounts.
+#[utoipa::path(
+    get,
+    path = "/service-accounts",
+    tags = ["OAuth"],
+    responses(
+        (status = 200, description = "Listed service accounts successfully", body = ApiResponse<Vec<ServiceAccountModel>>)
+    )
+)]
+pub async fn list_service_accounts<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .list_service_accounts(tenant.hospital_id)
+        .await
+    {
+        Ok(service_accounts) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(service_accounts),
+                message: Some("List service accounts successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<ServiceAccountModel>> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+/// Removes one of this hospital's service accounts. Tokens already issued stop working at
+/// once.
+#[utoipa::path(
+    delete,
+    path = "/service-accounts/{client_id}",
+    tags = ["OAuth"],
+    responses(
+        (status = 200, description = "Service account removed successfully")
+    )
+)]
+pub async fn remove_service_account<T, U, S>(
+    State(oauth_use_case): State<Arc<OAuthUseCase<T, U, S>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(client_id): Path<String>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+    U: OAuthRepository + Send + Sync,
+    S: SessionsRepository + Send + Sync,
+{
+    match oauth_use_case
+        .remove_service_account(tenant.hospital_id, client_id)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("Remove service account successfully".to_string()),
+            }),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+fn oauth_error_response(err: anyhow::Error) -> Response {
+    let (status_code, error) = match err.downcast_ref::<OAuthError>() {
+        Some(OAuthError::InvalidClient) => (
+            StatusCode::UNAUTHORIZED,
+            OAuthError::InvalidClient.to_string(),
+        ),
+        Some(oauth_error) => (StatusCode::BAD_REQUEST, oauth_error.to_string()),
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "server_error".to_string(),
+        ),
+    };
+
+    (
+        status_code,
+        Json(OAuthErrorResponseModel {
+            error,
+            error_description: None,
+        }),
+    )
+        .into_response()
+}
+
+fn invalid_token_response() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer error=\"invalid_token\"")],
+        Json(OAuthErrorResponseModel {
+            error: "invalid_token".to_string(),
+            error_description: None,
+        }),
+    )
+        .into_response()
+}
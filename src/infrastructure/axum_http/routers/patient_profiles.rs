@@ -0,0 +1,316 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware::{from_fn, from_fn_with_state},
+    response::{IntoResponse, Response},
+};
+use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
+
+use crate::{
+    application::usecases::patient_profiles::PatientProfilesUseCase,
+    domain::{
+        repositories::{errors::RepositoryError, patient_profiles::PatientProfilesRepository},
+        value_objects::{
+            patient_profiles_model::{PatientProfileModel, UpsertPatientProfileModel},
+            policy::Permission,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{
+                PermissionAuthorization, patients_authorization, permission_authorization,
+            },
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::patient_profiles::PatientProfilesPostgres,
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
+    let patient_profiles_repository = PatientProfilesPostgres::new(db_pool.clone());
+    let patient_profiles_use_case =
+        PatientProfilesUseCase::new(Arc::new(patient_profiles_repository));
+
+    let me_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(
+            get_my_profile,
+            create_my_profile,
+            update_my_profile,
+            delete_my_profile
+        ))
+        .route_layer(from_fn(patients_authorization));
+
+    let read_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(find_by_user_id))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::PatientRead, db_pool.clone()),
+            permission_authorization,
+        ));
+
+    let update_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(update_by_user_id))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::PatientUpdate, db_pool),
+            permission_authorization,
+        ));
+
+    OpenApiRouter::new().nest(
+        "/users",
+        OpenApiRouter::new()
+            .merge(me_routes)
+            .merge(read_routes)
+            .merge(update_routes)
+            .with_state(Arc::new(patient_profiles_use_case)),
+    )
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(RepositoryError::Conflict { .. }) = err.downcast_ref::<RepositoryError>() {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("Patient profile already exists. Use PUT to update it".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+fn not_found() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some("Patient profile not found".to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Gets the signed-in patient's profile.
+#[utoipa::path(
+    get,
+    path = "/me/profile",
+    tags = ["Patient Profiles"],
+    responses(
+        (status = 200, description = "Get patient profile successfully", body = ApiResponse<PatientProfileModel>),
+        (status = 404, description = "No profile yet")
+    )
+)]
+pub async fn get_my_profile<P>(
+    State(patient_profiles_use_case): State<Arc<PatientProfilesUseCase<P>>>,
+    Extension(user_id): Extension<i32>,
+) -> Response
+where
+    P: PatientProfilesRepository + Send + Sync,
+{
+    match patient_profiles_use_case.find_by_user_id(user_id).await {
+        Ok(Some(profile)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some("Get patient profile successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Creates the signed-in patient's profile.
+#[utoipa::path(
+    post,
+    path = "/me/profile",
+    tags = ["Patient Profiles"],
+    request_body = UpsertPatientProfileModel,
+    responses(
+        (status = 201, description = "Create patient profile successfully", body = ApiResponse<PatientProfileModel>),
+        (status = 409, description = "Profile already exists"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn create_my_profile<P>(
+    State(patient_profiles_use_case): State<Arc<PatientProfilesUseCase<P>>>,
+    Extension(user_id): Extension<i32>,
+    Json(upsert_patient_profile_model): Json<UpsertPatientProfileModel>,
+) -> Response
+where
+    P: PatientProfilesRepository + Send + Sync,
+{
+    match patient_profiles_use_case
+        .create(user_id, upsert_patient_profile_model)
+        .await
+    {
+        Ok(profile) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some("Create patient profile successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Replaces the signed-in patient's profile.
+#[utoipa::path(
+    put,
+    path = "/me/profile",
+    tags = ["Patient Profiles"],
+    request_body = UpsertPatientProfileModel,
+    responses(
+        (status = 200, description = "Update patient profile successfully", body = ApiResponse<PatientProfileModel>),
+        (status = 404, description = "No profile yet"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn update_my_profile<P>(
+    State(patient_profiles_use_case): State<Arc<PatientProfilesUseCase<P>>>,
+    Extension(user_id): Extension<i32>,
+    Json(upsert_patient_profile_model): Json<UpsertPatientProfileModel>,
+) -> Response
+where
+    P: PatientProfilesRepository + Send + Sync,
+{
+    match patient_profiles_use_case
+        .update(user_id, upsert_patient_profile_model)
+        .await
+    {
+        Ok(Some(profile)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some("Update patient profile successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Deletes the signed-in patient's profile.
+#[utoipa::path(
+    delete,
+    path = "/me/profile",
+    tags = ["Patient Profiles"],
+    responses(
+        (status = 204, description = "Delete patient profile successfully"),
+        (status = 404, description = "No profile yet")
+    )
+)]
+pub async fn delete_my_profile<P>(
+    State(patient_profiles_use_case): State<Arc<PatientProfilesUseCase<P>>>,
+    Extension(user_id): Extension<i32>,
+) -> Response
+where
+    P: PatientProfilesRepository + Send + Sync,
+{
+    match patient_profiles_use_case.delete(user_id).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Gets a patient's profile. Requires `patient.read` unless it is the caller's own.
+#[utoipa::path(
+    get,
+    path = "/{user_id}/profile",
+    tags = ["Patient Profiles"],
+    responses(
+        (status = 200, description = "Get patient profile successfully", body = ApiResponse<PatientProfileModel>),
+        (status = 404, description = "The user has no profile")
+    )
+)]
+pub async fn find_by_user_id<P>(
+    State(patient_profiles_use_case): State<Arc<PatientProfilesUseCase<P>>>,
+    Path(user_id): Path<i32>,
+) -> Response
+where
+    P: PatientProfilesRepository + Send + Sync,
+{
+    match patient_profiles_use_case.find_by_user_id(user_id).await {
+        Ok(Some(profile)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some(format!("Get profile of user id: {} successfully", user_id)),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Replaces a patient's profile. Requires `patient.update` unless it is the caller's own.
+#[utoipa::path(
+    put,
+    path = "/{user_id}/profile",
+    tags = ["Patient Profiles"],
+    request_body = UpsertPatientProfileModel,
+    responses(
+        (status = 200, description = "Update patient profile successfully", body = ApiResponse<PatientProfileModel>),
+        (status = 404, description = "The user has no profile"),
+        (status = 422, description = "Validation failed", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn update_by_user_id<P>(
+    State(patient_profiles_use_case): State<Arc<PatientProfilesUseCase<P>>>,
+    Path(user_id): Path<i32>,
+    Json(upsert_patient_profile_model): Json<UpsertPatientProfileModel>,
+) -> Response
+where
+    P: PatientProfilesRepository + Send + Sync,
+{
+    match patient_profiles_use_case
+        .update(user_id, upsert_patient_profile_model)
+        .await
+    {
+        Ok(Some(profile)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(profile),
+                message: Some(format!(
+                    "Update profile of user id: {} successfully",
+                    user_id
+                )),
+            }),
+        )
+            .into_response(),
+        Ok(None) => not_found(),
+        Err(e) => error_response(e),
+    }
+}
@@ -1,24 +1,38 @@
 use std::sync::Arc;
 
 use axum::{
-    Json, Router,
-    extract::{Path, State},
+    Extension, Json, Router,
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
+    middleware::from_fn_with_state,
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 use utoipa_axum::router::OpenApiRouter;
+use validator::ValidationErrors;
 
 use crate::{
     application::usecases::users::UsersUseCase,
     domain::{
-        repositories::users::UsersRepository,
-        value_objects::users_model::{
-            FindUserByIdResponseModel, RegisterUserModel, RegisterUserResponseModel,
+        repositories::{errors::RepositoryError, users::UsersRepository},
+        value_objects::{
+            consents_model::ConsentError,
+            pagination::PageModel,
+            policy::Permission,
+            tenant::Tenant,
+            users_model::{
+                FindUserByIdResponseModel, ListUsersQueryModel, RegisterUserModel,
+                RegisterUserResponseModel, SearchUsersQueryModel, UserSearchResultModel,
+            },
         },
     },
     infrastructure::{
-        axum_http::api_response::ApiResponse,
+        axum_http::{
+            api_response::{ApiResponse, FieldErrorModel},
+            middleware::{
+                IdempotencyState, PermissionAuthorization, idempotency, permission_authorization,
+            },
+        },
         postgres::{postgres_connection::PgPoolSquad, repositories::users::UsersPostgres},
     },
 };
@@ -36,95 +50,299 @@ pub fn routes(db_pool: PgPoolSquad) -> Router {
 
 /// Defines routes with OpenAPI specs. Should be used over `routes()` where possible.
 pub fn routes_with_openapi(db_pool: PgPoolSquad) -> OpenApiRouter {
-    let users_repository = UsersPostgres::new(db_pool);
+    let users_repository = UsersPostgres::new(db_pool.clone());
     let users_use_case = UsersUseCase::new(Arc::new(users_repository));
 
+    let read_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(list))
+        .routes(utoipa_axum::routes!(search))
+        .routes(utoipa_axum::routes!(find_by_id))
+        .routes(utoipa_axum::routes!(find_by_hospital_number))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::UserRead, db_pool.clone()),
+            permission_authorization,
+        ));
+
+    let write_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(register))
+        .route_layer(from_fn_with_state(
+            IdempotencyState::new(db_pool.clone()),
+            idempotency,
+        ));
+
+    let staff_write_routes = OpenApiRouter::new()
+        .routes(utoipa_axum::routes!(register_on_behalf))
+        .route_layer(from_fn_with_state(
+            IdempotencyState::new(db_pool.clone()),
+            idempotency,
+        ))
+        .route_layer(from_fn_with_state(
+            PermissionAuthorization::new(Permission::PatientRegister, db_pool),
+            permission_authorization,
+        ));
+
     OpenApiRouter::new().nest(
         "/users",
         OpenApiRouter::new()
-            .routes(utoipa_axum::routes!(register))
-            .routes(utoipa_axum::routes!(find_by_id))
+            .merge(write_routes)
+            .merge(staff_write_routes)
+            .merge(read_routes)
             .with_state(Arc::new(users_use_case)),
     )
 }
 
 /// Registers a new user (patient or doctor) in the system.
+///
+/// The current version of every consent purpose required at registration must be among
+/// `consent_version_ids`; see `GET /consents/current`.
+///
+/// A patient who already has an account, e.g. from another hospital, is registered at this
+/// hospital with that account when `password` matches it; the other fields are ignored.
+///
+/// Send an `Idempotency-Key` header to make retries safe: a retry with the same key and body
+/// replays the first response instead of registering again.
 #[utoipa::path(
     post,
     path = "/",
     tags = ["Users"],
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Client-generated key that makes retries safe")
+    ),
     request_body = RegisterUserModel,
     responses(
-        (status = 201, description = "User registered successfully", body = ApiResponse<RegisterUserResponseModel>)
+        (status = 201, description = "User registered successfully", body = ApiResponse<RegisterUserResponseModel>),
+        (status = 409, description = "An account with this citizen ID already exists here, or its password does not match"),
+        (status = 422, description = "Validation failed or required consents not accepted", body = ApiResponse<Vec<FieldErrorModel>>)
     )
 )]
 pub async fn register<T>(
     State(users_use_case): State<Arc<UsersUseCase<T>>>,
+    Extension(tenant): Extension<Tenant>,
     Json(register_user_model): Json<RegisterUserModel>,
 ) -> impl IntoResponse
 where
     T: UsersRepository + Send + Sync,
 {
-    match users_use_case.register(register_user_model).await {
-        Ok(user_id) => {
-            let data = RegisterUserResponseModel {
-                hospital_number: user_id,
-            };
+    match users_use_case
+        .register(tenant.hospital_id, register_user_model)
+        .await
+    {
+        Ok(data) => {
+            let message = format!("Register user id: {} successfully", data.user_id);
             (
                 StatusCode::CREATED,
                 Json(ApiResponse {
                     data: Some(data),
-                    message: Some(format!("Register user id: {} successfully", user_id)),
+                    message: Some(message),
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<RegisterUserResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => error_response(e),
     }
 }
 
-/// Find user by id.
+/// Registers a patient on their behalf, e.g. by a receptionist at the front desk. The
+/// registration is audited with the staff member as the actor, and its consents are recorded
+/// as given in person. A patient who already has an account is registered at this hospital
+/// with it when `first_name`, `last_name` and `date_of_birth` match their account and profile;
+/// otherwise the 409 stands. Requires `patient.register`.
+///
+/// Accepts an `Idempotency-Key` header like `POST /users`.
 #[utoipa::path(
-    get,
-    path = "/{user_id}",
+    post,
+    path = "/on-behalf",
     tags = ["Users"],
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Client-generated key that makes retries safe")
+    ),
+    request_body = RegisterUserModel,
     responses(
-        (status = 201, description = "Find user by id successfully", body = ApiResponse<FindUserByIdResponseModel>)
+        (status = 201, description = "User registered successfully", body = ApiResponse<RegisterUserResponseModel>),
+        (status = 409, description = "An account with this citizen ID already exists here, or its name or date of birth does not match"),
+        (status = 422, description = "Validation failed or required consents not accepted", body = ApiResponse<Vec<FieldErrorModel>>)
     )
 )]
-pub async fn find_by_id<T>(
+pub async fn register_on_behalf<T>(
     State(users_use_case): State<Arc<UsersUseCase<T>>>,
-    Path(user_id): Path<i32>,
-) -> impl IntoResponse
+    Extension(tenant): Extension<Tenant>,
+    Extension(staff_id): Extension<i32>,
+    Json(register_user_model): Json<RegisterUserModel>,
+) -> Response
 where
     T: UsersRepository + Send + Sync,
 {
-    match users_use_case.find_by_id(user_id).await {
-        Ok(user_entity) => {
-            let data = FindUserByIdResponseModel {
-                id: user_entity.id,
-                citizen_id: user_entity.citizen_id.clone(),
-                first_name: user_entity.first_name.clone(),
-                last_name: user_entity.last_name.clone(),
-                phone_number: user_entity.phone_number.clone(),
-                role: user_entity.role.clone(),
-                created_at: user_entity.created_at,
-                updated_at: user_entity.updated_at,
-                deleted_at: user_entity.deleted_at,
-            };
+    match users_use_case
+        .register_on_behalf(tenant.hospital_id, staff_id, register_user_model)
+        .await
+    {
+        Ok(data) => {
+            let message = format!("Register user id: {} successfully", data.user_id);
             (
                 StatusCode::CREATED,
                 Json(ApiResponse {
                     data: Some(data),
-                    message: Some(format!("Get user id: {} successfully", user_id)),
+                    message: Some(message),
                 }),
             )
+                .into_response()
         }
+        Err(e) => error_response(e),
+    }
+}
+
+fn error_response(err: anyhow::Error) -> Response {
+    if let Some(validation_errors) = err.downcast_ref::<ValidationErrors>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                data: Some(FieldErrorModel::from_validation_errors(validation_errors)),
+                message: Some("Validation failed".to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if let Some(consent_error) = err.downcast_ref::<ConsentError>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(consent_error.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    // ห้ามส่ง hospital number ของ account เดิมกลับไป
+    if let Some(RepositoryError::Conflict { .. }) = err.downcast_ref::<RepositoryError>() {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some(
+                    "An account with this citizen ID already exists. Sign in to recover it"
+                        .to_string(),
+                ),
+            }),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()> {
+            data: None,
+            message: Some(err.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Lists and searches users of the hospital, one page at a time. Requires `user.read`.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Users"],
+    params(ListUsersQueryModel),
+    responses(
+        (status = 200, description = "Listed users successfully", body = ApiResponse<PageModel<FindUserByIdResponseModel>>),
+        (status = 422, description = "Invalid filter or cursor", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn list<T>(
+    State(users_use_case): State<Arc<UsersUseCase<T>>>,
+    Extension(tenant): Extension<Tenant>,
+    Query(list_users_query): Query<ListUsersQueryModel>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+{
+    match users_use_case
+        .list(tenant.hospital_id, list_users_query)
+        .await
+    {
+        Ok(page) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(page),
+                message: Some("List users successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Fuzzy name search; requires `user.read`. Tolerates typos and matches Thai names typed in
+/// Latin script.
+#[utoipa::path(
+    get,
+    path = "/search",
+    tags = ["Users"],
+    params(SearchUsersQueryModel),
+    responses(
+        (status = 200, description = "Searched users successfully", body = ApiResponse<Vec<UserSearchResultModel>>),
+        (status = 422, description = "Invalid query", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn search<T>(
+    State(users_use_case): State<Arc<UsersUseCase<T>>>,
+    Extension(tenant): Extension<Tenant>,
+    Query(search_users_query): Query<SearchUsersQueryModel>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+{
+    match users_use_case
+        .search(tenant.hospital_id, search_users_query)
+        .await
+    {
+        Ok(results) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(results),
+                message: Some("Search users successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// Find user by id. Users not registered at the hospital are not found.
+#[utoipa::path(
+    get,
+    path = "/{user_id}",
+    tags = ["Users"],
+    responses(
+        (status = 201, description = "Find user by id successfully", body = ApiResponse<FindUserByIdResponseModel>),
+        (status = 404, description = "User not found at this hospital")
+    )
+)]
+pub async fn find_by_id<T>(
+    State(users_use_case): State<Arc<UsersUseCase<T>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(user_id): Path<i32>,
+) -> impl IntoResponse
+where
+    T: UsersRepository + Send + Sync,
+{
+    match users_use_case.find_by_id(tenant.hospital_id, user_id).await {
+        Ok(Some(data)) => (
+            StatusCode::CREATED,
+            Json(ApiResponse {
+                data: Some(data),
+                message: Some(format!("Get user id: {} successfully", user_id)),
+            }),
+        ),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<FindUserByIdResponseModel> {
+                data: None,
+                message: Some(format!("User id: {} not found", user_id)),
+            }),
+        ),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::<FindUserByIdResponseModel> {
@@ -134,3 +352,47 @@ where
         ),
     }
 }
+
+/// Find a user of the hospital by hospital number, as printed on their card; requires
+/// `user.read`. Dashes and spaces in the number are ignored.
+#[utoipa::path(
+    get,
+    path = "/hospital-number/{hospital_number}",
+    tags = ["Users"],
+    responses(
+        (status = 200, description = "Found user successfully", body = ApiResponse<FindUserByIdResponseModel>),
+        (status = 404, description = "No user with this hospital number at this hospital"),
+        (status = 422, description = "Malformed hospital number or wrong check character", body = ApiResponse<Vec<FieldErrorModel>>)
+    )
+)]
+pub async fn find_by_hospital_number<T>(
+    State(users_use_case): State<Arc<UsersUseCase<T>>>,
+    Extension(tenant): Extension<Tenant>,
+    Path(hospital_number): Path<String>,
+) -> Response
+where
+    T: UsersRepository + Send + Sync,
+{
+    match users_use_case
+        .find_by_hospital_number(tenant.hospital_id, hospital_number)
+        .await
+    {
+        Ok(Some(data)) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: Some(data),
+                message: Some("Find user by hospital number successfully".to_string()),
+            }),
+        )
+            .into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()> {
+                data: None,
+                message: Some("User not found".to_string()),
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use rustls::{RootCertStore, ServerConfig, server::WebPkiClientVerifier};
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use crate::config::config_model::Tls;
+
+/// Subject of the client certificate presented over mTLS, inserted into request extensions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCertificate {
+    pub subject: String,
+    pub common_name: Option<String>,
+}
+
+pub fn load_server_config(tls: &Tls) -> Result<Arc<ServerConfig>> {
+    let certs = CertificateDer::pem_file_iter(&tls.cert_path)
+        .map_err(|e| anyhow::anyhow!("read {} failed: {e}", tls.cert_path))?
+        .collect::<Result<Vec<_>, _>>()?;
+    let key = PrivateKeyDer::from_pem_file(&tls.key_path)
+        .map_err(|e| anyhow::anyhow!("read {} failed: {e}", tls.key_path))?;
+
+    let builder = ServerConfig::builder();
+
+    let builder = match &tls.client_ca_path {
+        Some(client_ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in CertificateDer::pem_file_iter(client_ca_path)
+                .map_err(|e| anyhow::anyhow!("read {} failed: {e}", client_ca_path))?
+            {
+                roots.add(cert?)?;
+            }
+
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots));
+            // ไม่บังคับ = caller ที่ไม่มี cert ยังใช้ cookie / token ได้ตามเดิม
+            let verifier = if tls.client_auth_required {
+                verifier.build()?
+            } else {
+                verifier.allow_unauthenticated().build()?
+            };
+
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut server_config = builder.with_single_cert(certs, key)?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(Arc::new(server_config))
+}
+
+pub fn client_certificate(cert: &CertificateDer<'_>) -> Option<ClientCertificate> {
+    let (_, cert) = X509Certificate::from_der(cert.as_ref()).ok()?;
+    let subject = cert.subject();
+
+    Some(ClientCertificate {
+        subject: subject.to_string(),
+        common_name: subject
+            .iter_common_name()
+            .next()
+            .and_then(|cn| cn.as_str().ok())
+            .map(str::to_string),
+    })
+}
@@ -0,0 +1,43 @@
+use std::{sync::Arc, time::Duration};
+
+use tracing::{error, info};
+
+use crate::{
+    application::usecases::data_exports::DataExportsUseCase,
+    config::config_model::DataExport,
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad, repositories::data_exports::DataExportsPostgres,
+    },
+};
+
+/// Every `interval_seconds`, deletes expired exports and generates the queued ones until the
+/// queue is empty. A failed run is logged and retried on the next tick.
+pub async fn run(db_pool: PgPoolSquad, config: DataExport) {
+    let interval_seconds = config.interval_seconds;
+    let data_exports_use_case =
+        DataExportsUseCase::new(Arc::new(DataExportsPostgres::new(db_pool)), config);
+
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        match data_exports_use_case.delete_expired().await {
+            Ok(deleted) if deleted > 0 => info!("Deleted {} expired data exports", deleted),
+            Ok(_) => {}
+            Err(e) => error!("Deleting expired data exports failed: {}", e),
+        }
+
+        loop {
+            match data_exports_use_case.process_next().await {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(e) => {
+                    error!("Generating a data export failed: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+}
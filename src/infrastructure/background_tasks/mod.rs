@@ -0,0 +1,2 @@
+pub mod data_exports;
+pub mod role_grant_expiry;
@@ -0,0 +1,34 @@
+use std::{sync::Arc, time::Duration};
+
+use tracing::{error, info};
+
+use crate::{
+    application::usecases::role_grants::RoleGrantsUseCase,
+    config::config_model::RoleGrantExpiry,
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad, repositories::role_grants::RoleGrantsPostgres,
+    },
+};
+
+/// Runs the role grant sweep every `interval_seconds` until the process exits. A failed run is
+/// logged and retried on the next tick.
+pub async fn run(db_pool: PgPoolSquad, config: RoleGrantExpiry) {
+    let role_grants_use_case = RoleGrantsUseCase::new(Arc::new(RoleGrantsPostgres::new(db_pool)));
+    let notice = chrono::Duration::days(config.notice_days);
+
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_seconds));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        match role_grants_use_case.sweep(notice).await {
+            Ok(sweep) if sweep.notified > 0 || sweep.expired > 0 => info!(
+                "Role grant sweep: notified {} expiring, expired {} and revoked {} sessions",
+                sweep.notified, sweep.expired, sweep.revoked_sessions
+            ),
+            Ok(_) => {}
+            Err(e) => error!("Role grant sweep failed: {}", e),
+        }
+    }
+}
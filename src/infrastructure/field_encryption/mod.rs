@@ -0,0 +1,127 @@
+use std::sync::OnceLock;
+
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+use anyhow::Result;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{
+    config::config_loader::get_field_encryption_env,
+    domain::value_objects::validation::CITIZEN_ID_PREFIX_MIN_LENGTH,
+};
+
+/// Marks a column value written by `encrypt`; anything else is a not-yet-migrated plaintext.
+const CIPHERTEXT_PREFIX: &str = "enc:v1:";
+const NONCE_LENGTH: usize = 12;
+
+struct Keys {
+    data_key: [u8; 32],
+    blind_index_key: [u8; 32],
+}
+
+static KEYS: OnceLock<Keys> = OnceLock::new();
+
+fn read_key_file(path: &str) -> Result<[u8; 32]> {
+    let encoded = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("read key file {} failed: {e}", path))?;
+    let decoded = STANDARD.decode(encoded.trim())?;
+
+    decoded
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("key file {} must hold 32 base64-encoded bytes", path))
+}
+
+fn keys() -> Result<&'static Keys> {
+    if let Some(keys) = KEYS.get() {
+        return Ok(keys);
+    }
+
+    let env = get_field_encryption_env()?;
+    let keys = Keys {
+        data_key: read_key_file(&env.data_key_path)?,
+        blind_index_key: read_key_file(&env.blind_index_key_path)?,
+    };
+
+    Ok(KEYS.get_or_init(|| keys))
+}
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(CIPHERTEXT_PREFIX)
+}
+
+/// AES-256-GCM with a random nonce, stored as `enc:v1:` + base64(nonce || ciphertext).
+pub fn encrypt(plaintext: &str) -> Result<String> {
+    let payload = encrypt_bytes(plaintext.as_bytes())?;
+
+    Ok(format!("{}{}", CIPHERTEXT_PREFIX, STANDARD.encode(payload)))
+}
+
+/// Values without the prefix are returned unchanged so rows not yet migrated stay readable.
+pub fn decrypt(value: &str) -> Result<String> {
+    let Some(encoded) = value.strip_prefix(CIPHERTEXT_PREFIX) else {
+        return Ok(value.to_string());
+    };
+
+    let plaintext = decrypt_bytes(&STANDARD.decode(encoded)?)?;
+
+    Ok(String::from_utf8(plaintext)?)
+}
+
+/// `encrypt` for binary columns: nonce || ciphertext, without prefix or base64.
+pub fn encrypt_bytes(plaintext: &[u8]) -> Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&keys()?.data_key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| anyhow::anyhow!("field encryption failed"))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+
+    Ok(payload)
+}
+
+pub fn decrypt_bytes(payload: &[u8]) -> Result<Vec<u8>> {
+    if payload.len() < NONCE_LENGTH {
+        return Err(anyhow::anyhow!("field ciphertext is too short"));
+    }
+    let (nonce, ciphertext) = payload.split_at(NONCE_LENGTH);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&keys()?.data_key));
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("field decryption failed"))
+}
+
+/// Keyed HMAC-SHA256 (hex) used for equality lookups and uniqueness on encrypted columns.
+pub fn blind_index(value: &str) -> Result<String> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&keys()?.blind_index_key)
+        .map_err(|_| anyhow::anyhow!("invalid blind index key"))?;
+    mac.update(value.as_bytes());
+
+    Ok(mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Blind index of one citizen ID prefix, kept apart from `blind_index` of the full value.
+pub fn citizen_id_prefix_index(prefix: &str) -> Result<String> {
+    blind_index(&format!("citizen_id_prefix:{}", prefix))
+}
+
+/// Prefix indexes from `CITIZEN_ID_PREFIX_MIN_LENGTH` digits up to the full citizen ID.
+pub fn citizen_id_prefix_indexes(citizen_id: &str) -> Result<Vec<String>> {
+    (CITIZEN_ID_PREFIX_MIN_LENGTH..=citizen_id.len())
+        .filter_map(|length| citizen_id.get(..length))
+        .map(citizen_id_prefix_index)
+        .collect()
+}
@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+/// Identifies the user by `hospital_number` or, failing that, by `citizen_id`.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LoginModel {
-    pub hospital_number: i32,
+    #[serde(default)]
+    pub hospital_number: Option<String>,
+    #[serde(default)]
+    pub citizen_id: Option<String>,
     pub password: String,
 }
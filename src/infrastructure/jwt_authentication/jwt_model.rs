@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::domain::value_objects::roles::Role;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Passport {
     pub access_token: String,
@@ -10,13 +12,53 @@ pub struct Passport {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Claims {
     pub sub: String,
-    pub role: Roles,
+    pub role: Role,
+    pub exp: usize,
+    pub iat: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    /// Hospital the token was issued at. Tokens issued before tenancy have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hid: Option<i32>,
+    /// Set on a guardian's delegated token: `sub` is the dependent, `act.sub` the guardian.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+}
+
+/// Who is actually making requests with a token issued for someone else (RFC 8693 `act`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Actor {
+    pub sub: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OAuthClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub scope: String,
     pub exp: usize,
     pub iat: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    /// Grant type the token was issued through; `client_credentials` marks a service token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gty: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
-pub enum Roles {
-    Patient,
-    Doctor,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub exp: usize,
+    pub iat: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
 }
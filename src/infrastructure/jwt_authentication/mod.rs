@@ -1,11 +1,97 @@
 pub mod authentication_model;
 pub mod jwt_model;
 
+use std::sync::OnceLock;
+
 use anyhow::Result;
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
-use jwt_model::Claims;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jwt_model::{Claims, OAuthClaims};
+use rustls_pki_types::{pem::PemObject, SubjectPublicKeyInfoDer};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use x509_parser::{
+    prelude::{FromDer, SubjectPublicKeyInfo},
+    public_key::PublicKey,
+};
+
+use crate::{
+    config::config_loader::get_oauth_secret_env,
+    domain::value_objects::oauth_model::JsonWebKeyModel,
+};
+
+/// RSA key pair that signs ID tokens; the public half is published as a JWK.
+struct IdTokenKeys {
+    encoding_key: EncodingKey,
+    jwk: JsonWebKeyModel,
+}
+
+static ID_TOKEN_KEYS: OnceLock<IdTokenKeys> = OnceLock::new();
+
+fn id_token_keys() -> Result<&'static IdTokenKeys> {
+    if let Some(keys) = ID_TOKEN_KEYS.get() {
+        return Ok(keys);
+    }
+
+    let env = get_oauth_secret_env()?;
+    let private_key = std::fs::read(&env.id_token_key_path)
+        .map_err(|e| anyhow::anyhow!("read {} failed: {e}", env.id_token_key_path))?;
+    let public_key = SubjectPublicKeyInfoDer::from_pem_file(&env.id_token_public_key_path)
+        .map_err(|e| anyhow::anyhow!("read {} failed: {e}", env.id_token_public_key_path))?;
+
+    let (_, spki) = SubjectPublicKeyInfo::from_der(public_key.as_ref())
+        .map_err(|e| anyhow::anyhow!("parse {} failed: {e}", env.id_token_public_key_path))?;
+    let Ok(PublicKey::RSA(rsa)) = spki.parsed() else {
+        return Err(anyhow::anyhow!(
+            "{} is not an RSA public key",
+            env.id_token_public_key_path
+        ));
+    };
+
+    // DER integer อาจมี 0x00 นำหน้า ซึ่ง JWK ไม่ใส่
+    let jwk = JsonWebKeyModel {
+        kty: "RSA".to_string(),
+        key_use: "sig".to_string(),
+        alg: "RS256".to_string(),
+        kid: URL_SAFE_NO_PAD.encode(Sha256::digest(public_key.as_ref())),
+        n: URL_SAFE_NO_PAD.encode(trim_leading_zeros(rsa.modulus)),
+        e: URL_SAFE_NO_PAD.encode(trim_leading_zeros(rsa.exponent)),
+    };
+    let keys = IdTokenKeys {
+        encoding_key: EncodingKey::from_rsa_pem(&private_key)?,
+        jwk,
+    };
+
+    // key คนละคู่จะออก token ที่ client ตรวจไม่ผ่าน จึงให้ล้มตั้งแต่ตอนโหลด
+    let probe = sign_id_token(&keys, &serde_json::json!({}))?;
+    let decoding_key = DecodingKey::from_rsa_components(&keys.jwk.n, &keys.jwk.e)?;
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    decode::<serde_json::Value>(&probe, &decoding_key, &validation).map_err(|_| {
+        anyhow::anyhow!(
+            "{} is not the public key of {}",
+            env.id_token_public_key_path,
+            env.id_token_key_path
+        )
+    })?;
+
+    Ok(ID_TOKEN_KEYS.get_or_init(|| keys))
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn sign_id_token<T: Serialize>(keys: &IdTokenKeys, claims: &T) -> Result<String> {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(keys.jwk.kid.clone());
 
-pub fn generate_token(secret: String, claims: &Claims) -> Result<String> {
+    Ok(encode(&header, claims, &keys.encoding_key)?)
+}
+
+pub fn generate_token<T: Serialize>(secret: String, claims: &T) -> Result<String> {
     let token = encode(
         &Header::default(),
         claims,
@@ -22,3 +108,27 @@ pub fn verify_token(secret: String, token: String) -> Result<Claims> {
     )?;
     Ok(result.claims)
 }
+
+pub fn verify_oauth_token(secret: String, issuer: &str, token: String) -> Result<OAuthClaims> {
+    let mut validation = Validation::default();
+    validation.set_issuer(&[issuer]);
+    // aud ของ access token คือ client_id ของแต่ละแอป ไม่ได้ตรวจที่นี่
+    validation.validate_aud = false;
+
+    let result = decode(
+        &token,
+        &DecodingKey::from_secret(secret.as_ref()),
+        &validation,
+    )?;
+    Ok(result.claims)
+}
+
+/// Signs with RS256 so clients can verify ID tokens against `/oauth/jwks` without a shared
+/// secret.
+pub fn generate_id_token<T: Serialize>(claims: &T) -> Result<String> {
+    sign_id_token(id_token_keys()?, claims)
+}
+
+pub fn id_token_jwk() -> Result<JsonWebKeyModel> {
+    Ok(id_token_keys()?.jwk.clone())
+}
@@ -1,4 +1,9 @@
 pub mod postgres;
 pub mod jwt_authentication;
 pub mod argon2_hashing;
-pub mod axum_http;
\ No newline at end of file
+pub mod axum_http;
+pub mod field_encryption;
+pub mod opaque_token;
+pub mod transliteration;
+pub mod background_tasks;
+pub mod zip_archive;
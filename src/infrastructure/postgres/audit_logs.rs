@@ -0,0 +1,34 @@
+use anyhow::Result;
+use diesel::dsl::insert_into;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::{
+    domain::{
+        entities::audit_logs::InsertAuditLogEntity, value_objects::audit_logs_model::AuditAction,
+    },
+    infrastructure::postgres::schema::audit_logs,
+};
+
+/// Appends an entry at the action's priority. Takes a connection so the entry commits or rolls
+/// back with the change it describes.
+pub async fn record(
+    conn: &mut AsyncPgConnection,
+    actor_id: Option<i32>,
+    action: AuditAction,
+    subject_user_id: Option<i32>,
+    details: serde_json::Value,
+) -> Result<()> {
+    insert_into(audit_logs::table)
+        .values(InsertAuditLogEntity {
+            actor_id,
+            action: action.to_string(),
+            subject_user_id,
+            details,
+            created_at: chrono::Utc::now().naive_utc(),
+            priority: action.priority().to_string(),
+        })
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
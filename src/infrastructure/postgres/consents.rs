@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, dsl::insert_into};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::{
+    domain::{
+        entities::consents::{
+            ConsentPurposeEntity, ConsentRecordEntity, ConsentVersionEntity,
+            InsertConsentRecordEntity,
+        },
+        value_objects::consents_model::{ConsentAction, ConsentError, RegistrationConsents},
+    },
+    infrastructure::postgres::schema::{consent_purposes, consent_records, consent_versions},
+};
+
+/// Every purpose that has a version in effect, paired with the latest such version.
+pub async fn current_versions(
+    conn: &mut AsyncPgConnection,
+) -> Result<Vec<(ConsentPurposeEntity, ConsentVersionEntity)>> {
+    let rows: Vec<(ConsentPurposeEntity, ConsentVersionEntity)> = consent_purposes::table
+        .inner_join(consent_versions::table)
+        .filter(consent_versions::effective_from.le(chrono::Utc::now().naive_utc()))
+        .order((consent_purposes::id.asc(), consent_versions::version.asc()))
+        .select((
+            ConsentPurposeEntity::as_select(),
+            ConsentVersionEntity::as_select(),
+        ))
+        .load(conn)
+        .await?;
+
+    // แถวสุดท้ายของแต่ละ purpose คือฉบับปัจจุบัน
+    let mut current: Vec<(ConsentPurposeEntity, ConsentVersionEntity)> = Vec::new();
+    for (purpose, version) in rows {
+        match current.last_mut() {
+            Some(last) if last.0.id == purpose.id => *last = (purpose, version),
+            _ => current.push((purpose, version)),
+        }
+    }
+
+    Ok(current)
+}
+
+/// Keeps the latest record per user and purpose of records loaded oldest first.
+pub fn latest_per_purpose(
+    rows: Vec<(ConsentRecordEntity, ConsentVersionEntity)>,
+) -> Vec<(ConsentRecordEntity, ConsentVersionEntity)> {
+    let mut latest = HashMap::new();
+    for (record, version) in rows {
+        latest.insert((record.user_id, version.purpose_id), (record, version));
+    }
+
+    let mut latest: Vec<_> = latest.into_values().collect();
+    latest.sort_by_key(|(record, _)| record.id);
+    latest
+}
+
+/// Grants the consents accepted at registration. Fails with
+/// `ConsentError::RequiredNotAccepted` when a purpose required at registration is missing, or
+/// `ConsentError::VersionNotCurrent` when an accepted version is not in effect.
+pub async fn accept_at_registration(
+    conn: &mut AsyncPgConnection,
+    user_id: i32,
+    consents: &RegistrationConsents,
+    recorded_by: Option<i32>,
+) -> Result<()> {
+    let current = current_versions(conn).await?;
+
+    let missing: Vec<String> = current
+        .iter()
+        .filter(|(purpose, version)| {
+            purpose.required_at_registration && !consents.consent_version_ids.contains(&version.id)
+        })
+        .map(|(purpose, _)| purpose.code.clone())
+        .collect();
+    if !missing.is_empty() {
+        return Err(ConsentError::RequiredNotAccepted { purposes: missing }.into());
+    }
+
+    let mut consent_version_ids = consents.consent_version_ids.clone();
+    consent_version_ids.sort_unstable();
+    consent_version_ids.dedup();
+
+    if consent_version_ids
+        .iter()
+        .any(|id| !current.iter().any(|(_, version)| version.id == *id))
+    {
+        return Err(ConsentError::VersionNotCurrent.into());
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let rows: Vec<InsertConsentRecordEntity> = consent_version_ids
+        .into_iter()
+        .map(|consent_version_id| InsertConsentRecordEntity {
+            user_id,
+            consent_version_id,
+            action: ConsentAction::Granted.to_string(),
+            channel: consents.channel.to_string(),
+            recorded_by,
+            created_at: now,
+        })
+        .collect();
+
+    insert_into(consent_records::table)
+        .values(rows)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
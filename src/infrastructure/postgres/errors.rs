@@ -0,0 +1,16 @@
+use diesel::result::{DatabaseErrorKind, Error};
+
+use crate::domain::repositories::errors::RepositoryError;
+
+/// Turns unique violations into `RepositoryError::Conflict`; everything else is passed through.
+pub fn map_constraint_violation(err: Error) -> anyhow::Error {
+    match err {
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+            RepositoryError::Conflict {
+                constraint: info.constraint_name().unwrap_or_default().to_string(),
+            }
+            .into()
+        }
+        err => err.into(),
+    }
+}
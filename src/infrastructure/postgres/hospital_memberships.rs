@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use diesel::{
+    ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, dsl::insert_into, pg::Pg,
+    sql_types::Integer,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::{
+    domain::{
+        entities::hospitals::{HospitalEntity, InsertHospitalMembershipEntity},
+        value_objects::hospital_number::HospitalNumberFormat,
+    },
+    infrastructure::postgres::schema::{
+        hospital_memberships, hospital_number_sequences, hospitals,
+    },
+};
+
+/// Ids of users registered at the hospital, for use as `users::id.eq_any(members_of(id))`.
+pub fn members_of(hospital_id: i32) -> hospital_memberships::BoxedQuery<'static, Pg, Integer> {
+    hospital_memberships::table
+        .filter(hospital_memberships::hospital_id.eq(hospital_id))
+        .select(hospital_memberships::user_id)
+        .into_boxed()
+}
+
+/// Issues the hospital's next hospital number in its own format. The sequence row for the year
+/// stays locked until the surrounding transaction ends, so concurrent registrations never
+/// share a number.
+pub async fn allocate_hospital_number(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+) -> Result<String> {
+    let hospital = hospitals::table
+        .find(hospital_id)
+        .select(HospitalEntity::as_select())
+        .first::<HospitalEntity>(conn)
+        .await?;
+
+    let format = HospitalNumberFormat::from(&hospital);
+    let year_code = format.year_code(chrono::Utc::now().date_naive());
+
+    // ลำดับเริ่มใหม่ทุกปี: แถวแรกของปีเริ่มที่ 1
+    let sequence = insert_into(hospital_number_sequences::table)
+        .values((
+            hospital_number_sequences::hospital_id.eq(hospital_id),
+            hospital_number_sequences::year_code.eq(year_code),
+            hospital_number_sequences::last_value.eq(1),
+        ))
+        .on_conflict((
+            hospital_number_sequences::hospital_id,
+            hospital_number_sequences::year_code,
+        ))
+        .do_update()
+        .set(hospital_number_sequences::last_value.eq(hospital_number_sequences::last_value + 1))
+        .returning(hospital_number_sequences::last_value)
+        .get_result::<i32>(conn)
+        .await?;
+
+    Ok(format.format(year_code, sequence))
+}
+
+/// Returns the user's hospital number at the hospital, registering them there first when
+/// needed, e.g. for a doctor granted a role at a second hospital.
+pub async fn ensure_membership(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+    user_id: i32,
+) -> Result<String> {
+    let existing = hospital_memberships::table
+        .filter(hospital_memberships::hospital_id.eq(hospital_id))
+        .filter(hospital_memberships::user_id.eq(user_id))
+        .select(hospital_memberships::hospital_number)
+        .first::<String>(conn)
+        .await
+        .optional()?;
+
+    if let Some(hospital_number) = existing {
+        return Ok(hospital_number);
+    }
+
+    let hospital_number = allocate_hospital_number(conn, hospital_id).await?;
+
+    insert_into(hospital_memberships::table)
+        .values(InsertHospitalMembershipEntity {
+            hospital_id,
+            user_id,
+            hospital_number: hospital_number.clone(),
+            created_at: chrono::Utc::now().naive_utc(),
+        })
+        .execute(conn)
+        .await?;
+
+    Ok(hospital_number)
+}
+
+/// Hospital numbers at the hospital by user id; users not registered there are absent.
+pub async fn find_hospital_numbers(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+    user_ids: Vec<i32>,
+) -> Result<HashMap<i32, String>> {
+    let rows: Vec<(i32, String)> = hospital_memberships::table
+        .filter(hospital_memberships::hospital_id.eq(hospital_id))
+        .filter(hospital_memberships::user_id.eq_any(user_ids))
+        .select((
+            hospital_memberships::user_id,
+            hospital_memberships::hospital_number,
+        ))
+        .load(conn)
+        .await?;
+
+    Ok(rows.into_iter().collect())
+}
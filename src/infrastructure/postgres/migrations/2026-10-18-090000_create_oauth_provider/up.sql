@@ -0,0 +1,42 @@
+CREATE TABLE oauth_clients (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    client_id            VARCHAR(64)  NOT NULL UNIQUE,
+    client_secret        VARCHAR(255),
+    name                 VARCHAR(100) NOT NULL,
+
+    redirect_uris        VARCHAR[] NOT NULL,
+    allowed_scopes       VARCHAR[] NOT NULL,
+
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at           TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at           TIMESTAMP
+);
+
+CREATE TABLE oauth_authorization_codes (
+    id                      INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    code                    VARCHAR(64)  NOT NULL UNIQUE,
+    client_id               VARCHAR(64)  NOT NULL REFERENCES oauth_clients (client_id),
+    user_id                 INTEGER      NOT NULL REFERENCES users (id),
+    redirect_uri            VARCHAR(255) NOT NULL,
+    scopes                  VARCHAR[] NOT NULL,
+    code_challenge          VARCHAR(128) NOT NULL,
+    code_challenge_method   VARCHAR(16)  NOT NULL,
+    nonce                   VARCHAR(255),
+
+    expires_at              TIMESTAMP NOT NULL,
+    consumed_at             TIMESTAMP,
+    created_at              TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE TABLE oauth_consents (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id              INTEGER     NOT NULL REFERENCES users (id),
+    client_id            VARCHAR(64) NOT NULL REFERENCES oauth_clients (client_id),
+    scopes               VARCHAR[] NOT NULL,
+
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at           TIMESTAMP NOT NULL DEFAULT now(),
+    revoked_at           TIMESTAMP,
+
+    UNIQUE (user_id, client_id)
+);
@@ -0,0 +1,11 @@
+CREATE TABLE sessions (
+    id                   VARCHAR(64) PRIMARY KEY,
+    user_id              INTEGER     NOT NULL REFERENCES users (id),
+    client_id            VARCHAR(64) REFERENCES oauth_clients (client_id),
+
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    expires_at           TIMESTAMP NOT NULL,
+    revoked_at           TIMESTAMP
+);
+
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
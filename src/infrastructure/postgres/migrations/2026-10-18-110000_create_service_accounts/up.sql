@@ -0,0 +1,13 @@
+CREATE TABLE service_accounts (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    client_id            VARCHAR(64)  NOT NULL UNIQUE,
+    client_secret        VARCHAR(255) NOT NULL,
+    name                 VARCHAR(100) NOT NULL,
+
+    scopes               VARCHAR[] NOT NULL,
+    created_by           INTEGER REFERENCES users (id),
+
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at           TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at           TIMESTAMP
+);
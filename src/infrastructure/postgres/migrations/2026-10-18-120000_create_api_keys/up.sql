@@ -0,0 +1,15 @@
+CREATE TABLE api_keys (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    prefix               VARCHAR(16)  NOT NULL UNIQUE,
+    key_hash             VARCHAR(64)  NOT NULL,
+    name                 VARCHAR(100) NOT NULL,
+
+    scopes               VARCHAR[] NOT NULL,
+    allowed_ips          VARCHAR[] NOT NULL DEFAULT '{}',
+    created_by           INTEGER REFERENCES users (id),
+
+    expires_at           TIMESTAMP,
+    last_used_at         TIMESTAMP,
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    revoked_at           TIMESTAMP
+);
@@ -0,0 +1,18 @@
+CREATE TABLE idempotency_keys (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    idempotency_key      VARCHAR(255) NOT NULL,
+    scope                VARCHAR(255) NOT NULL,
+    fingerprint          VARCHAR(64)  NOT NULL,
+
+    -- NULL ระหว่างที่ request แรกยังทำงานอยู่
+    status_code          INTEGER,
+    content_type         VARCHAR(255),
+    response_body        BYTEA,
+
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    expires_at           TIMESTAMP NOT NULL,
+
+    UNIQUE (idempotency_key, scope)
+);
+
+CREATE INDEX idempotency_keys_expires_at_idx ON idempotency_keys (expires_at);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+-- แถวที่ถูกเข้ารหัสแล้วจะ revert ไม่ได้ (ciphertext ยาวเกิน VARCHAR(32))
+ALTER TABLE users DROP COLUMN IF EXISTS citizen_id_hash;
+
+ALTER TABLE users
+    ALTER COLUMN citizen_id TYPE VARCHAR(32),
+    ALTER COLUMN phone_number TYPE VARCHAR(32);
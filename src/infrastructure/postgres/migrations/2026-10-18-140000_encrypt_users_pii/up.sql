@@ -0,0 +1,10 @@
+-- citizen_id / phone_number จะเก็บเป็น ciphertext (enc:v1:...) ซึ่งยาวเกิน VARCHAR(32)
+ALTER TABLE users
+    ALTER COLUMN citizen_id TYPE TEXT,
+    ALTER COLUMN phone_number TYPE TEXT;
+
+-- ความ unique ย้ายไปที่ blind index แต่ยังคง users_citizen_id_key ไว้กันเลขบัตรซ้ำในแถวที่ยังเป็น
+-- plaintext ส่วน ciphertext สุ่ม nonce ทุกครั้งจึงไม่ชนกัน
+
+-- NULL จนกว่า encrypt_existing_users ตอน startup จะเติมให้แถวเดิม
+ALTER TABLE users ADD COLUMN citizen_id_hash VARCHAR(64) UNIQUE;
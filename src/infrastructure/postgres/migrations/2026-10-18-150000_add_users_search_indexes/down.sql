@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS users_role_idx;
+DROP INDEX IF EXISTS users_created_at_idx;
+ALTER TABLE users DROP COLUMN IF EXISTS citizen_id_prefix_hashes;
+ALTER TABLE users DROP COLUMN IF EXISTS phone_number_hash;
@@ -0,0 +1,8 @@
+-- blind index สำหรับค้นหาเบอร์โทร (ตรงตัว) และเลขบัตรประชาชน (ตาม prefix) บนคอลัมน์ที่เข้ารหัส
+ALTER TABLE users ADD COLUMN phone_number_hash VARCHAR(64);
+ALTER TABLE users ADD COLUMN citizen_id_prefix_hashes VARCHAR[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX users_phone_number_hash_idx ON users (phone_number_hash);
+CREATE INDEX users_citizen_id_prefix_hashes_idx ON users USING GIN (citizen_id_prefix_hashes);
+CREATE INDEX users_created_at_idx ON users (created_at, id);
+CREATE INDEX users_role_idx ON users USING GIN (role);
@@ -0,0 +1,9 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- ชื่อแบบถอดเป็นอักษรโรมัน ใช้จับคู่ชื่อไทยกับชื่อที่สะกดภาษาอังกฤษ (เติมโดยแอป)
+ALTER TABLE users ADD COLUMN name_romanized TEXT NOT NULL DEFAULT '';
+
+CREATE INDEX users_first_name_trgm_idx ON users USING GIN (first_name gin_trgm_ops);
+CREATE INDEX users_last_name_trgm_idx ON users USING GIN (last_name gin_trgm_ops);
+CREATE INDEX users_full_name_trgm_idx ON users USING GIN ((first_name || ' ' || last_name) gin_trgm_ops);
+CREATE INDEX users_name_romanized_trgm_idx ON users USING GIN (name_romanized gin_trgm_ops);
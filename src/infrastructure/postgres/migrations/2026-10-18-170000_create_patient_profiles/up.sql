@@ -0,0 +1,21 @@
+CREATE TABLE patient_profiles (
+    user_id              INTEGER PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+    date_of_birth        DATE        NOT NULL,
+    sex                  VARCHAR(16) NOT NULL,
+    blood_group          VARCHAR(8),
+
+    allergies            TEXT[] NOT NULL DEFAULT '{}',
+    chronic_conditions   TEXT[] NOT NULL DEFAULT '{}',
+    -- { address_line, subdistrict, district, province, postal_code }
+    address              JSONB,
+    -- [{ name, relationship, phone_number }]
+    emergency_contacts   JSONB  NOT NULL DEFAULT '[]',
+
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at           TIMESTAMP NOT NULL DEFAULT now(),
+
+    CONSTRAINT patient_profiles_sex_check
+        CHECK (sex IN ('male', 'female', 'other', 'unknown')),
+    CONSTRAINT patient_profiles_blood_group_check
+        CHECK (blood_group IN ('A+', 'A-', 'B+', 'B-', 'AB+', 'AB-', 'O+', 'O-'))
+);
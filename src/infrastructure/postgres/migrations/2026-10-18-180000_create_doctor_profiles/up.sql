@@ -0,0 +1,21 @@
+CREATE TABLE doctor_profiles (
+    user_id              INTEGER PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+    license_number       VARCHAR(32)  NOT NULL,
+    specialties          VARCHAR[]    NOT NULL DEFAULT '{}',
+    languages            VARCHAR[]    NOT NULL DEFAULT '{}',
+    department           VARCHAR(100),
+    bio                  TEXT,
+    photo_url            VARCHAR(255),
+
+    -- NULL จนกว่า admin จะตรวจเลขใบอนุญาตแล้ว; แสดงใน directory เฉพาะที่ตรวจแล้ว
+    verified_at          TIMESTAMP,
+    verified_by          INTEGER REFERENCES users (id),
+
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at           TIMESTAMP NOT NULL DEFAULT now(),
+
+    CONSTRAINT doctor_profiles_license_number_key UNIQUE (license_number)
+);
+
+CREATE INDEX doctor_profiles_specialties_idx ON doctor_profiles USING GIN (specialties);
+CREATE INDEX doctor_profiles_department_idx ON doctor_profiles (department);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS doctor_application_transitions;
+DROP TABLE IF EXISTS doctor_applications;
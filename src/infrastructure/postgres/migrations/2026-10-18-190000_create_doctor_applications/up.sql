@@ -0,0 +1,39 @@
+CREATE TABLE doctor_applications (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id              INTEGER      NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    license_number       VARCHAR(32)  NOT NULL,
+    specialties          VARCHAR[]    NOT NULL DEFAULT '{}',
+    department           VARCHAR(100),
+    -- [{ kind, file_name, url }]
+    documents            JSONB        NOT NULL DEFAULT '[]',
+
+    status               VARCHAR(16)  NOT NULL DEFAULT 'pending',
+    review_notes         TEXT,
+    reviewed_by          INTEGER REFERENCES users (id),
+    reviewed_at          TIMESTAMP,
+
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at           TIMESTAMP NOT NULL DEFAULT now(),
+
+    CONSTRAINT doctor_applications_status_check
+        CHECK (status IN ('pending', 'approved', 'rejected', 'revoked'))
+);
+
+-- ยื่นใหม่ได้หลังถูก reject/revoke แต่มีคำขอที่ยังเปิดอยู่ได้ทีละหนึ่ง
+CREATE UNIQUE INDEX doctor_applications_user_open_key
+    ON doctor_applications (user_id)
+    WHERE status IN ('pending', 'approved');
+CREATE INDEX doctor_applications_status_idx ON doctor_applications (status, created_at);
+
+CREATE TABLE doctor_application_transitions (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    application_id       INTEGER     NOT NULL REFERENCES doctor_applications (id) ON DELETE CASCADE,
+    from_status          VARCHAR(16),
+    to_status            VARCHAR(16) NOT NULL,
+    actor_id             INTEGER     NOT NULL REFERENCES users (id),
+    notes                TEXT,
+    created_at           TIMESTAMP   NOT NULL DEFAULT now()
+);
+
+CREATE INDEX doctor_application_transitions_application_id_idx
+    ON doctor_application_transitions (application_id, created_at);
@@ -0,0 +1,19 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE users ADD COLUMN role VARCHAR[] NOT NULL DEFAULT '{}';
+
+UPDATE users
+SET role = ARRAY(
+    SELECT roles.name
+    FROM user_roles
+    JOIN roles ON roles.id = user_roles.role_id
+    WHERE user_roles.user_id = users.id
+    ORDER BY roles.id
+);
+
+ALTER TABLE users ALTER COLUMN role DROP DEFAULT;
+CREATE INDEX users_role_idx ON users USING GIN (role);
+
+DROP TABLE IF EXISTS user_roles;
+DROP TABLE IF EXISTS role_permissions;
+DROP TABLE IF EXISTS permissions;
+DROP TABLE IF EXISTS roles;
@@ -0,0 +1,52 @@
+CREATE TABLE roles (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name                 VARCHAR(50) NOT NULL UNIQUE,
+    description          TEXT,
+    created_at           TIMESTAMP   NOT NULL DEFAULT now()
+);
+
+CREATE TABLE permissions (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    -- <resource>.<action> เช่น patient.read
+    name                 VARCHAR(100) NOT NULL UNIQUE,
+    description          TEXT,
+    created_at           TIMESTAMP    NOT NULL DEFAULT now()
+);
+
+CREATE TABLE role_permissions (
+    role_id              INTEGER NOT NULL REFERENCES roles (id) ON DELETE CASCADE,
+    permission_id        INTEGER NOT NULL REFERENCES permissions (id) ON DELETE CASCADE,
+    PRIMARY KEY (role_id, permission_id)
+);
+
+CREATE TABLE user_roles (
+    user_id              INTEGER   NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    role_id              INTEGER   NOT NULL REFERENCES roles (id) ON DELETE CASCADE,
+    -- NULL = ระบบให้เอง (ตอนสมัคร หรือย้ายข้อมูลจากคอลัมน์เดิม)
+    granted_by           INTEGER REFERENCES users (id),
+    granted_at           TIMESTAMP NOT NULL DEFAULT now(),
+    -- NULL = ไม่หมดอายุ
+    expires_at           TIMESTAMP,
+    PRIMARY KEY (user_id, role_id)
+);
+
+CREATE INDEX user_roles_role_id_idx ON user_roles (role_id);
+
+INSERT INTO roles (name, description) VALUES
+    ('Patient', 'Registered patient'),
+    ('Doctor', 'Verified doctor'),
+    ('Admin', 'System administrator');
+
+-- ย้าย role เดิมจากอาเรย์ ค่าที่ไม่รู้จักถูกเก็บเป็น role ใหม่เพื่อไม่ให้ข้อมูลหาย
+INSERT INTO roles (name)
+SELECT DISTINCT unnest(role) FROM users
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO user_roles (user_id, role_id, granted_at)
+SELECT DISTINCT ON (users.id, roles.id) users.id, roles.id, users.created_at
+FROM users
+CROSS JOIN LATERAL unnest(users.role) AS user_role (name)
+JOIN roles ON roles.name = user_role.name;
+
+DROP INDEX IF EXISTS users_role_idx;
+ALTER TABLE users DROP COLUMN role;
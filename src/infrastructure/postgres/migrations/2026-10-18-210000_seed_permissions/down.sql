@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM permissions WHERE name IN (
+    'user.read',
+    'patient.read',
+    'patient.update',
+    'doctor.read',
+    'doctor.verify',
+    'doctor_application.review',
+    'role.assign',
+    'policy.explain'
+);
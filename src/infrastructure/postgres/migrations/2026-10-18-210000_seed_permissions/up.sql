@@ -0,0 +1,21 @@
+INSERT INTO permissions (name, description) VALUES
+    ('user.read', 'Read user accounts'),
+    ('patient.read', 'Read patient profiles'),
+    ('patient.update', 'Update patient profiles'),
+    ('doctor.read', 'Read full doctor profiles, including license numbers'),
+    ('doctor.verify', 'Verify or unverify doctor licenses'),
+    ('doctor_application.review', 'Review doctor onboarding applications'),
+    ('role.assign', 'Grant and revoke roles'),
+    ('policy.explain', 'Explain authorization decisions')
+ON CONFLICT (name) DO NOTHING;
+
+-- Patient ไม่ต้องมี permission เพราะเข้าถึงข้อมูลของตัวเองผ่านกฎ owner
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT roles.id, permissions.id
+FROM roles
+JOIN permissions ON
+    (roles.name = 'Admin')
+    OR (roles.name = 'Doctor' AND permissions.name IN (
+        'user.read', 'patient.read', 'patient.update', 'doctor.read'
+    ))
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS audit_logs;
+
+DELETE FROM role_permissions
+WHERE role_id IN (SELECT id FROM roles WHERE name = 'Admin')
+    AND permission_id IN (SELECT id FROM permissions WHERE name = 'patient.register');
+
+DELETE FROM permissions WHERE name = 'patient.register';
+
+-- user_roles และ role_permissions ของ role เหล่านี้ถูกลบตามด้วย ON DELETE CASCADE
+DELETE FROM roles WHERE name IN ('Nurse', 'Pharmacist', 'Receptionist');
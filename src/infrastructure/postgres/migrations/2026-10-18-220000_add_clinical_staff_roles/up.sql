@@ -0,0 +1,39 @@
+INSERT INTO roles (name, description) VALUES
+    ('Nurse', 'Nursing staff'),
+    ('Pharmacist', 'Pharmacy staff'),
+    ('Receptionist', 'Front desk staff who register patients')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO permissions (name, description) VALUES
+    ('patient.register', 'Register patients on their behalf')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT roles.id, permissions.id
+FROM roles
+JOIN permissions ON
+    (roles.name = 'Admin' AND permissions.name = 'patient.register')
+    OR (roles.name = 'Nurse' AND permissions.name IN (
+        'user.read', 'patient.read', 'patient.update'
+    ))
+    OR (roles.name = 'Pharmacist' AND permissions.name IN (
+        'user.read', 'patient.read', 'doctor.read'
+    ))
+    OR (roles.name = 'Receptionist' AND permissions.name IN (
+        'user.read', 'patient.register'
+    ))
+ON CONFLICT DO NOTHING;
+
+CREATE TABLE audit_logs (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    -- NULL = ระบบทำเอง
+    actor_id             INTEGER REFERENCES users (id),
+    -- <resource>.<event> เช่น user.registered_on_behalf ไม่ใส่ CHECK เพราะเพิ่มชนิดได้เรื่อยๆ
+    action               VARCHAR(100) NOT NULL,
+    subject_user_id      INTEGER REFERENCES users (id) ON DELETE SET NULL,
+    details              JSONB        NOT NULL DEFAULT '{}',
+    created_at           TIMESTAMP    NOT NULL DEFAULT now()
+);
+
+CREATE INDEX audit_logs_subject_user_id_idx ON audit_logs (subject_user_id, created_at);
+CREATE INDEX audit_logs_actor_id_idx ON audit_logs (actor_id, created_at);
@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS notifications;
+
+DROP INDEX IF EXISTS user_roles_valid_until_idx;
+ALTER TABLE user_roles DROP CONSTRAINT IF EXISTS user_roles_validity_check;
+ALTER TABLE user_roles DROP COLUMN IF EXISTS expired_at;
+ALTER TABLE user_roles DROP COLUMN IF EXISTS expiry_notified_at;
+ALTER TABLE user_roles DROP COLUMN IF EXISTS valid_from;
+
+ALTER TABLE user_roles RENAME COLUMN valid_until TO expires_at;
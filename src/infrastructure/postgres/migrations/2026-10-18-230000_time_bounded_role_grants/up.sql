@@ -0,0 +1,26 @@
+ALTER TABLE user_roles RENAME COLUMN expires_at TO valid_until;
+
+-- NULL = มีผลทันที
+ALTER TABLE user_roles ADD COLUMN valid_from TIMESTAMP;
+-- ตั้งโดย background task: แจ้ง admin ล่วงหน้าแล้ว / หมดอายุและ revoke session แล้ว
+ALTER TABLE user_roles ADD COLUMN expiry_notified_at TIMESTAMP;
+ALTER TABLE user_roles ADD COLUMN expired_at TIMESTAMP;
+
+ALTER TABLE user_roles ADD CONSTRAINT user_roles_validity_check
+    CHECK (valid_from IS NULL OR valid_until IS NULL OR valid_from < valid_until);
+
+CREATE INDEX user_roles_valid_until_idx ON user_roles (valid_until)
+    WHERE valid_until IS NOT NULL AND expired_at IS NULL;
+
+CREATE TABLE notifications (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    recipient_id         INTEGER      NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    -- <resource>.<event> เช่น role_grant.expiring ไม่ใส่ CHECK เพราะเพิ่มชนิดได้เรื่อยๆ
+    kind                 VARCHAR(100) NOT NULL,
+    subject_user_id      INTEGER REFERENCES users (id) ON DELETE SET NULL,
+    payload              JSONB        NOT NULL DEFAULT '{}',
+    created_at           TIMESTAMP    NOT NULL DEFAULT now(),
+    read_at              TIMESTAMP
+);
+
+CREATE INDEX notifications_recipient_id_idx ON notifications (recipient_id, created_at);
@@ -0,0 +1,32 @@
+-- This file should undo anything in `up.sql`
+-- API key, service account และ client ของโรงพยาบาลอื่นถูกปิด เพื่อไม่ให้กลายเป็นใช้ได้ทุกที่
+UPDATE api_keys SET revoked_at = now()
+WHERE revoked_at IS NULL AND hospital_id <> (SELECT id FROM hospitals WHERE code = 'main');
+ALTER TABLE api_keys DROP COLUMN hospital_id;
+UPDATE service_accounts SET deleted_at = now(), updated_at = now()
+WHERE deleted_at IS NULL AND hospital_id <> (SELECT id FROM hospitals WHERE code = 'main');
+ALTER TABLE service_accounts DROP COLUMN hospital_id;
+UPDATE oauth_clients SET deleted_at = now(), updated_at = now()
+WHERE deleted_at IS NULL AND hospital_id <> (SELECT id FROM hospitals WHERE code = 'main');
+ALTER TABLE oauth_clients DROP COLUMN hospital_id;
+
+-- role และใบสมัครของโรงพยาบาลอื่นนอกจาก main หายไปตอนย้อนกลับ
+DELETE FROM doctor_applications
+WHERE hospital_id <> (SELECT id FROM hospitals WHERE code = 'main');
+DROP INDEX doctor_applications_status_idx;
+CREATE INDEX doctor_applications_status_idx ON doctor_applications (status, created_at);
+DROP INDEX doctor_applications_user_open_key;
+CREATE UNIQUE INDEX doctor_applications_user_open_key
+    ON doctor_applications (user_id)
+    WHERE status IN ('pending', 'approved');
+ALTER TABLE doctor_applications DROP COLUMN hospital_id;
+
+DELETE FROM user_roles WHERE hospital_id <> (SELECT id FROM hospitals WHERE code = 'main');
+DROP INDEX user_roles_user_id_idx;
+ALTER TABLE user_roles DROP CONSTRAINT user_roles_membership_fkey;
+ALTER TABLE user_roles DROP CONSTRAINT user_roles_pkey;
+ALTER TABLE user_roles ADD PRIMARY KEY (user_id, role_id);
+ALTER TABLE user_roles DROP COLUMN hospital_id;
+
+DROP TABLE hospital_memberships;
+DROP TABLE hospitals;
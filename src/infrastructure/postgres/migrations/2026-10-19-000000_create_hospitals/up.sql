@@ -0,0 +1,68 @@
+CREATE TABLE hospitals (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    -- ใช้เป็น subdomain และค่าใน header X-Hospital
+    code                 VARCHAR(32)  NOT NULL UNIQUE,
+    name                 VARCHAR(200) NOT NULL,
+    -- hospital number ถัดไปของโรงพยาบาลนี้ จองด้วย UPDATE ... RETURNING ใน transaction
+    next_hospital_number INTEGER      NOT NULL DEFAULT 1,
+    created_at           TIMESTAMP    NOT NULL DEFAULT now(),
+    updated_at           TIMESTAMP    NOT NULL DEFAULT now(),
+
+    CONSTRAINT hospitals_code_check CHECK (code ~ '^[a-z0-9]([a-z0-9-]*[a-z0-9])?$')
+);
+
+-- โรงพยาบาลเดิมก่อนมี tenancy; hospital number เดิมคือ users.id จึงเริ่มนับต่อจากค่าสูงสุด
+INSERT INTO hospitals (code, name, next_hospital_number)
+SELECT 'main', 'Main hospital', COALESCE(MAX(id), 0) + 1 FROM users;
+
+CREATE TABLE hospital_memberships (
+    hospital_id          INTEGER   NOT NULL REFERENCES hospitals (id) ON DELETE CASCADE,
+    user_id              INTEGER   NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    hospital_number      INTEGER   NOT NULL,
+    created_at           TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (hospital_id, user_id),
+    CONSTRAINT hospital_memberships_hospital_number_key UNIQUE (hospital_id, hospital_number)
+);
+
+CREATE INDEX hospital_memberships_user_id_idx ON hospital_memberships (user_id);
+
+INSERT INTO hospital_memberships (hospital_id, user_id, hospital_number, created_at)
+SELECT hospitals.id, users.id, users.id, users.created_at
+FROM users CROSS JOIN hospitals
+WHERE hospitals.code = 'main';
+
+-- role มีผลเฉพาะโรงพยาบาลที่ให้ และต้องเป็นสมาชิกของโรงพยาบาลนั้นก่อน
+ALTER TABLE user_roles ADD COLUMN hospital_id INTEGER;
+UPDATE user_roles SET hospital_id = (SELECT id FROM hospitals WHERE code = 'main');
+ALTER TABLE user_roles ALTER COLUMN hospital_id SET NOT NULL;
+ALTER TABLE user_roles DROP CONSTRAINT user_roles_pkey;
+ALTER TABLE user_roles ADD PRIMARY KEY (hospital_id, user_id, role_id);
+ALTER TABLE user_roles ADD CONSTRAINT user_roles_membership_fkey
+    FOREIGN KEY (hospital_id, user_id) REFERENCES hospital_memberships (hospital_id, user_id)
+    ON DELETE CASCADE;
+CREATE INDEX user_roles_user_id_idx ON user_roles (user_id);
+
+ALTER TABLE doctor_applications ADD COLUMN hospital_id INTEGER REFERENCES hospitals (id);
+UPDATE doctor_applications SET hospital_id = (SELECT id FROM hospitals WHERE code = 'main');
+ALTER TABLE doctor_applications ALTER COLUMN hospital_id SET NOT NULL;
+
+DROP INDEX doctor_applications_user_open_key;
+CREATE UNIQUE INDEX doctor_applications_user_open_key
+    ON doctor_applications (hospital_id, user_id)
+    WHERE status IN ('pending', 'approved');
+DROP INDEX doctor_applications_status_idx;
+CREATE INDEX doctor_applications_status_idx
+    ON doctor_applications (hospital_id, status, created_at);
+
+-- API key, service account และ OAuth client ใช้ได้เฉพาะโรงพยาบาลที่สร้าง
+ALTER TABLE api_keys ADD COLUMN hospital_id INTEGER REFERENCES hospitals (id);
+UPDATE api_keys SET hospital_id = (SELECT id FROM hospitals WHERE code = 'main');
+ALTER TABLE api_keys ALTER COLUMN hospital_id SET NOT NULL;
+
+ALTER TABLE service_accounts ADD COLUMN hospital_id INTEGER REFERENCES hospitals (id);
+UPDATE service_accounts SET hospital_id = (SELECT id FROM hospitals WHERE code = 'main');
+ALTER TABLE service_accounts ALTER COLUMN hospital_id SET NOT NULL;
+
+ALTER TABLE oauth_clients ADD COLUMN hospital_id INTEGER REFERENCES hospitals (id);
+UPDATE oauth_clients SET hospital_id = (SELECT id FROM hospitals WHERE code = 'main');
+ALTER TABLE oauth_clients ALTER COLUMN hospital_id SET NOT NULL;
@@ -0,0 +1,21 @@
+-- This file should undo anything in `up.sql`
+-- HN แบบเดิมเป็นตัวเลขล้วน ใช้ users.id แทนเพื่อไม่ให้ชนกันข้ามปี
+ALTER TABLE hospitals ADD COLUMN next_hospital_number INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE hospital_memberships
+    ALTER COLUMN hospital_number TYPE INTEGER USING user_id;
+
+UPDATE hospitals
+SET next_hospital_number = COALESCE((
+    SELECT MAX(hospital_number) FROM hospital_memberships
+    WHERE hospital_memberships.hospital_id = hospitals.id
+), 0) + 1;
+
+DROP TABLE hospital_number_sequences;
+
+ALTER TABLE hospitals
+    DROP CONSTRAINT hospitals_hn_sequence_digits_check,
+    DROP CONSTRAINT hospitals_hn_prefix_check,
+    DROP COLUMN hn_buddhist_year,
+    DROP COLUMN hn_sequence_digits,
+    DROP COLUMN hn_prefix;
@@ -0,0 +1,52 @@
+-- รูปแบบ HN ของแต่ละโรงพยาบาล: prefix + ปี 2 หลัก + ลำดับเติม 0 + check character
+-- (mod 11 น้ำหนัก 2 ถึง 10 วนจากขวา ค่า 10 เขียนเป็น X)
+ALTER TABLE hospitals
+    ADD COLUMN hn_prefix           VARCHAR(8) NOT NULL DEFAULT '',
+    ADD COLUMN hn_sequence_digits  SMALLINT   NOT NULL DEFAULT 6,
+    ADD COLUMN hn_buddhist_year    BOOLEAN    NOT NULL DEFAULT TRUE,
+    ADD CONSTRAINT hospitals_hn_prefix_check CHECK (hn_prefix ~ '^[A-Z]*$'),
+    ADD CONSTRAINT hospitals_hn_sequence_digits_check CHECK (hn_sequence_digits BETWEEN 3 AND 9);
+
+-- ลำดับเริ่มใหม่ทุกปี แถวถูก lock ตอนจองเลขจนจบ transaction ของการลงทะเบียน
+CREATE TABLE hospital_number_sequences (
+    hospital_id INTEGER  NOT NULL REFERENCES hospitals (id) ON DELETE CASCADE,
+    year_code   SMALLINT NOT NULL,
+    last_value  INTEGER  NOT NULL,
+    PRIMARY KEY (hospital_id, year_code)
+);
+
+-- HN เดิมคือ users.id ใช้เป็นลำดับของปีที่ลงทะเบียน
+ALTER TABLE hospital_memberships
+    ALTER COLUMN hospital_number TYPE VARCHAR(32) USING hospital_number::text;
+
+INSERT INTO hospital_number_sequences (hospital_id, year_code, last_value)
+SELECT memberships.hospital_id,
+    ((EXTRACT(YEAR FROM memberships.created_at)::int
+        + CASE WHEN hospitals.hn_buddhist_year THEN 543 ELSE 0 END) % 100)::smallint,
+    MAX(memberships.hospital_number::int)
+FROM hospital_memberships AS memberships
+JOIN hospitals ON hospitals.id = memberships.hospital_id
+GROUP BY 1, 2;
+
+WITH bodies AS (
+    SELECT memberships.hospital_id, memberships.user_id, hospitals.hn_prefix,
+        lpad(((EXTRACT(YEAR FROM memberships.created_at)::int
+            + CASE WHEN hospitals.hn_buddhist_year THEN 543 ELSE 0 END) % 100)::text, 2, '0')
+        || lpad(
+            memberships.hospital_number,
+            GREATEST(hospitals.hn_sequence_digits, length(memberships.hospital_number)),
+            '0'
+        ) AS body
+    FROM hospital_memberships AS memberships
+    JOIN hospitals ON hospitals.id = memberships.hospital_id
+)
+UPDATE hospital_memberships AS memberships
+SET hospital_number = bodies.hn_prefix || bodies.body || substr('0123456789X', (11 - (
+        SELECT SUM(substr(bodies.body, i, 1)::int * (2 + (length(bodies.body) - i) % 9))
+        FROM generate_series(1, length(bodies.body)) AS i
+    ) % 11) % 11 + 1, 1)
+FROM bodies
+WHERE memberships.hospital_id = bodies.hospital_id
+    AND memberships.user_id = bodies.user_id;
+
+ALTER TABLE hospitals DROP COLUMN next_hospital_number;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+-- role_permissions ถูกลบตามด้วย ON DELETE CASCADE
+DELETE FROM permissions WHERE name = 'guardianship.manage';
+
+DROP TABLE IF EXISTS guardianships;
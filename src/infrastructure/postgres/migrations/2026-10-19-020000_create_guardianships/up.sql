@@ -0,0 +1,33 @@
+CREATE TABLE guardianships (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    guardian_id          INTEGER      NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    dependent_id         INTEGER      NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    relationship         VARCHAR(20)  NOT NULL CHECK (relationship IN (
+        'parent', 'legal_guardian', 'spouse', 'child', 'relative', 'caregiver'
+    )),
+    -- หลักฐานความยินยอม เช่น หนังสือมอบอำนาจ สูติบัตร คำสั่งศาล
+    consent_evidence     JSONB        NOT NULL,
+    -- NULL = ไม่มีวันหมดอายุ
+    valid_until          TIMESTAMP,
+    created_by           INTEGER      NOT NULL REFERENCES users (id),
+    created_at           TIMESTAMP    NOT NULL DEFAULT now(),
+    revoked_by           INTEGER      REFERENCES users (id),
+    revoked_at           TIMESTAMP,
+    CHECK (guardian_id <> dependent_id)
+);
+
+-- ความสัมพันธ์ที่ยังไม่ถูกเพิกถอนมีได้คู่ละหนึ่ง
+CREATE UNIQUE INDEX guardianships_active_key ON guardianships (guardian_id, dependent_id)
+    WHERE revoked_at IS NULL;
+CREATE INDEX guardianships_dependent_id_idx ON guardianships (dependent_id);
+
+INSERT INTO permissions (name, description) VALUES
+    ('guardianship.manage', 'Record and revoke guardian-dependent relationships')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT roles.id, permissions.id
+FROM roles
+JOIN permissions ON permissions.name = 'guardianship.manage'
+WHERE roles.name IN ('Admin', 'Receptionist')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,17 @@
+-- This file should undo anything in `up.sql`
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT roles.id, permissions.id
+FROM roles
+JOIN permissions ON roles.name = 'Doctor' AND permissions.name IN ('patient.read', 'patient.update')
+ON CONFLICT DO NOTHING;
+
+-- role_permissions ถูกลบตามด้วย ON DELETE CASCADE
+DELETE FROM permissions
+WHERE name IN ('care_team.manage', 'emergency_access.invoke', 'emergency_access.review');
+
+DROP TABLE IF EXISTS care_assignments;
+DROP TABLE IF EXISTS emergency_accesses;
+
+DROP INDEX IF EXISTS audit_logs_high_priority_idx;
+ALTER TABLE notifications DROP COLUMN IF EXISTS priority;
+ALTER TABLE audit_logs DROP COLUMN IF EXISTS priority;
@@ -0,0 +1,72 @@
+-- high = ต้องมีคนตรวจทาน เช่น break-the-glass
+ALTER TABLE audit_logs ADD COLUMN priority VARCHAR(8) NOT NULL DEFAULT 'normal'
+    CHECK (priority IN ('normal', 'high'));
+ALTER TABLE notifications ADD COLUMN priority VARCHAR(8) NOT NULL DEFAULT 'normal'
+    CHECK (priority IN ('normal', 'high'));
+
+CREATE INDEX audit_logs_high_priority_idx ON audit_logs (created_at) WHERE priority = 'high';
+
+CREATE TABLE emergency_accesses (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    hospital_id          INTEGER      NOT NULL REFERENCES hospitals (id) ON DELETE CASCADE,
+    -- ผู้ที่ break the glass
+    user_id              INTEGER      NOT NULL REFERENCES users (id),
+    patient_id           INTEGER      NOT NULL REFERENCES users (id),
+    reason               TEXT         NOT NULL,
+    created_at           TIMESTAMP    NOT NULL DEFAULT now(),
+    expires_at           TIMESTAMP    NOT NULL,
+    reviewed_by          INTEGER      REFERENCES users (id),
+    reviewed_at          TIMESTAMP,
+    review_notes         TEXT,
+    CHECK (created_at < expires_at)
+);
+
+CREATE INDEX emergency_accesses_user_id_idx ON emergency_accesses (hospital_id, user_id, expires_at);
+CREATE INDEX emergency_accesses_created_at_idx ON emergency_accesses (hospital_id, created_at);
+
+INSERT INTO permissions (name, description) VALUES
+    ('emergency_access.invoke', 'Break the glass for time-limited access to a patient record'),
+    ('emergency_access.review', 'Review break-the-glass uses')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT roles.id, permissions.id
+FROM roles
+JOIN permissions ON
+    (roles.name = 'Doctor' AND permissions.name = 'emergency_access.invoke')
+    OR (roles.name = 'Admin' AND permissions.name = 'emergency_access.review')
+ON CONFLICT DO NOTHING;
+
+-- แพทย์อ่าน/แก้ข้อมูลผู้ป่วยได้เฉพาะคนที่ตนดูแลอยู่ ผู้ป่วยนอกทีมต้อง break the glass
+CREATE TABLE care_assignments (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    hospital_id          INTEGER      NOT NULL REFERENCES hospitals (id) ON DELETE CASCADE,
+    doctor_id            INTEGER      NOT NULL REFERENCES users (id),
+    patient_id           INTEGER      NOT NULL REFERENCES users (id),
+    assigned_by          INTEGER      REFERENCES users (id),
+    created_at           TIMESTAMP    NOT NULL DEFAULT now(),
+    ended_by             INTEGER      REFERENCES users (id),
+    ended_at             TIMESTAMP,
+    CHECK (doctor_id <> patient_id)
+);
+
+CREATE UNIQUE INDEX care_assignments_active_idx ON care_assignments (hospital_id, doctor_id, patient_id)
+    WHERE ended_at IS NULL;
+CREATE INDEX care_assignments_patient_id_idx ON care_assignments (hospital_id, patient_id);
+
+INSERT INTO permissions (name, description) VALUES
+    ('care_team.manage', 'Assign doctors to the patients they care for')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT roles.id, permissions.id
+FROM roles
+JOIN permissions ON roles.name = 'Admin' AND permissions.name = 'care_team.manage'
+ON CONFLICT DO NOTHING;
+
+DELETE FROM role_permissions
+USING roles, permissions
+WHERE role_permissions.role_id = roles.id
+    AND role_permissions.permission_id = permissions.id
+    AND roles.name = 'Doctor'
+    AND permissions.name IN ('patient.read', 'patient.update');
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+-- role_permissions ถูกลบตามด้วย ON DELETE CASCADE
+DELETE FROM permissions WHERE name = 'consent.manage';
+
+DROP TABLE IF EXISTS consent_records;
+DROP TABLE IF EXISTS consent_versions;
+DROP TABLE IF EXISTS consent_purposes;
@@ -0,0 +1,46 @@
+CREATE TABLE consent_purposes (
+    id                       INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    code                     VARCHAR(64)  NOT NULL UNIQUE,
+    name                     VARCHAR(255) NOT NULL,
+    description              TEXT         NOT NULL DEFAULT '',
+    -- ต้องยอมรับฉบับปัจจุบันตอนลงทะเบียน เช่น ข้อกำหนดการใช้บริการ
+    required_at_registration BOOLEAN      NOT NULL DEFAULT FALSE,
+    created_at               TIMESTAMP    NOT NULL DEFAULT now()
+);
+
+-- ฉบับที่มีผลล่าสุด (effective_from <= now) ของแต่ละ purpose คือฉบับปัจจุบัน
+CREATE TABLE consent_versions (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    purpose_id           INTEGER      NOT NULL REFERENCES consent_purposes (id) ON DELETE CASCADE,
+    version              INTEGER      NOT NULL,
+    text                 TEXT         NOT NULL,
+    effective_from       TIMESTAMP    NOT NULL,
+    created_by           INTEGER      REFERENCES users (id),
+    created_at           TIMESTAMP    NOT NULL DEFAULT now(),
+    UNIQUE (purpose_id, version)
+);
+
+-- append-only: สถานะปัจจุบันคือ record ล่าสุดของ user ต่อ purpose
+CREATE TABLE consent_records (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id              INTEGER      NOT NULL REFERENCES users (id),
+    consent_version_id   INTEGER      NOT NULL REFERENCES consent_versions (id),
+    action               VARCHAR(16)  NOT NULL CHECK (action IN ('granted', 'withdrawn')),
+    channel              VARCHAR(16)  NOT NULL
+        CHECK (channel IN ('web', 'mobile', 'in_person', 'paper')),
+    -- เจ้าหน้าที่ที่บันทึกแทน เช่น ตอนลงทะเบียนที่เคาน์เตอร์
+    recorded_by          INTEGER      REFERENCES users (id),
+    created_at           TIMESTAMP    NOT NULL DEFAULT now()
+);
+
+CREATE INDEX consent_records_user_id_idx ON consent_records (user_id, id);
+
+INSERT INTO permissions (name, description) VALUES
+    ('consent.manage', 'Publish consent texts and view the consent report')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT roles.id, permissions.id
+FROM roles
+JOIN permissions ON roles.name = 'Admin' AND permissions.name = 'consent.manage'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,21 @@
+CREATE TABLE data_exports (
+    -- token แบบสุ่ม ใช้เป็นส่วนหนึ่งของลิงก์ดาวน์โหลด
+    id                   VARCHAR(64)  PRIMARY KEY,
+    user_id              INTEGER      NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    format               VARCHAR(8)   NOT NULL CHECK (format IN ('json', 'zip')),
+    status               VARCHAR(16)  NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'processing', 'ready', 'failed')),
+    -- เข้ารหัสด้วย field_encryption
+    content              BYTEA,
+    error                TEXT,
+    created_at           TIMESTAMP    NOT NULL DEFAULT now(),
+    -- เวลาที่ worker เริ่มสร้าง ถ้าค้างนานเกิน lease (เช่น worker ล่ม) จะถูกหยิบไปสร้างใหม่
+    claimed_at           TIMESTAMP,
+    completed_at         TIMESTAMP,
+    -- ลบทิ้งเมื่อเลยเวลานี้
+    expires_at           TIMESTAMP    NOT NULL
+);
+
+CREATE INDEX data_exports_pending_idx ON data_exports (created_at) WHERE status = 'pending';
+CREATE INDEX data_exports_processing_idx ON data_exports (claimed_at) WHERE status = 'processing';
+CREATE INDEX data_exports_expires_at_idx ON data_exports (expires_at);
@@ -0,0 +1,24 @@
+-- ทะเบียนการลบข้อมูลส่วนบุคคลตามคำขอ แถวใน users ยังคงอยู่เพื่อ foreign key และเวชระเบียน
+CREATE TABLE erasures (
+    id                   INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id              INTEGER      NOT NULL UNIQUE REFERENCES users (id),
+    hospital_id          INTEGER      NOT NULL REFERENCES hospitals (id),
+    legal_basis          VARCHAR(32)  NOT NULL
+        CHECK (legal_basis IN ('consent_withdrawn', 'no_longer_necessary', 'objection',
+                               'unlawful_processing', 'court_order')),
+    notes                TEXT         NOT NULL DEFAULT '',
+    erased_by            INTEGER      REFERENCES users (id),
+    created_at           TIMESTAMP    NOT NULL DEFAULT now()
+);
+
+CREATE INDEX erasures_hospital_id_idx ON erasures (hospital_id, created_at);
+
+INSERT INTO permissions (name, description) VALUES
+    ('user.erase', 'Erase a user''s personal data on a right-to-erasure request')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT roles.id, permissions.id
+FROM roles
+JOIN permissions ON roles.name = 'Admin' AND permissions.name = 'user.erase'
+ON CONFLICT DO NOTHING;
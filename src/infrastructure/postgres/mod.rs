@@ -1,4 +1,11 @@
+pub mod audit_logs;
+pub mod consents;
+pub mod errors;
+pub mod hospital_memberships;
+pub mod name_romanization;
+pub mod pii_encryption;
 pub mod postgres_connection;
 pub mod postgres_migration;
 pub mod repositories;
+pub mod role_grants;
 pub mod schema;
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, QueryDsl, dsl::not};
+use diesel_async::RunQueryDsl;
+use tracing::info;
+
+use crate::infrastructure::{
+    postgres::{
+        postgres_connection::PgPoolSquad,
+        schema::{erasures, users},
+    },
+    transliteration,
+};
+
+const BATCH_SIZE: i64 = 500;
+
+/// Fills `name_romanized` for rows registered before fuzzy search. Only rows with an empty
+/// value are touched, so it is safe to re-run. Deleted and erased users are skipped: erasure
+/// clears `name_romanized` on purpose. Runs at startup before the server accepts requests.
+pub async fn romanize_existing_users(db_pool: &PgPoolSquad) -> Result<usize> {
+    let mut conn = db_pool.get().await?;
+    let mut romanized = 0;
+    let mut last_id = 0;
+
+    loop {
+        // เดินตาม id เพราะชื่อที่ romanize ไม่ได้จะยังเป็นค่าว่างอยู่
+        let rows: Vec<(i32, String, String)> = users::table
+            .filter(users::name_romanized.eq(""))
+            .filter(users::deleted_at.is_null())
+            .filter(not(
+                users::id.eq_any(erasures::table.select(erasures::user_id))
+            ))
+            .filter(users::id.gt(last_id))
+            .order(users::id.asc())
+            .limit(BATCH_SIZE)
+            .select((users::id, users::first_name, users::last_name))
+            .load(&mut conn)
+            .await?;
+
+        let Some((id, _, _)) = rows.last() else {
+            break;
+        };
+        last_id = *id;
+
+        for (id, first_name, last_name) in rows {
+            diesel::update(users::table.find(id))
+                .set(users::name_romanized.eq(transliteration::romanize(&format!(
+                    "{} {}",
+                    first_name, last_name
+                ))))
+                .execute(&mut conn)
+                .await?;
+
+            romanized += 1;
+        }
+
+        info!("Romanized {} users so far", romanized);
+    }
+
+    Ok(romanized)
+}
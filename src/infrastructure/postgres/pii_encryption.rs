@@ -0,0 +1,92 @@
+use anyhow::Result;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, QueryDsl,
+    dsl::not,
+    result::{DatabaseErrorKind, Error},
+};
+use diesel_async::RunQueryDsl;
+use tracing::{info, warn};
+
+use crate::{
+    domain::value_objects::validation::{normalize_citizen_id, normalize_phone_number},
+    infrastructure::{
+        field_encryption,
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            schema::{erasures, users},
+        },
+    },
+};
+
+const BATCH_SIZE: i64 = 500;
+
+/// Encrypts `citizen_id` / `phone_number` of rows written before column encryption and fills
+/// in the blind indexes. Only rows missing a blind index are touched, so it is safe to re-run.
+/// Deleted and erased users are skipped: erasure clears their blind indexes on purpose.
+///
+/// Runs at startup before the server accepts requests. Rows whose normalized citizen ID or phone
+/// number collides with another user are logged and left as they are for manual resolution.
+pub async fn encrypt_existing_users(db_pool: &PgPoolSquad) -> Result<usize> {
+    let mut conn = db_pool.get().await?;
+    let mut encrypted = 0;
+    let mut last_id = 0;
+
+    loop {
+        // เดินตาม id เพราะแถวที่ชนกับ user อื่นจะยังไม่มี blind index
+        let rows: Vec<(i32, String, String)> = users::table
+            .filter(
+                users::citizen_id_hash
+                    .is_null()
+                    .or(users::phone_number_hash.is_null()),
+            )
+            .filter(users::deleted_at.is_null())
+            .filter(not(
+                users::id.eq_any(erasures::table.select(erasures::user_id))
+            ))
+            .filter(users::id.gt(last_id))
+            .order(users::id.asc())
+            .limit(BATCH_SIZE)
+            .select((users::id, users::citizen_id, users::phone_number))
+            .load(&mut conn)
+            .await?;
+
+        let Some((id, _, _)) = rows.last() else {
+            break;
+        };
+        last_id = *id;
+
+        for (id, citizen_id, phone_number) in rows {
+            // decrypt ไม่แตะค่าที่ยังเป็น plaintext จึงรันซ้ำหลังหยุดกลางทางได้
+            let citizen_id = normalize_citizen_id(&field_encryption::decrypt(&citizen_id)?);
+            let phone_number = field_encryption::decrypt(&phone_number)?;
+            // แถวเก่าก่อนมี validation อาจไม่ใช่ E.164 ทำให้ค้นด้วย blind index ไม่เจอ
+            let phone_number = normalize_phone_number(&phone_number).unwrap_or(phone_number);
+
+            let result = diesel::update(users::table.find(id))
+                .set((
+                    users::citizen_id.eq(field_encryption::encrypt(&citizen_id)?),
+                    users::phone_number.eq(field_encryption::encrypt(&phone_number)?),
+                    users::citizen_id_hash.eq(field_encryption::blind_index(&citizen_id)?),
+                    users::citizen_id_prefix_hashes
+                        .eq(field_encryption::citizen_id_prefix_indexes(&citizen_id)?),
+                    users::phone_number_hash.eq(field_encryption::blind_index(&phone_number)?),
+                ))
+                .execute(&mut conn)
+                .await;
+
+            match result {
+                Ok(_) => encrypted += 1,
+                Err(Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info)) => warn!(
+                    "User {} was not encrypted, it duplicates another user on {}",
+                    id,
+                    info.constraint_name().unwrap_or_default()
+                ),
+                Err(e) => return Err(anyhow::anyhow!("encrypting user {} failed: {e}", id)),
+            }
+        }
+
+        info!("Encrypted {} users so far", encrypted);
+    }
+
+    Ok(encrypted)
+}
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+    dsl::insert_into,
+};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        entities::api_keys::{ApiKeyEntity, InsertApiKeyEntity},
+        repositories::api_keys::ApiKeysRepository,
+    },
+    infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::api_keys},
+};
+
+pub struct ApiKeysPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl ApiKeysPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeysRepository for ApiKeysPostgres {
+    async fn create(&self, insert_api_key_entity: InsertApiKeyEntity) -> Result<i32> {
+        let mut conn = self.db_pool.get().await?;
+        let result = insert_into(api_keys::table)
+            .values(insert_api_key_entity)
+            .returning(api_keys::id)
+            .get_result::<i32>(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_active_by_prefix(&self, prefix: String) -> Result<Option<ApiKeyEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let now = chrono::Utc::now().naive_utc();
+        let result = api_keys::table
+            .filter(api_keys::prefix.eq(prefix))
+            .filter(api_keys::revoked_at.is_null())
+            .filter(
+                api_keys::expires_at
+                    .is_null()
+                    .or(api_keys::expires_at.gt(now)),
+            )
+            .select(ApiKeyEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn list(&self, hospital_id: i32) -> Result<Vec<ApiKeyEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = api_keys::table
+            .filter(api_keys::hospital_id.eq(hospital_id))
+            .order(api_keys::id.asc())
+            .select(ApiKeyEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn revoke_by_id(&self, hospital_id: i32, id: i32) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        diesel::update(api_keys::table)
+            .filter(api_keys::id.eq(id))
+            .filter(api_keys::hospital_id.eq(hospital_id))
+            .filter(api_keys::revoked_at.is_null())
+            .set(api_keys::revoked_at.eq(chrono::Utc::now().naive_utc()))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn touch_last_used_by_id(&self, id: i32) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        diesel::update(api_keys::table)
+            .filter(api_keys::id.eq(id))
+            .set(api_keys::last_used_at.eq(chrono::Utc::now().naive_utc()))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
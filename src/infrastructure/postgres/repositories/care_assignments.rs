@@ -0,0 +1,94 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        entities::care_assignments::{CareAssignmentEntity, InsertCareAssignmentEntity},
+        repositories::care_assignments::CareAssignmentsRepository,
+        value_objects::care_assignments_model::CareAssignmentFilter,
+    },
+    infrastructure::postgres::{
+        errors::map_constraint_violation, postgres_connection::PgPoolSquad,
+        schema::care_assignments,
+    },
+};
+
+pub struct CareAssignmentsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl CareAssignmentsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl CareAssignmentsRepository for CareAssignmentsPostgres {
+    async fn create(
+        &self,
+        insert_care_assignment_entity: InsertCareAssignmentEntity,
+    ) -> Result<CareAssignmentEntity> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = insert_into(care_assignments::table)
+            .values(insert_care_assignment_entity)
+            .returning(CareAssignmentEntity::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(map_constraint_violation)?;
+
+        Ok(result)
+    }
+
+    async fn list(&self, filter: CareAssignmentFilter) -> Result<Vec<CareAssignmentEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let mut query = care_assignments::table
+            .filter(care_assignments::hospital_id.eq(filter.hospital_id))
+            .filter(care_assignments::ended_at.is_null())
+            .into_boxed();
+
+        if let Some(doctor_id) = filter.doctor_id {
+            query = query.filter(care_assignments::doctor_id.eq(doctor_id));
+        }
+        if let Some(patient_id) = filter.patient_id {
+            query = query.filter(care_assignments::patient_id.eq(patient_id));
+        }
+
+        let result = query
+            .order(care_assignments::id.desc())
+            .select(CareAssignmentEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn end(
+        &self,
+        hospital_id: i32,
+        id: i32,
+        ended_by: i32,
+    ) -> Result<Option<CareAssignmentEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = diesel::update(
+            care_assignments::table
+                .find(id)
+                .filter(care_assignments::hospital_id.eq(hospital_id))
+                .filter(care_assignments::ended_at.is_null()),
+        )
+        .set((
+            care_assignments::ended_by.eq(Some(ended_by)),
+            care_assignments::ended_at.eq(Some(chrono::Utc::now().naive_utc())),
+        ))
+        .returning(CareAssignmentEntity::as_returning())
+        .get_result(&mut conn)
+        .await
+        .optional()?;
+
+        Ok(result)
+    }
+}
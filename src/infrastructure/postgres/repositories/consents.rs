@@ -0,0 +1,193 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::{
+    ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+    dsl::{insert_into, max},
+};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+
+use crate::{
+    domain::{
+        entities::consents::{
+            ConsentPurposeEntity, ConsentRecordEntity, ConsentVersionEntity,
+            InsertConsentPurposeEntity, InsertConsentRecordEntity, InsertConsentVersionEntity,
+        },
+        repositories::consents::ConsentsRepository,
+    },
+    infrastructure::postgres::{
+        consents,
+        errors::map_constraint_violation,
+        hospital_memberships,
+        postgres_connection::PgPoolSquad,
+        schema::{
+            consent_purposes, consent_records, consent_versions,
+            hospital_memberships as memberships,
+        },
+    },
+};
+
+pub struct ConsentsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl ConsentsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ConsentsRepository for ConsentsPostgres {
+    async fn create_purpose(
+        &self,
+        insert_consent_purpose_entity: InsertConsentPurposeEntity,
+    ) -> Result<ConsentPurposeEntity> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = insert_into(consent_purposes::table)
+            .values(insert_consent_purpose_entity)
+            .returning(ConsentPurposeEntity::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(map_constraint_violation)?;
+
+        Ok(result)
+    }
+
+    async fn publish_version(
+        &self,
+        purpose_id: i32,
+        text: String,
+        effective_from: NaiveDateTime,
+        created_by: i32,
+    ) -> Result<Option<ConsentVersionEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                // lock purpose ไว้ให้เลข version ไม่ชนกันเมื่อ publish พร้อมกัน
+                let purpose = consent_purposes::table
+                    .find(purpose_id)
+                    .select(consent_purposes::id)
+                    .for_update()
+                    .first::<i32>(conn)
+                    .await
+                    .optional()?;
+                if purpose.is_none() {
+                    return Ok(None);
+                }
+
+                let latest_version = consent_versions::table
+                    .filter(consent_versions::purpose_id.eq(purpose_id))
+                    .select(max(consent_versions::version))
+                    .first::<Option<i32>>(conn)
+                    .await?;
+
+                let version = insert_into(consent_versions::table)
+                    .values(InsertConsentVersionEntity {
+                        purpose_id,
+                        version: latest_version.unwrap_or(0) + 1,
+                        text,
+                        effective_from,
+                        created_by: Some(created_by),
+                        created_at: chrono::Utc::now().naive_utc(),
+                    })
+                    .returning(ConsentVersionEntity::as_returning())
+                    .get_result(conn)
+                    .await?;
+
+                Ok(Some(version))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn find_current_versions(
+        &self,
+    ) -> Result<Vec<(ConsentPurposeEntity, ConsentVersionEntity)>> {
+        let mut conn = self.db_pool.get().await?;
+
+        consents::current_versions(&mut conn).await
+    }
+
+    async fn find_version(&self, id: i32) -> Result<Option<ConsentVersionEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = consent_versions::table
+            .find(id)
+            .select(ConsentVersionEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn find_latest_records(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<(ConsentRecordEntity, ConsentVersionEntity)>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let rows = consent_records::table
+            .inner_join(consent_versions::table)
+            .filter(consent_records::user_id.eq(user_id))
+            .order(consent_records::id.asc())
+            .select((
+                ConsentRecordEntity::as_select(),
+                ConsentVersionEntity::as_select(),
+            ))
+            .load(&mut conn)
+            .await?;
+
+        Ok(consents::latest_per_purpose(rows))
+    }
+
+    async fn find_latest_records_at_hospital(
+        &self,
+        hospital_id: i32,
+    ) -> Result<Vec<(ConsentRecordEntity, ConsentVersionEntity)>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let rows = consent_records::table
+            .inner_join(consent_versions::table)
+            .filter(consent_records::user_id.eq_any(hospital_memberships::members_of(hospital_id)))
+            .order(consent_records::id.asc())
+            .select((
+                ConsentRecordEntity::as_select(),
+                ConsentVersionEntity::as_select(),
+            ))
+            .load(&mut conn)
+            .await?;
+
+        Ok(consents::latest_per_purpose(rows))
+    }
+
+    async fn count_members(&self, hospital_id: i32) -> Result<i64> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = memberships::table
+            .filter(memberships::hospital_id.eq(hospital_id))
+            .count()
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn record(
+        &self,
+        insert_consent_record_entity: InsertConsentRecordEntity,
+    ) -> Result<ConsentRecordEntity> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = insert_into(consent_records::table)
+            .values(insert_consent_record_entity)
+            .returning(ConsentRecordEntity::as_returning())
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,339 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+    dsl::insert_into,
+};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+
+use crate::{
+    domain::{
+        entities::{
+            audit_logs::AuditLogEntity,
+            consents::ConsentRecordEntity,
+            data_exports::{DataExportEntity, InsertDataExportEntity},
+            doctor_profiles::DoctorProfileEntity,
+            hospitals::HospitalMembershipEntity,
+            patient_profiles::PatientProfileEntity,
+            roles::UserRoleEntity,
+            sessions::SessionEntity,
+            users::UserEntity,
+        },
+        repositories::data_exports::DataExportsRepository,
+        value_objects::data_exports_model::{
+            DataExportBundleModel, DataExportStatus, ExportedAuditLogModel,
+            ExportedConsentRecordModel, ExportedMembershipModel, ExportedRoleGrantModel,
+            ExportedSessionModel, ExportedUserModel,
+        },
+    },
+    infrastructure::{
+        field_encryption,
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            schema::{
+                audit_logs, consent_purposes, consent_records, consent_versions, data_exports,
+                doctor_profiles, hospital_memberships, patient_profiles, roles, sessions,
+                user_roles, users,
+            },
+        },
+    },
+};
+
+pub struct DataExportsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl DataExportsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataExportsRepository for DataExportsPostgres {
+    async fn find_bundle(&self, user_id: i32) -> Result<Option<DataExportBundleModel>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let Some(mut user) = users::table
+                    .find(user_id)
+                    .select(UserEntity::as_select())
+                    .first(conn)
+                    .await
+                    .optional()?
+                else {
+                    return Ok(None);
+                };
+                user.citizen_id = field_encryption::decrypt(&user.citizen_id)?;
+                user.phone_number = field_encryption::decrypt(&user.phone_number)?;
+
+                let hospital_memberships = hospital_memberships::table
+                    .filter(hospital_memberships::user_id.eq(user_id))
+                    .order(hospital_memberships::created_at.asc())
+                    .select(HospitalMembershipEntity::as_select())
+                    .load(conn)
+                    .await?;
+
+                let patient_profile = patient_profiles::table
+                    .find(user_id)
+                    .select(PatientProfileEntity::as_select())
+                    .first(conn)
+                    .await
+                    .optional()?;
+
+                let doctor_profile = doctor_profiles::table
+                    .find(user_id)
+                    .select(DoctorProfileEntity::as_select())
+                    .first(conn)
+                    .await
+                    .optional()?;
+
+                let role_grants: Vec<(String, UserRoleEntity)> = user_roles::table
+                    .inner_join(roles::table)
+                    .filter(user_roles::user_id.eq(user_id))
+                    .order(user_roles::granted_at.asc())
+                    .select((roles::name, UserRoleEntity::as_select()))
+                    .load(conn)
+                    .await?;
+
+                let consents: Vec<(ConsentRecordEntity, i32, String)> = consent_records::table
+                    .inner_join(consent_versions::table.inner_join(consent_purposes::table))
+                    .filter(consent_records::user_id.eq(user_id))
+                    .order(consent_records::id.asc())
+                    .select((
+                        ConsentRecordEntity::as_select(),
+                        consent_versions::version,
+                        consent_purposes::code,
+                    ))
+                    .load(conn)
+                    .await?;
+
+                let sessions = sessions::table
+                    .filter(sessions::user_id.eq(user_id))
+                    .order(sessions::created_at.asc())
+                    .select(SessionEntity::as_select())
+                    .load(conn)
+                    .await?;
+
+                let access_log = audit_logs::table
+                    .filter(
+                        audit_logs::subject_user_id
+                            .eq(user_id)
+                            .or(audit_logs::actor_id.eq(user_id)),
+                    )
+                    .order(audit_logs::id.asc())
+                    .select(AuditLogEntity::as_select())
+                    .load(conn)
+                    .await?;
+
+                Ok(Some(DataExportBundleModel {
+                    generated_at: chrono::Utc::now().naive_utc(),
+                    user: ExportedUserModel::from(user),
+                    hospital_memberships: hospital_memberships
+                        .into_iter()
+                        .map(ExportedMembershipModel::from)
+                        .collect(),
+                    patient_profile: patient_profile.map(TryInto::try_into).transpose()?,
+                    doctor_profile: doctor_profile.map(Into::into),
+                    role_grants: role_grants
+                        .into_iter()
+                        .map(|(role, grant)| ExportedRoleGrantModel {
+                            hospital_id: grant.hospital_id,
+                            role,
+                            granted_by: grant.granted_by,
+                            granted_at: grant.granted_at,
+                            valid_from: grant.valid_from,
+                            valid_until: grant.valid_until,
+                            expired_at: grant.expired_at,
+                        })
+                        .collect(),
+                    consents: consents
+                        .into_iter()
+                        .map(
+                            |(record, version, purpose_code)| ExportedConsentRecordModel {
+                                purpose_code,
+                                version,
+                                action: record.action,
+                                channel: record.channel,
+                                recorded_by: record.recorded_by,
+                                created_at: record.created_at,
+                            },
+                        )
+                        .collect(),
+                    sessions: sessions
+                        .into_iter()
+                        .map(ExportedSessionModel::from)
+                        .collect(),
+                    access_log: access_log
+                        .into_iter()
+                        .map(ExportedAuditLogModel::from)
+                        .collect(),
+                }))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn count_rows(&self, user_id: i32) -> Result<i64> {
+        let mut conn = self.db_pool.get().await?;
+
+        let sessions = sessions::table
+            .filter(sessions::user_id.eq(user_id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        let consents = consent_records::table
+            .filter(consent_records::user_id.eq(user_id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        let access_log = audit_logs::table
+            .filter(
+                audit_logs::subject_user_id
+                    .eq(user_id)
+                    .or(audit_logs::actor_id.eq(user_id)),
+            )
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+
+        Ok(sessions + consents + access_log)
+    }
+
+    async fn create(
+        &self,
+        insert_data_export_entity: InsertDataExportEntity,
+    ) -> Result<DataExportEntity> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = insert_into(data_exports::table)
+            .values(insert_data_export_entity)
+            .returning(DataExportEntity::as_returning())
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_by_id(&self, user_id: i32, id: String) -> Result<Option<DataExportEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = data_exports::table
+            .find(id)
+            .filter(data_exports::user_id.eq(user_id))
+            .select(DataExportEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn find_content(&self, user_id: i32, id: String) -> Result<Option<Vec<u8>>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = data_exports::table
+            .find(id)
+            .filter(data_exports::user_id.eq(user_id))
+            .select(data_exports::content)
+            .first::<Option<Vec<u8>>>(&mut conn)
+            .await
+            .optional()?;
+
+        result
+            .flatten()
+            .map(|content| field_encryption::decrypt_bytes(&content))
+            .transpose()
+    }
+
+    async fn claim_next(&self, stale_before: NaiveDateTime) -> Result<Option<DataExportEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let Some(id) = data_exports::table
+                    .filter(
+                        data_exports::status
+                            .eq(DataExportStatus::Pending.to_string())
+                            .or(data_exports::status
+                                .eq(DataExportStatus::Processing.to_string())
+                                .and(data_exports::claimed_at.lt(stale_before))),
+                    )
+                    .order(data_exports::created_at.asc())
+                    .select(data_exports::id)
+                    .for_update()
+                    .skip_locked()
+                    .first::<String>(conn)
+                    .await
+                    .optional()?
+                else {
+                    return Ok(None);
+                };
+
+                let export = diesel::update(data_exports::table.find(id))
+                    .set((
+                        data_exports::status.eq(DataExportStatus::Processing.to_string()),
+                        data_exports::claimed_at.eq(Some(chrono::Utc::now().naive_utc())),
+                    ))
+                    .returning(DataExportEntity::as_returning())
+                    .get_result(conn)
+                    .await?;
+
+                Ok(Some(export))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn complete(
+        &self,
+        id: String,
+        content: Vec<u8>,
+        completed_at: NaiveDateTime,
+        expires_at: NaiveDateTime,
+    ) -> Result<()> {
+        let content = field_encryption::encrypt_bytes(&content)?;
+
+        let mut conn = self.db_pool.get().await?;
+
+        diesel::update(data_exports::table.find(id))
+            .set((
+                data_exports::status.eq(DataExportStatus::Ready.to_string()),
+                data_exports::content.eq(Some(content)),
+                data_exports::completed_at.eq(Some(completed_at)),
+                data_exports::expires_at.eq(expires_at),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fail(&self, id: String, error: String) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+
+        diesel::update(data_exports::table.find(id))
+            .set((
+                data_exports::status.eq(DataExportStatus::Failed.to_string()),
+                data_exports::error.eq(Some(error)),
+                data_exports::completed_at.eq(Some(chrono::Utc::now().naive_utc())),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self, now: NaiveDateTime) -> Result<usize> {
+        let mut conn = self.db_pool.get().await?;
+
+        let deleted = diesel::delete(data_exports::table.filter(data_exports::expires_at.lt(now)))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted)
+    }
+}
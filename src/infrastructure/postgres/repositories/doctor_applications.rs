@@ -0,0 +1,254 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::{
+    ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, dsl::insert_into,
+    upsert::excluded,
+};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+
+use crate::{
+    domain::{
+        entities::doctor_applications::{
+            DoctorApplicationEntity, DoctorApplicationTransitionEntity,
+            InsertDoctorApplicationEntity, InsertDoctorApplicationTransitionEntity,
+        },
+        repositories::doctor_applications::DoctorApplicationsRepository,
+        value_objects::{
+            doctor_applications_model::{
+                DoctorApplicationEffect, DoctorApplicationStatus, DoctorApplicationTransition,
+            },
+            roles::Role,
+        },
+    },
+    infrastructure::postgres::{
+        errors::map_constraint_violation,
+        postgres_connection::PgPoolSquad,
+        role_grants,
+        schema::{doctor_application_transitions, doctor_applications, doctor_profiles},
+    },
+};
+
+pub struct DoctorApplicationsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl DoctorApplicationsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DoctorApplicationsRepository for DoctorApplicationsPostgres {
+    async fn submit(
+        &self,
+        insert_doctor_application_entity: InsertDoctorApplicationEntity,
+    ) -> Result<DoctorApplicationEntity> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let application = insert_into(doctor_applications::table)
+                    .values(&insert_doctor_application_entity)
+                    .returning(DoctorApplicationEntity::as_returning())
+                    .get_result(conn)
+                    .await
+                    .map_err(map_constraint_violation)?;
+
+                insert_into(doctor_application_transitions::table)
+                    .values(InsertDoctorApplicationTransitionEntity {
+                        application_id: application.id,
+                        from_status: None,
+                        to_status: DoctorApplicationStatus::Pending.to_string(),
+                        actor_id: application.user_id,
+                        notes: None,
+                        created_at: application.created_at,
+                    })
+                    .execute(conn)
+                    .await?;
+
+                Ok(application)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn find_by_id(
+        &self,
+        hospital_id: i32,
+        id: i32,
+    ) -> Result<Option<DoctorApplicationEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = doctor_applications::table
+            .find(id)
+            .filter(doctor_applications::hospital_id.eq(hospital_id))
+            .select(DoctorApplicationEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn list_by_user_id(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<DoctorApplicationEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = doctor_applications::table
+            .filter(doctor_applications::hospital_id.eq(hospital_id))
+            .filter(doctor_applications::user_id.eq(user_id))
+            .order(doctor_applications::id.desc())
+            .select(DoctorApplicationEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_by_status(
+        &self,
+        hospital_id: i32,
+        status: String,
+    ) -> Result<Vec<DoctorApplicationEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = doctor_applications::table
+            .filter(doctor_applications::hospital_id.eq(hospital_id))
+            .filter(doctor_applications::status.eq(status))
+            .order((
+                doctor_applications::created_at.asc(),
+                doctor_applications::id.asc(),
+            ))
+            .select(DoctorApplicationEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_transitions(
+        &self,
+        application_id: i32,
+    ) -> Result<Vec<DoctorApplicationTransitionEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = doctor_application_transitions::table
+            .filter(doctor_application_transitions::application_id.eq(application_id))
+            .order(doctor_application_transitions::id.asc())
+            .select(DoctorApplicationTransitionEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn transition(
+        &self,
+        transition: DoctorApplicationTransition,
+    ) -> Result<Option<DoctorApplicationEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let now = chrono::Utc::now().naive_utc();
+
+                // เงื่อนไข status เดิมกัน admin สองคนตัดสินคำขอเดียวกันพร้อมกัน
+                let Some(application) = diesel::update(
+                    doctor_applications::table
+                        .find(transition.application_id)
+                        .filter(doctor_applications::status.eq(transition.from_status.to_string())),
+                )
+                .set((
+                    doctor_applications::status.eq(transition.to_status.to_string()),
+                    doctor_applications::review_notes.eq(transition.notes.clone()),
+                    doctor_applications::reviewed_by.eq(Some(transition.actor_id)),
+                    doctor_applications::reviewed_at.eq(Some(now)),
+                    doctor_applications::updated_at.eq(now),
+                ))
+                .returning(DoctorApplicationEntity::as_returning())
+                .get_result(conn)
+                .await
+                .optional()?
+                else {
+                    return Ok(None);
+                };
+
+                insert_into(doctor_application_transitions::table)
+                    .values(InsertDoctorApplicationTransitionEntity {
+                        application_id: application.id,
+                        from_status: Some(transition.from_status.to_string()),
+                        to_status: transition.to_status.to_string(),
+                        actor_id: transition.actor_id,
+                        notes: transition.notes,
+                        created_at: now,
+                    })
+                    .execute(conn)
+                    .await?;
+
+                match DoctorApplicationEffect::of(transition.to_status) {
+                    DoctorApplicationEffect::GrantDoctor => {
+                        role_grants::grant_role(
+                            conn,
+                            application.hospital_id,
+                            application.user_id,
+                            Role::Doctor,
+                            Some(transition.actor_id),
+                            None,
+                            transition.valid_until,
+                        )
+                        .await?;
+
+                        // ใบอนุญาตถูกตรวจแล้วตอนอนุมัติ จึงขึ้น directory ได้ทันที
+                        insert_into(doctor_profiles::table)
+                            .values((
+                                doctor_profiles::user_id.eq(application.user_id),
+                                doctor_profiles::license_number
+                                    .eq(application.license_number.clone()),
+                                doctor_profiles::specialties.eq(application.specialties.clone()),
+                                doctor_profiles::department.eq(application.department.clone()),
+                                doctor_profiles::verified_at.eq(Some(now)),
+                                doctor_profiles::verified_by.eq(Some(transition.actor_id)),
+                                doctor_profiles::updated_at.eq(now),
+                            ))
+                            .on_conflict(doctor_profiles::user_id)
+                            .do_update()
+                            .set((
+                                doctor_profiles::license_number
+                                    .eq(excluded(doctor_profiles::license_number)),
+                                doctor_profiles::verified_at.eq(Some(now)),
+                                doctor_profiles::verified_by.eq(Some(transition.actor_id)),
+                                doctor_profiles::updated_at.eq(now),
+                            ))
+                            .execute(conn)
+                            .await
+                            .map_err(map_constraint_violation)?;
+                    }
+                    DoctorApplicationEffect::RevokeDoctor => {
+                        role_grants::revoke_role(
+                            conn,
+                            application.hospital_id,
+                            application.user_id,
+                            Role::Doctor,
+                        )
+                        .await?;
+
+                        diesel::update(doctor_profiles::table.find(application.user_id))
+                            .set((
+                                doctor_profiles::verified_at.eq(None::<NaiveDateTime>),
+                                doctor_profiles::verified_by.eq(None::<i32>),
+                                doctor_profiles::updated_at.eq(now),
+                            ))
+                            .execute(conn)
+                            .await?;
+                    }
+                    DoctorApplicationEffect::None => {}
+                }
+
+                Ok(Some(application))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::{
+    ExpressionMethods, JoinOnDsl, OptionalExtension, PgArrayExpressionMethods, QueryDsl,
+    SelectableHelper, dsl::insert_into,
+};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        entities::doctor_profiles::{
+            DoctorDirectoryEntity, DoctorProfileEntity, UpsertDoctorProfileEntity,
+        },
+        repositories::doctor_profiles::DoctorProfilesRepository,
+        value_objects::{doctor_profiles_model::DoctorDirectoryFilter, roles::Role},
+    },
+    infrastructure::postgres::{
+        errors::map_constraint_violation,
+        postgres_connection::PgPoolSquad,
+        role_grants,
+        schema::{doctor_profiles, users},
+    },
+};
+
+pub struct DoctorProfilesPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl DoctorProfilesPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DoctorProfilesRepository for DoctorProfilesPostgres {
+    async fn create(
+        &self,
+        upsert_doctor_profile_entity: UpsertDoctorProfileEntity,
+    ) -> Result<DoctorProfileEntity> {
+        let mut conn = self.db_pool.get().await?;
+        let result = insert_into(doctor_profiles::table)
+            .values(upsert_doctor_profile_entity)
+            .returning(DoctorProfileEntity::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(map_constraint_violation)?;
+
+        Ok(result)
+    }
+
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Option<DoctorProfileEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = doctor_profiles::table
+            .find(user_id)
+            .select(DoctorProfileEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn update(
+        &self,
+        upsert_doctor_profile_entity: UpsertDoctorProfileEntity,
+        reset_verification: bool,
+    ) -> Result<Option<DoctorProfileEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let target = doctor_profiles::table.find(upsert_doctor_profile_entity.user_id);
+
+        let result = if reset_verification {
+            diesel::update(target)
+                .set((
+                    &upsert_doctor_profile_entity,
+                    doctor_profiles::verified_at.eq(None::<NaiveDateTime>),
+                    doctor_profiles::verified_by.eq(None::<i32>),
+                ))
+                .returning(DoctorProfileEntity::as_returning())
+                .get_result(&mut conn)
+                .await
+        } else {
+            diesel::update(target)
+                .set(&upsert_doctor_profile_entity)
+                .returning(DoctorProfileEntity::as_returning())
+                .get_result(&mut conn)
+                .await
+        };
+
+        Ok(result.optional().map_err(map_constraint_violation)?)
+    }
+
+    async fn set_verification(
+        &self,
+        user_id: i32,
+        verified_by: Option<i32>,
+    ) -> Result<Option<DoctorProfileEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let verified_at = verified_by.map(|_| chrono::Utc::now().naive_utc());
+
+        let result = diesel::update(doctor_profiles::table.find(user_id))
+            .set((
+                doctor_profiles::verified_at.eq(verified_at),
+                doctor_profiles::verified_by.eq(verified_by),
+            ))
+            .returning(DoctorProfileEntity::as_returning())
+            .get_result(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn list_directory(
+        &self,
+        filter: DoctorDirectoryFilter,
+    ) -> Result<Vec<DoctorDirectoryEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let mut query = doctor_profiles::table
+            .inner_join(users::table.on(users::id.eq(doctor_profiles::user_id)))
+            .filter(doctor_profiles::verified_at.is_not_null())
+            .filter(users::deleted_at.is_null())
+            .filter(users::id.eq_any(role_grants::holders_of(filter.hospital_id, Role::Doctor)))
+            .select((
+                DoctorProfileEntity::as_select(),
+                users::first_name,
+                users::last_name,
+            ))
+            .into_boxed();
+
+        if let Some(specialty) = filter.specialty {
+            query = query.filter(doctor_profiles::specialties.contains(vec![specialty]));
+        }
+        if let Some(department) = filter.department {
+            query = query.filter(doctor_profiles::department.eq(department));
+        }
+        if let Some(language) = filter.language {
+            query = query.filter(doctor_profiles::languages.contains(vec![language]));
+        }
+        if let Some(after_user_id) = filter.after_user_id {
+            query = query.filter(doctor_profiles::user_id.gt(after_user_id));
+        }
+
+        let rows: Vec<(DoctorProfileEntity, String, String)> = query
+            .order(doctor_profiles::user_id.asc())
+            .limit(filter.limit)
+            .load(&mut conn)
+            .await?;
+
+        Ok(rows.into_iter().map(to_directory_entity).collect())
+    }
+
+    async fn find_in_directory(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Option<DoctorDirectoryEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let row: Option<(DoctorProfileEntity, String, String)> = doctor_profiles::table
+            .inner_join(users::table.on(users::id.eq(doctor_profiles::user_id)))
+            .filter(doctor_profiles::user_id.eq(user_id))
+            .filter(doctor_profiles::verified_at.is_not_null())
+            .filter(users::deleted_at.is_null())
+            .filter(users::id.eq_any(role_grants::holders_of(hospital_id, Role::Doctor)))
+            .select((
+                DoctorProfileEntity::as_select(),
+                users::first_name,
+                users::last_name,
+            ))
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(row.map(to_directory_entity))
+    }
+}
+
+fn to_directory_entity(
+    (profile, first_name, last_name): (DoctorProfileEntity, String, String),
+) -> DoctorDirectoryEntity {
+    DoctorDirectoryEntity {
+        profile,
+        first_name,
+        last_name,
+    }
+}
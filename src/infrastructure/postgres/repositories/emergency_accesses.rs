@@ -0,0 +1,197 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, dsl::insert_into};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+
+use crate::{
+    domain::{
+        entities::{
+            emergency_accesses::{EmergencyAccessEntity, InsertEmergencyAccessEntity},
+            notifications::InsertNotificationEntity,
+        },
+        repositories::emergency_accesses::EmergencyAccessRepository,
+        value_objects::{
+            audit_logs_model::AuditAction, emergency_accesses_model::EmergencyAccessFilter,
+            notifications_model::NotificationKind, roles::Role,
+        },
+    },
+    infrastructure::postgres::{
+        audit_logs, hospital_memberships,
+        postgres_connection::PgPoolSquad,
+        role_grants,
+        schema::{emergency_accesses, notifications, users},
+    },
+};
+
+pub struct EmergencyAccessPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl EmergencyAccessPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl EmergencyAccessRepository for EmergencyAccessPostgres {
+    async fn grant(
+        &self,
+        insert_emergency_access_entity: InsertEmergencyAccessEntity,
+    ) -> Result<Option<EmergencyAccessEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let hospital_id = insert_emergency_access_entity.hospital_id;
+                let patient_exists = users::table
+                    .find(insert_emergency_access_entity.patient_id)
+                    .filter(users::id.eq_any(hospital_memberships::members_of(hospital_id)))
+                    .filter(users::id.eq_any(role_grants::holders_of(hospital_id, Role::Patient)))
+                    .filter(users::deleted_at.is_null())
+                    .select(users::id)
+                    .first::<i32>(conn)
+                    .await
+                    .optional()?
+                    .is_some();
+                if !patient_exists {
+                    return Ok(None);
+                }
+
+                let access = insert_into(emergency_accesses::table)
+                    .values(&insert_emergency_access_entity)
+                    .returning(EmergencyAccessEntity::as_returning())
+                    .get_result(conn)
+                    .await?;
+
+                audit_logs::record(
+                    conn,
+                    Some(access.user_id),
+                    AuditAction::EmergencyAccessGranted,
+                    Some(access.patient_id),
+                    serde_json::json!({
+                        "emergency_access_id": access.id,
+                        "reason": access.reason,
+                        "expires_at": access.expires_at,
+                        "hospital_id": access.hospital_id,
+                    }),
+                )
+                .await?;
+
+                // admin ของโรงพยาบาลเป็นผู้ตรวจทาน ยกเว้นคนที่ break the glass เอง
+                let admin_ids: Vec<i32> = users::table
+                    .filter(
+                        users::id.eq_any(role_grants::holders_of(access.hospital_id, Role::Admin)),
+                    )
+                    .filter(users::deleted_at.is_null())
+                    .filter(users::id.ne(access.user_id))
+                    .select(users::id)
+                    .load(conn)
+                    .await?;
+
+                let kind = NotificationKind::EmergencyAccessGranted;
+                let rows: Vec<InsertNotificationEntity> = admin_ids
+                    .into_iter()
+                    .map(|recipient_id| InsertNotificationEntity {
+                        recipient_id,
+                        kind: kind.to_string(),
+                        subject_user_id: Some(access.patient_id),
+                        payload: serde_json::json!({
+                            "emergency_access_id": access.id,
+                            "user_id": access.user_id,
+                            "reason": access.reason,
+                            "expires_at": access.expires_at,
+                            "hospital_id": access.hospital_id,
+                        }),
+                        created_at: access.created_at,
+                        priority: kind.priority().to_string(),
+                    })
+                    .collect();
+
+                insert_into(notifications::table)
+                    .values(rows)
+                    .execute(conn)
+                    .await?;
+
+                Ok(Some(access))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn list(&self, filter: EmergencyAccessFilter) -> Result<Vec<EmergencyAccessEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let mut query = emergency_accesses::table
+            .filter(emergency_accesses::hospital_id.eq(filter.hospital_id))
+            .into_boxed();
+
+        if let Some(from) = filter.from {
+            query = query.filter(emergency_accesses::created_at.ge(from));
+        }
+        if let Some(to) = filter.to {
+            query = query.filter(emergency_accesses::created_at.lt(to));
+        }
+        if filter.unreviewed {
+            query = query.filter(emergency_accesses::reviewed_at.is_null());
+        }
+
+        let result = query
+            .order(emergency_accesses::id.desc())
+            .select(EmergencyAccessEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn review(
+        &self,
+        hospital_id: i32,
+        id: i32,
+        reviewed_by: i32,
+        notes: String,
+    ) -> Result<Option<EmergencyAccessEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let Some(access) = diesel::update(
+                    emergency_accesses::table
+                        .find(id)
+                        .filter(emergency_accesses::hospital_id.eq(hospital_id))
+                        .filter(emergency_accesses::reviewed_at.is_null()),
+                )
+                .set((
+                    emergency_accesses::reviewed_by.eq(Some(reviewed_by)),
+                    emergency_accesses::reviewed_at.eq(Some(chrono::Utc::now().naive_utc())),
+                    emergency_accesses::review_notes.eq(Some(notes)),
+                ))
+                .returning(EmergencyAccessEntity::as_returning())
+                .get_result(conn)
+                .await
+                .optional()?
+                else {
+                    return Ok(None);
+                };
+
+                audit_logs::record(
+                    conn,
+                    Some(reviewed_by),
+                    AuditAction::EmergencyAccessReviewed,
+                    Some(access.patient_id),
+                    serde_json::json!({
+                        "emergency_access_id": access.id,
+                        "user_id": access.user_id,
+                        "hospital_id": hospital_id,
+                    }),
+                )
+                .await?;
+
+                Ok(Some(access))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
@@ -0,0 +1,243 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+    dsl::{insert_into, max},
+};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+
+use crate::{
+    domain::{
+        entities::erasures::{ErasureEntity, InsertErasureEntity},
+        repositories::erasures::ErasuresRepository,
+        value_objects::audit_logs_model::AuditAction,
+    },
+    infrastructure::{
+        field_encryption,
+        postgres::{
+            audit_logs,
+            errors::map_constraint_violation,
+            hospital_memberships,
+            postgres_connection::PgPoolSquad,
+            schema::{
+                care_assignments, data_exports, emergency_accesses, erasures, guardianships,
+                hospital_memberships as memberships, oauth_authorization_codes, oauth_consents,
+                patient_profiles, sessions, users,
+            },
+        },
+    },
+};
+
+/// Shown in place of the user's name once erased.
+const ERASED_FIRST_NAME: &str = "Erased";
+
+pub struct ErasuresPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl ErasuresPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ErasuresRepository for ErasuresPostgres {
+    async fn find_last_clinical_activity_at(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Option<NaiveDateTime>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let is_member = users::table
+            .find(user_id)
+            .filter(users::id.eq_any(hospital_memberships::members_of(hospital_id)))
+            .select(users::id)
+            .first::<i32>(&mut conn)
+            .await
+            .optional()?
+            .is_some();
+        if !is_member {
+            return Ok(None);
+        }
+
+        let profile_updated_at = patient_profiles::table
+            .find(user_id)
+            .select(patient_profiles::updated_at)
+            .first::<NaiveDateTime>(&mut conn)
+            .await
+            .optional()?;
+
+        let registered_at = memberships::table
+            .filter(memberships::user_id.eq(user_id))
+            .select(max(memberships::created_at))
+            .first::<Option<NaiveDateTime>>(&mut conn)
+            .await?;
+
+        let now = chrono::Utc::now().naive_utc();
+        let cared_for_at = care_assignments::table
+            .filter(care_assignments::patient_id.eq(user_id))
+            .select((care_assignments::created_at, care_assignments::ended_at))
+            .load::<(NaiveDateTime, Option<NaiveDateTime>)>(&mut conn)
+            .await?
+            .into_iter()
+            .map(|(created_at, ended_at)| ended_at.unwrap_or(now).max(created_at))
+            .max();
+
+        let emergency_accessed_at = emergency_accesses::table
+            .filter(emergency_accesses::patient_id.eq(user_id))
+            .select(max(emergency_accesses::created_at))
+            .first::<Option<NaiveDateTime>>(&mut conn)
+            .await?;
+
+        Ok([
+            profile_updated_at,
+            registered_at,
+            cared_for_at,
+            emergency_accessed_at,
+        ]
+        .into_iter()
+        .flatten()
+        .max())
+    }
+
+    async fn erase(
+        &self,
+        insert_erasure_entity: InsertErasureEntity,
+    ) -> Result<Option<ErasureEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let user_id = insert_erasure_entity.user_id;
+                let now = insert_erasure_entity.created_at;
+
+                let is_member = users::table
+                    .find(user_id)
+                    .filter(users::id.eq_any(hospital_memberships::members_of(
+                        insert_erasure_entity.hospital_id,
+                    )))
+                    .select(users::id)
+                    .for_update()
+                    .first::<i32>(conn)
+                    .await
+                    .optional()?
+                    .is_some();
+                if !is_member {
+                    return Ok(None);
+                }
+
+                // unique บน erasures.user_id กันการลบซ้ำ
+                let erasure = insert_into(erasures::table)
+                    .values(&insert_erasure_entity)
+                    .returning(ErasureEntity::as_returning())
+                    .get_result(conn)
+                    .await
+                    .map_err(map_constraint_violation)?;
+
+                // แถวยังอยู่เพื่อ foreign key; ค่าแทนที่ไม่ผูกกับตัวบุคคลและไม่ซ้ำกันระหว่าง user
+                // blind index ถูกล้าง จึงค้นหาหรือ login ด้วยเลขบัตร/เบอร์โทรเดิมไม่ได้อีก
+                let pseudonym = format!("erased-{}", user_id);
+                diesel::update(users::table.find(user_id))
+                    .set((
+                        users::first_name.eq(ERASED_FIRST_NAME),
+                        users::last_name.eq(&pseudonym),
+                        users::name_romanized.eq(""),
+                        users::citizen_id.eq(field_encryption::encrypt(&pseudonym)?),
+                        users::citizen_id_hash.eq(None::<String>),
+                        users::citizen_id_prefix_hashes.eq(Vec::<String>::new()),
+                        users::phone_number.eq(field_encryption::encrypt("")?),
+                        users::phone_number_hash.eq(None::<String>),
+                        // ไม่ใช่ argon2 hash จึงไม่มีรหัสผ่านใดผ่านการตรวจสอบ
+                        users::password.eq(""),
+                        users::updated_at.eq(now),
+                    ))
+                    .execute(conn)
+                    .await?;
+
+                diesel::update(users::table.find(user_id))
+                    .filter(users::deleted_at.is_null())
+                    .set(users::deleted_at.eq(now))
+                    .execute(conn)
+                    .await?;
+
+                // ข้อมูลทางคลินิกยังเก็บไว้ตามเวชระเบียน ล้างเฉพาะที่อยู่และผู้ติดต่อ
+                diesel::update(patient_profiles::table.find(user_id))
+                    .set((
+                        patient_profiles::address.eq(None::<serde_json::Value>),
+                        patient_profiles::emergency_contacts.eq(serde_json::json!([])),
+                        patient_profiles::updated_at.eq(now),
+                    ))
+                    .execute(conn)
+                    .await?;
+
+                let revoked_sessions = diesel::update(
+                    sessions::table
+                        .filter(sessions::user_id.eq(user_id))
+                        .filter(sessions::revoked_at.is_null()),
+                )
+                .set(sessions::revoked_at.eq(now))
+                .execute(conn)
+                .await?;
+
+                diesel::update(
+                    oauth_consents::table
+                        .filter(oauth_consents::user_id.eq(user_id))
+                        .filter(oauth_consents::revoked_at.is_null()),
+                )
+                .set(oauth_consents::revoked_at.eq(now))
+                .execute(conn)
+                .await?;
+
+                diesel::update(
+                    oauth_authorization_codes::table
+                        .filter(oauth_authorization_codes::user_id.eq(user_id))
+                        .filter(oauth_authorization_codes::consumed_at.is_null()),
+                )
+                .set(oauth_authorization_codes::consumed_at.eq(now))
+                .execute(conn)
+                .await?;
+
+                diesel::update(
+                    guardianships::table
+                        .filter(
+                            guardianships::guardian_id
+                                .eq(user_id)
+                                .or(guardianships::dependent_id.eq(user_id)),
+                        )
+                        .filter(guardianships::revoked_at.is_null()),
+                )
+                .set((
+                    guardianships::revoked_by.eq(erasure.erased_by),
+                    guardianships::revoked_at.eq(Some(now)),
+                ))
+                .execute(conn)
+                .await?;
+
+                diesel::delete(data_exports::table.filter(data_exports::user_id.eq(user_id)))
+                    .execute(conn)
+                    .await?;
+
+                audit_logs::record(
+                    conn,
+                    erasure.erased_by,
+                    AuditAction::UserErased,
+                    Some(user_id),
+                    serde_json::json!({
+                        "erasure_id": erasure.id,
+                        "legal_basis": erasure.legal_basis,
+                        "notes": erasure.notes,
+                        "revoked_sessions": revoked_sessions,
+                        "hospital_id": erasure.hospital_id,
+                    }),
+                )
+                .await?;
+
+                Ok(Some(erasure))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
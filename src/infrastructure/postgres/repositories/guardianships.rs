@@ -0,0 +1,217 @@
+use anyhow::Result;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper,
+    dsl::insert_into,
+};
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+
+use crate::{
+    domain::{
+        entities::guardianships::{GuardianshipEntity, InsertGuardianshipEntity},
+        repositories::guardianships::GuardianshipsRepository,
+        value_objects::audit_logs_model::AuditAction,
+    },
+    infrastructure::postgres::{
+        audit_logs, errors::map_constraint_violation, hospital_memberships::members_of,
+        postgres_connection::PgPoolSquad, schema::guardianships,
+    },
+};
+
+pub struct GuardianshipsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl GuardianshipsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl GuardianshipsRepository for GuardianshipsPostgres {
+    async fn create(
+        &self,
+        hospital_id: i32,
+        insert_guardianship_entity: InsertGuardianshipEntity,
+    ) -> Result<GuardianshipEntity> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let guardianship = insert_into(guardianships::table)
+                    .values(&insert_guardianship_entity)
+                    .returning(GuardianshipEntity::as_returning())
+                    .get_result(conn)
+                    .await
+                    .map_err(map_constraint_violation)?;
+
+                audit_logs::record(
+                    conn,
+                    Some(guardianship.created_by),
+                    AuditAction::GuardianshipCreated,
+                    Some(guardianship.dependent_id),
+                    serde_json::json!({
+                        "guardianship_id": guardianship.id,
+                        "guardian_id": guardianship.guardian_id,
+                        "relationship": guardianship.relationship,
+                        "consent_evidence": guardianship.consent_evidence,
+                        "valid_until": guardianship.valid_until,
+                        "hospital_id": hospital_id,
+                    }),
+                )
+                .await?;
+
+                Ok(guardianship)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn revoke(
+        &self,
+        hospital_id: i32,
+        id: i32,
+        revoked_by: i32,
+    ) -> Result<Option<GuardianshipEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let Some(guardianship) = diesel::update(
+                    guardianships::table
+                        .find(id)
+                        .filter(guardianships::revoked_at.is_null())
+                        .filter(guardianships::guardian_id.eq_any(members_of(hospital_id)))
+                        .filter(guardianships::dependent_id.eq_any(members_of(hospital_id))),
+                )
+                .set((
+                    guardianships::revoked_by.eq(Some(revoked_by)),
+                    guardianships::revoked_at.eq(Some(chrono::Utc::now().naive_utc())),
+                ))
+                .returning(GuardianshipEntity::as_returning())
+                .get_result(conn)
+                .await
+                .optional()?
+                else {
+                    return Ok(None);
+                };
+
+                audit_logs::record(
+                    conn,
+                    Some(revoked_by),
+                    AuditAction::GuardianshipRevoked,
+                    Some(guardianship.dependent_id),
+                    serde_json::json!({
+                        "guardianship_id": guardianship.id,
+                        "guardian_id": guardianship.guardian_id,
+                        "hospital_id": hospital_id,
+                    }),
+                )
+                .await?;
+
+                Ok(Some(guardianship))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn find_active(
+        &self,
+        guardian_id: i32,
+        dependent_id: i32,
+    ) -> Result<Option<GuardianshipEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = guardianships::table
+            .filter(guardianships::guardian_id.eq(guardian_id))
+            .filter(guardianships::dependent_id.eq(dependent_id))
+            .filter(guardianships::revoked_at.is_null())
+            .filter(
+                guardianships::valid_until
+                    .is_null()
+                    .or(guardianships::valid_until.gt(chrono::Utc::now().naive_utc())),
+            )
+            .select(GuardianshipEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn list_active_for_guardian(&self, guardian_id: i32) -> Result<Vec<GuardianshipEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = guardianships::table
+            .filter(guardianships::guardian_id.eq(guardian_id))
+            .filter(guardianships::revoked_at.is_null())
+            .filter(
+                guardianships::valid_until
+                    .is_null()
+                    .or(guardianships::valid_until.gt(chrono::Utc::now().naive_utc())),
+            )
+            .order(guardianships::id.asc())
+            .select(GuardianshipEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_by_user_id(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<GuardianshipEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = guardianships::table
+            .filter(
+                guardianships::guardian_id
+                    .eq(user_id)
+                    .or(guardianships::dependent_id.eq(user_id)),
+            )
+            .filter(guardianships::guardian_id.eq_any(members_of(hospital_id)))
+            .filter(guardianships::dependent_id.eq_any(members_of(hospital_id)))
+            .order(guardianships::id.desc())
+            .select(GuardianshipEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn record_switch(
+        &self,
+        guardianship: &GuardianshipEntity,
+        details: serde_json::Value,
+    ) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+
+        audit_logs::record(
+            &mut conn,
+            Some(guardianship.guardian_id),
+            AuditAction::GuardianshipSwitched,
+            Some(guardianship.dependent_id),
+            details,
+        )
+        .await
+    }
+
+    async fn record_delegated_request(
+        &self,
+        guardian_id: i32,
+        dependent_id: i32,
+        details: serde_json::Value,
+    ) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+
+        audit_logs::record(
+            &mut conn,
+            Some(guardian_id),
+            AuditAction::GuardianshipActedOnBehalf,
+            Some(dependent_id),
+            details,
+        )
+        .await
+    }
+}
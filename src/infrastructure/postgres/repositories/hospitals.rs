@@ -0,0 +1,45 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{entities::hospitals::HospitalEntity, repositories::hospitals::HospitalsRepository},
+    infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::hospitals},
+};
+
+pub struct HospitalsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl HospitalsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl HospitalsRepository for HospitalsPostgres {
+    async fn find_by_id(&self, id: i32) -> Result<Option<HospitalEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = hospitals::table
+            .find(id)
+            .select(HospitalEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn find_by_code(&self, code: String) -> Result<Option<HospitalEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = hospitals::table
+            .filter(hospitals::code.eq(code))
+            .select(HospitalEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+}
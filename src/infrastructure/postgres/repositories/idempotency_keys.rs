@@ -0,0 +1,104 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        entities::idempotency_keys::{IdempotencyKeyEntity, InsertIdempotencyKeyEntity},
+        repositories::idempotency_keys::IdempotencyKeysRepository,
+    },
+    infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::idempotency_keys},
+};
+
+pub struct IdempotencyKeysPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl IdempotencyKeysPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyKeysRepository for IdempotencyKeysPostgres {
+    async fn reserve(
+        &self,
+        insert_idempotency_key_entity: InsertIdempotencyKeyEntity,
+    ) -> Result<bool> {
+        let mut conn = self.db_pool.get().await?;
+
+        diesel::delete(idempotency_keys::table)
+            .filter(
+                idempotency_keys::idempotency_key
+                    .eq(&insert_idempotency_key_entity.idempotency_key),
+            )
+            .filter(idempotency_keys::scope.eq(&insert_idempotency_key_entity.scope))
+            .filter(idempotency_keys::expires_at.le(insert_idempotency_key_entity.created_at))
+            .execute(&mut conn)
+            .await?;
+
+        // ชนกับ record ที่ยังไม่หมดอายุ = insert ได้ 0 แถว
+        let inserted = insert_into(idempotency_keys::table)
+            .values(&insert_idempotency_key_entity)
+            .on_conflict((idempotency_keys::idempotency_key, idempotency_keys::scope))
+            .do_nothing()
+            .execute(&mut conn)
+            .await?;
+
+        Ok(inserted == 1)
+    }
+
+    async fn find_active(
+        &self,
+        idempotency_key: String,
+        scope: String,
+    ) -> Result<Option<IdempotencyKeyEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = idempotency_keys::table
+            .filter(idempotency_keys::idempotency_key.eq(idempotency_key))
+            .filter(idempotency_keys::scope.eq(scope))
+            .filter(idempotency_keys::expires_at.gt(chrono::Utc::now().naive_utc()))
+            .select(IdempotencyKeyEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn complete(
+        &self,
+        idempotency_key: String,
+        scope: String,
+        status_code: i32,
+        content_type: Option<String>,
+        response_body: Vec<u8>,
+    ) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        diesel::update(idempotency_keys::table)
+            .filter(idempotency_keys::idempotency_key.eq(idempotency_key))
+            .filter(idempotency_keys::scope.eq(scope))
+            .set((
+                idempotency_keys::status_code.eq(status_code),
+                idempotency_keys::content_type.eq(content_type),
+                idempotency_keys::response_body.eq(response_body),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn release(&self, idempotency_key: String, scope: String) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        diesel::delete(idempotency_keys::table)
+            .filter(idempotency_keys::idempotency_key.eq(idempotency_key))
+            .filter(idempotency_keys::scope.eq(scope))
+            .filter(idempotency_keys::status_code.is_null())
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1 +1,18 @@
+pub mod api_keys;
+pub mod care_assignments;
+pub mod consents;
+pub mod data_exports;
+pub mod doctor_applications;
+pub mod doctor_profiles;
+pub mod emergency_accesses;
+pub mod erasures;
+pub mod guardianships;
+pub mod hospitals;
+pub mod idempotency_keys;
+pub mod notifications;
+pub mod oauth;
+pub mod patient_profiles;
+pub mod permissions;
+pub mod role_grants;
+pub mod sessions;
 pub mod users;
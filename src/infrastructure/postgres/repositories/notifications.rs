@@ -0,0 +1,66 @@
+use anyhow::Result;
+use diesel::{
+    ExpressionMethods, QueryDsl, SelectableHelper,
+    dsl::sql,
+    sql_types::{Nullable, Timestamp},
+};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        entities::notifications::NotificationEntity,
+        repositories::notifications::NotificationsRepository,
+    },
+    infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::notifications},
+};
+
+pub struct NotificationsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl NotificationsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl NotificationsRepository for NotificationsPostgres {
+    async fn list_by_recipient_id(
+        &self,
+        recipient_id: i32,
+        unread_only: bool,
+    ) -> Result<Vec<NotificationEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let mut query = notifications::table
+            .filter(notifications::recipient_id.eq(recipient_id))
+            .into_boxed();
+
+        if unread_only {
+            query = query.filter(notifications::read_at.is_null());
+        }
+
+        let result = query
+            .order((notifications::created_at.desc(), notifications::id.desc()))
+            .select(NotificationEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn mark_read(&self, id: i32, recipient_id: i32) -> Result<bool> {
+        let mut conn = self.db_pool.get().await?;
+
+        let updated = diesel::update(notifications::table)
+            .filter(notifications::id.eq(id))
+            .filter(notifications::recipient_id.eq(recipient_id))
+            // อ่านซ้ำไม่เปลี่ยนเวลาที่อ่านครั้งแรก
+            .set(notifications::read_at.eq(sql::<Nullable<Timestamp>>("COALESCE(read_at, now())")))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(updated > 0)
+    }
+}
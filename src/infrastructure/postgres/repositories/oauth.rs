@@ -0,0 +1,233 @@
+use anyhow::Result;
+use diesel::{
+    ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, dsl::insert_into,
+    upsert::excluded,
+};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        entities::oauth::{
+            InsertOAuthAuthorizationCodeEntity, OAuthAuthorizationCodeEntity, OAuthClientEntity,
+            OAuthConsentEntity, RegisterOAuthClientEntity, RegisterServiceAccountEntity,
+            ServiceAccountEntity, UpsertOAuthConsentEntity,
+        },
+        repositories::oauth::OAuthRepository,
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        schema::{oauth_authorization_codes, oauth_clients, oauth_consents, service_accounts},
+    },
+};
+
+pub struct OAuthPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl OAuthPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl OAuthRepository for OAuthPostgres {
+    async fn register_client(
+        &self,
+        register_client_entity: RegisterOAuthClientEntity,
+    ) -> Result<i32> {
+        let mut conn = self.db_pool.get().await?;
+        let result = insert_into(oauth_clients::table)
+            .values(register_client_entity)
+            .returning(oauth_clients::id)
+            .get_result::<i32>(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_client_by_client_id(&self, client_id: String) -> Result<OAuthClientEntity> {
+        let mut conn = self.db_pool.get().await?;
+        let result = oauth_clients::table
+            .filter(oauth_clients::client_id.eq(client_id))
+            .filter(oauth_clients::deleted_at.is_null())
+            .select(OAuthClientEntity::as_select())
+            .first(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_clients(&self, hospital_id: i32) -> Result<Vec<OAuthClientEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = oauth_clients::table
+            .filter(oauth_clients::hospital_id.eq(hospital_id))
+            .filter(oauth_clients::deleted_at.is_null())
+            .order(oauth_clients::id.asc())
+            .select(OAuthClientEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn remove_client_by_client_id(&self, hospital_id: i32, client_id: String) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        diesel::update(oauth_clients::table)
+            .filter(oauth_clients::client_id.eq(client_id))
+            .filter(oauth_clients::hospital_id.eq(hospital_id))
+            .filter(oauth_clients::deleted_at.is_null())
+            .set((
+                oauth_clients::deleted_at.eq(chrono::Utc::now().naive_utc()),
+                oauth_clients::updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn create_authorization_code(
+        &self,
+        authorization_code_entity: InsertOAuthAuthorizationCodeEntity,
+    ) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        insert_into(oauth_authorization_codes::table)
+            .values(authorization_code_entity)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn consume_authorization_code(
+        &self,
+        code: String,
+    ) -> Result<OAuthAuthorizationCodeEntity> {
+        let mut conn = self.db_pool.get().await?;
+
+        // code ใช้ได้ครั้งเดียว: mark consumed และคืนค่าใน statement เดียวกันกัน race
+        let result = diesel::update(oauth_authorization_codes::table)
+            .filter(oauth_authorization_codes::code.eq(code))
+            .filter(oauth_authorization_codes::consumed_at.is_null())
+            .set(oauth_authorization_codes::consumed_at.eq(chrono::Utc::now().naive_utc()))
+            .returning(OAuthAuthorizationCodeEntity::as_returning())
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_consent(
+        &self,
+        user_id: i32,
+        client_id: String,
+    ) -> Result<Option<OAuthConsentEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = oauth_consents::table
+            .filter(oauth_consents::user_id.eq(user_id))
+            .filter(oauth_consents::client_id.eq(client_id))
+            .filter(oauth_consents::revoked_at.is_null())
+            .select(OAuthConsentEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn upsert_consent(&self, consent_entity: UpsertOAuthConsentEntity) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        insert_into(oauth_consents::table)
+            .values(consent_entity)
+            .on_conflict((oauth_consents::user_id, oauth_consents::client_id))
+            .do_update()
+            .set((
+                oauth_consents::scopes.eq(excluded(oauth_consents::scopes)),
+                oauth_consents::updated_at.eq(excluded(oauth_consents::updated_at)),
+                oauth_consents::revoked_at.eq(None::<chrono::NaiveDateTime>),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_consent(&self, user_id: i32, client_id: String) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        diesel::update(oauth_consents::table)
+            .filter(oauth_consents::user_id.eq(user_id))
+            .filter(oauth_consents::client_id.eq(client_id))
+            .filter(oauth_consents::revoked_at.is_null())
+            .set((
+                oauth_consents::revoked_at.eq(chrono::Utc::now().naive_utc()),
+                oauth_consents::updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn register_service_account(
+        &self,
+        register_service_account_entity: RegisterServiceAccountEntity,
+    ) -> Result<i32> {
+        let mut conn = self.db_pool.get().await?;
+        let result = insert_into(service_accounts::table)
+            .values(register_service_account_entity)
+            .returning(service_accounts::id)
+            .get_result::<i32>(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_service_account_by_client_id(
+        &self,
+        client_id: String,
+    ) -> Result<ServiceAccountEntity> {
+        let mut conn = self.db_pool.get().await?;
+        let result = service_accounts::table
+            .filter(service_accounts::client_id.eq(client_id))
+            .filter(service_accounts::deleted_at.is_null())
+            .select(ServiceAccountEntity::as_select())
+            .first(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_service_accounts(&self, hospital_id: i32) -> Result<Vec<ServiceAccountEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = service_accounts::table
+            .filter(service_accounts::hospital_id.eq(hospital_id))
+            .filter(service_accounts::deleted_at.is_null())
+            .order(service_accounts::id.asc())
+            .select(ServiceAccountEntity::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn remove_service_account_by_client_id(
+        &self,
+        hospital_id: i32,
+        client_id: String,
+    ) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        diesel::update(service_accounts::table)
+            .filter(service_accounts::client_id.eq(client_id))
+            .filter(service_accounts::hospital_id.eq(hospital_id))
+            .filter(service_accounts::deleted_at.is_null())
+            .set((
+                service_accounts::deleted_at.eq(chrono::Utc::now().naive_utc()),
+                service_accounts::updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
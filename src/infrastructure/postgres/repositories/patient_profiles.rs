@@ -0,0 +1,79 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        entities::patient_profiles::{PatientProfileEntity, UpsertPatientProfileEntity},
+        repositories::patient_profiles::PatientProfilesRepository,
+    },
+    infrastructure::postgres::{
+        errors::map_constraint_violation, postgres_connection::PgPoolSquad,
+        schema::patient_profiles,
+    },
+};
+
+pub struct PatientProfilesPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl PatientProfilesPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PatientProfilesRepository for PatientProfilesPostgres {
+    async fn create(
+        &self,
+        upsert_patient_profile_entity: UpsertPatientProfileEntity,
+    ) -> Result<PatientProfileEntity> {
+        let mut conn = self.db_pool.get().await?;
+        let result = insert_into(patient_profiles::table)
+            .values(upsert_patient_profile_entity)
+            .returning(PatientProfileEntity::as_returning())
+            .get_result(&mut conn)
+            .await
+            .map_err(map_constraint_violation)?;
+
+        Ok(result)
+    }
+
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Option<PatientProfileEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = patient_profiles::table
+            .find(user_id)
+            .select(PatientProfileEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn update(
+        &self,
+        upsert_patient_profile_entity: UpsertPatientProfileEntity,
+    ) -> Result<Option<PatientProfileEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = diesel::update(patient_profiles::table)
+            .filter(patient_profiles::user_id.eq(upsert_patient_profile_entity.user_id))
+            .set(&upsert_patient_profile_entity)
+            .returning(PatientProfileEntity::as_returning())
+            .get_result(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn delete_by_user_id(&self, user_id: i32) -> Result<bool> {
+        let mut conn = self.db_pool.get().await?;
+        let deleted = diesel::delete(patient_profiles::table.find(user_id))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted > 0)
+    }
+}
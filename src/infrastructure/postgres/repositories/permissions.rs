@@ -0,0 +1,94 @@
+use anyhow::Result;
+use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        repositories::permissions::PermissionsRepository, value_objects::policy::RolePermission,
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        schema::{
+            care_assignments, emergency_accesses, permissions, role_permissions, roles, user_roles,
+        },
+    },
+};
+
+pub struct PermissionsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl PermissionsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PermissionsRepository for PermissionsPostgres {
+    async fn find_by_user_id(&self, hospital_id: i32, user_id: i32) -> Result<Vec<RolePermission>> {
+        let mut conn = self.db_pool.get().await?;
+        let now = chrono::Utc::now().naive_utc();
+
+        let rows: Vec<(String, String)> = user_roles::table
+            .inner_join(
+                roles::table.inner_join(role_permissions::table.inner_join(permissions::table)),
+            )
+            .filter(user_roles::hospital_id.eq(hospital_id))
+            .filter(user_roles::user_id.eq(user_id))
+            .filter(
+                user_roles::valid_from
+                    .is_null()
+                    .or(user_roles::valid_from.le(now)),
+            )
+            .filter(
+                user_roles::valid_until
+                    .is_null()
+                    .or(user_roles::valid_until.gt(now)),
+            )
+            .order((roles::id.asc(), permissions::id.asc()))
+            .select((roles::name, permissions::name))
+            .load(&mut conn)
+            .await?;
+
+        // permission ที่เพิ่มใน DB แต่โค้ดยังไม่รู้จักไม่มีผลกับการตัดสิน
+        Ok(rows
+            .into_iter()
+            .filter_map(|(role, permission)| {
+                Some(RolePermission {
+                    role: role.parse().ok()?,
+                    permission: permission.parse().ok()?,
+                })
+            })
+            .collect())
+    }
+
+    async fn find_care_patient_ids(&self, hospital_id: i32, user_id: i32) -> Result<Vec<i32>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = care_assignments::table
+            .filter(care_assignments::hospital_id.eq(hospital_id))
+            .filter(care_assignments::doctor_id.eq(user_id))
+            .filter(care_assignments::ended_at.is_null())
+            .select(care_assignments::patient_id)
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_emergency_patient_ids(&self, hospital_id: i32, user_id: i32) -> Result<Vec<i32>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = emergency_accesses::table
+            .filter(emergency_accesses::hospital_id.eq(hospital_id))
+            .filter(emergency_accesses::user_id.eq(user_id))
+            .filter(emergency_accesses::expires_at.gt(chrono::Utc::now().naive_utc()))
+            .select(emergency_accesses::patient_id)
+            .distinct()
+            .load(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, dsl::insert_into};
+use diesel_async::{
+    AsyncConnection, AsyncPgConnection, RunQueryDsl, scoped_futures::ScopedFutureExt,
+};
+
+use crate::{
+    domain::{
+        entities::{notifications::InsertNotificationEntity, roles::UserRoleEntity},
+        repositories::role_grants::RoleGrantsRepository,
+        value_objects::{
+            audit_logs_model::AuditAction, notifications_model::NotificationKind, roles::Role,
+        },
+    },
+    infrastructure::postgres::{
+        audit_logs,
+        postgres_connection::PgPoolSquad,
+        role_grants,
+        schema::{notifications, roles, sessions, user_roles, users},
+    },
+};
+
+pub struct RoleGrantsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl RoleGrantsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl RoleGrantsRepository for RoleGrantsPostgres {
+    async fn notify_expiring(
+        &self,
+        now: NaiveDateTime,
+        notice_until: NaiveDateTime,
+    ) -> Result<usize> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                // UPDATE ... RETURNING จองแถวไว้ ถ้ามีหลาย instance จะไม่แจ้งซ้ำ
+                let expiring = diesel::update(
+                    user_roles::table
+                        .filter(user_roles::valid_until.gt(now))
+                        .filter(user_roles::valid_until.le(notice_until))
+                        .filter(user_roles::expiry_notified_at.is_null())
+                        .filter(user_roles::expired_at.is_null()),
+                )
+                .set(user_roles::expiry_notified_at.eq(now))
+                .returning(UserRoleEntity::as_returning())
+                .get_results::<UserRoleEntity>(conn)
+                .await?;
+
+                if expiring.is_empty() {
+                    return Ok(0);
+                }
+
+                let role_names = role_names(conn, &expiring).await?;
+
+                // แจ้งเฉพาะ admin ของโรงพยาบาลที่ grant นั้นอยู่
+                let mut admin_ids_by_hospital: HashMap<i32, Vec<i32>> = HashMap::new();
+                for grant in &expiring {
+                    if admin_ids_by_hospital.contains_key(&grant.hospital_id) {
+                        continue;
+                    }
+                    let admin_ids: Vec<i32> = users::table
+                        .filter(
+                            users::id
+                                .eq_any(role_grants::holders_of(grant.hospital_id, Role::Admin)),
+                        )
+                        .filter(users::deleted_at.is_null())
+                        .select(users::id)
+                        .load(conn)
+                        .await?;
+                    admin_ids_by_hospital.insert(grant.hospital_id, admin_ids);
+                }
+
+                let mut rows = Vec::new();
+                for grant in &expiring {
+                    let admin_ids = &admin_ids_by_hospital[&grant.hospital_id];
+                    let mut recipient_ids = admin_ids.clone();
+                    if let Some(granted_by) = grant.granted_by.filter(|id| !admin_ids.contains(id))
+                    {
+                        recipient_ids.push(granted_by);
+                    }
+
+                    for recipient_id in recipient_ids {
+                        rows.push(InsertNotificationEntity {
+                            recipient_id,
+                            kind: NotificationKind::RoleGrantExpiring.to_string(),
+                            subject_user_id: Some(grant.user_id),
+                            payload: serde_json::json!({
+                                "role": role_names.get(&grant.role_id),
+                                "hospital_id": grant.hospital_id,
+                                "valid_until": grant.valid_until,
+                            }),
+                            created_at: now,
+                            priority: NotificationKind::RoleGrantExpiring.priority().to_string(),
+                        });
+                    }
+                }
+
+                insert_into(notifications::table)
+                    .values(rows)
+                    .execute(conn)
+                    .await?;
+
+                Ok(expiring.len())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn expire_due(&self, now: NaiveDateTime) -> Result<(usize, usize)> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let expired = diesel::update(
+                    user_roles::table
+                        .filter(user_roles::valid_until.le(now))
+                        .filter(user_roles::expired_at.is_null()),
+                )
+                .set(user_roles::expired_at.eq(now))
+                .returning(UserRoleEntity::as_returning())
+                .get_results::<UserRoleEntity>(conn)
+                .await?;
+
+                if expired.is_empty() {
+                    return Ok((0, 0));
+                }
+
+                let mut user_ids: Vec<i32> = expired.iter().map(|grant| grant.user_id).collect();
+                user_ids.sort_unstable();
+                user_ids.dedup();
+
+                // session ไม่ได้ผูกกับ role จึง revoke ทั้งหมดของ user ให้ login ใหม่ด้วย role ที่เหลือ
+                let revoked_sessions = diesel::update(
+                    sessions::table
+                        .filter(sessions::user_id.eq_any(user_ids))
+                        .filter(sessions::revoked_at.is_null())
+                        .filter(sessions::expires_at.gt(now)),
+                )
+                .set(sessions::revoked_at.eq(now))
+                .execute(conn)
+                .await?;
+
+                let role_names = role_names(conn, &expired).await?;
+                for grant in &expired {
+                    audit_logs::record(
+                        conn,
+                        None,
+                        AuditAction::RoleGrantExpired,
+                        Some(grant.user_id),
+                        serde_json::json!({
+                            "role": role_names.get(&grant.role_id),
+                            "hospital_id": grant.hospital_id,
+                            "valid_until": grant.valid_until,
+                            "granted_by": grant.granted_by,
+                        }),
+                    )
+                    .await?;
+                }
+
+                Ok((expired.len(), revoked_sessions))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
+
+async fn role_names(
+    conn: &mut AsyncPgConnection,
+    grants: &[UserRoleEntity],
+) -> Result<HashMap<i32, String>> {
+    let role_ids: Vec<i32> = grants.iter().map(|grant| grant.role_id).collect();
+
+    let rows: Vec<(i32, String)> = roles::table
+        .filter(roles::id.eq_any(role_ids))
+        .select((roles::id, roles::name))
+        .load(conn)
+        .await?;
+
+    Ok(rows.into_iter().collect())
+}
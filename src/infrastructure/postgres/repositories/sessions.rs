@@ -0,0 +1,61 @@
+use anyhow::Result;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+
+use crate::{
+    domain::{
+        entities::sessions::{InsertSessionEntity, SessionEntity},
+        repositories::sessions::SessionsRepository,
+    },
+    infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::sessions},
+};
+
+pub struct SessionsPostgres {
+    db_pool: PgPoolSquad,
+}
+
+impl SessionsPostgres {
+    pub fn new(db_pool: PgPoolSquad) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionsRepository for SessionsPostgres {
+    async fn create(&self, insert_session_entity: InsertSessionEntity) -> Result<String> {
+        let mut conn = self.db_pool.get().await?;
+        let result = insert_into(sessions::table)
+            .values(insert_session_entity)
+            .returning(sessions::id)
+            .get_result::<String>(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_active_by_id(&self, id: String) -> Result<Option<SessionEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = sessions::table
+            .filter(sessions::id.eq(id))
+            .filter(sessions::revoked_at.is_null())
+            .filter(sessions::expires_at.gt(chrono::Utc::now().naive_utc()))
+            .select(SessionEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn revoke_by_id(&self, id: String) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+        diesel::update(sessions::table)
+            .filter(sessions::id.eq(id))
+            .filter(sessions::revoked_at.is_null())
+            .set(sessions::revoked_at.eq(chrono::Utc::now().naive_utc()))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,18 +1,44 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime};
 use diesel::{
-    ExpressionMethods, QueryDsl,
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, PgArrayExpressionMethods,
+    PgTextExpressionMethods, QueryDsl, QueryableByName, SelectableHelper,
     dsl::insert_into,
-    sql_types::{Integer, Text},
+    sql_types::{BigInt, Float4, Integer, Text},
+};
+use diesel_async::{
+    AsyncConnection, AsyncPgConnection, RunQueryDsl, scoped_futures::ScopedFutureExt,
 };
-use diesel_async::RunQueryDsl;
 
 use crate::{
     domain::{
         entities::users::{RegisterUserEntity, UserEntity},
         repositories::users::UsersRepository,
-        value_objects::roles::Roles,
+        value_objects::{
+            audit_logs_model::AuditAction,
+            consents_model::RegistrationConsents,
+            pagination::SortDirection,
+            roles::{Role, RoleGrantModel},
+            users_model::{
+                RegisterUserResponseModel, UserCursorKey, UserListFilter, UserSearchHit,
+                UserSortField,
+            },
+        },
+    },
+    infrastructure::{
+        field_encryption,
+        postgres::{
+            audit_logs, consents,
+            errors::map_constraint_violation,
+            hospital_memberships,
+            postgres_connection::PgPoolSquad,
+            role_grants,
+            schema::{hospital_memberships as memberships, patient_profiles, users},
+        },
+        transliteration,
     },
-    infrastructure::postgres::{postgres_connection::PgPoolSquad, schema::users},
 };
 
 pub struct UsersPostgres {
@@ -27,22 +53,314 @@ impl UsersPostgres {
 
 #[async_trait::async_trait]
 impl UsersRepository for UsersPostgres {
-    async fn register(&self, register_user_entity: RegisterUserEntity) -> Result<i32> {
+    async fn register(
+        &self,
+        hospital_id: i32,
+        mut register_user_entity: RegisterUserEntity,
+        role: Role,
+        registered_by: Option<i32>,
+        registration_consents: RegistrationConsents,
+    ) -> Result<RegisterUserResponseModel> {
+        let citizen_id = register_user_entity.citizen_id.clone();
+        let phone_number = register_user_entity.phone_number.clone();
+
+        register_user_entity.citizen_id_hash = Some(field_encryption::blind_index(&citizen_id)?);
+        register_user_entity.citizen_id_prefix_hashes =
+            field_encryption::citizen_id_prefix_indexes(&citizen_id)?;
+        register_user_entity.phone_number_hash =
+            Some(field_encryption::blind_index(&phone_number)?);
+        register_user_entity.name_romanized = transliteration::romanize(&format!(
+            "{} {}",
+            register_user_entity.first_name, register_user_entity.last_name
+        ));
+        register_user_entity.citizen_id = field_encryption::encrypt(&citizen_id)?;
+        register_user_entity.phone_number = field_encryption::encrypt(&phone_number)?;
+
         let mut conn = self.db_pool.get().await?;
-        let result = insert_into(users::table)
-            .values(register_user_entity)
-            .returning(users::id)
-            .get_result::<i32>(&mut conn)
-            .await?;
 
-        Ok(result)
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let user_id = insert_into(users::table)
+                    .values(register_user_entity)
+                    .returning(users::id)
+                    .get_result::<i32>(conn)
+                    .await
+                    .map_err(map_constraint_violation)?;
+
+                register_at_hospital(
+                    conn,
+                    hospital_id,
+                    user_id,
+                    role,
+                    registered_by,
+                    &registration_consents,
+                )
+                .await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn register_existing(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        role: Role,
+        registered_by: Option<i32>,
+        registration_consents: RegistrationConsents,
+    ) -> Result<Option<RegisterUserResponseModel>> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let is_member = memberships::table
+                    .filter(memberships::hospital_id.eq(hospital_id))
+                    .filter(memberships::user_id.eq(user_id))
+                    .select(memberships::user_id)
+                    .first::<i32>(conn)
+                    .await
+                    .optional()?
+                    .is_some();
+                if is_member {
+                    return Ok(None);
+                }
+
+                register_at_hospital(
+                    conn,
+                    hospital_id,
+                    user_id,
+                    role,
+                    registered_by,
+                    &registration_consents,
+                )
+                .await
+                .map(Some)
+            }
+            .scope_boxed()
+        })
+        .await
     }
     async fn find_by_id(&self, id: i32) -> Result<UserEntity> {
         let mut conn = self.db_pool.get().await?;
-        let result = users::table.find(id).get_result(&mut conn).await?;
+        let result = users::table
+            .find(id)
+            .select(UserEntity::as_select())
+            .get_result(&mut conn)
+            .await?;
+
+        decrypt_user(result)
+    }
+
+    async fn find_by_citizen_id(&self, citizen_id: String) -> Result<Option<UserEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = users::table
+            .filter(users::citizen_id_hash.eq(field_encryption::blind_index(&citizen_id)?))
+            .select(UserEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        result.map(decrypt_user).transpose()
+    }
+
+    async fn find_date_of_birth(&self, user_id: i32) -> Result<Option<NaiveDate>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = patient_profiles::table
+            .find(user_id)
+            .select(patient_profiles::date_of_birth)
+            .first::<NaiveDate>(&mut conn)
+            .await
+            .optional()?;
+
         Ok(result)
     }
 
+    async fn find_by_hospital_number(
+        &self,
+        hospital_id: i32,
+        hospital_number: String,
+    ) -> Result<Option<UserEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let result = users::table
+            .inner_join(memberships::table)
+            .filter(memberships::hospital_id.eq(hospital_id))
+            .filter(memberships::hospital_number.eq(hospital_number))
+            .select(UserEntity::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        result.map(decrypt_user).transpose()
+    }
+
+    async fn find_hospital_numbers(
+        &self,
+        hospital_id: i32,
+        user_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, String>> {
+        let mut conn = self.db_pool.get().await?;
+
+        hospital_memberships::find_hospital_numbers(&mut conn, hospital_id, user_ids).await
+    }
+
+    async fn list(&self, filter: UserListFilter) -> Result<Vec<UserEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let mut query = users::table
+            .filter(users::id.eq_any(hospital_memberships::members_of(filter.hospital_id)))
+            .select(UserEntity::as_select())
+            .into_boxed();
+
+        query = if filter.deleted {
+            query.filter(users::deleted_at.is_not_null())
+        } else {
+            query.filter(users::deleted_at.is_null())
+        };
+
+        if let Some(role) = filter.role {
+            query =
+                query.filter(users::id.eq_any(role_grants::holders_of(filter.hospital_id, role)));
+        }
+        if let Some(created_from) = filter.created_from {
+            query = query.filter(users::created_at.ge(created_from));
+        }
+        if let Some(created_to) = filter.created_to {
+            query = query.filter(users::created_at.lt(created_to));
+        }
+        if let Some(name) = filter.name {
+            let pattern = format!("%{}%", escape_like(&name));
+            query = query.filter(
+                users::first_name
+                    .ilike(pattern.clone())
+                    .or(users::last_name.ilike(pattern)),
+            );
+        }
+        if let Some(phone_number) = filter.phone_number {
+            query = query
+                .filter(users::phone_number_hash.eq(field_encryption::blind_index(&phone_number)?));
+        }
+        if let Some(citizen_id_prefix) = filter.citizen_id_prefix {
+            query = query.filter(users::citizen_id_prefix_hashes.contains(vec![
+                field_encryption::citizen_id_prefix_index(&citizen_id_prefix)?,
+            ]));
+        }
+
+        // id เป็นตัวตัดสินเสมอ เพื่อให้ลำดับคงที่เมื่อค่าที่ sort ซ้ำกัน
+        query = match (filter.sort, filter.direction) {
+            (UserSortField::Id, SortDirection::Asc) => query.order(users::id.asc()),
+            (UserSortField::Id, SortDirection::Desc) => query.order(users::id.desc()),
+            (UserSortField::CreatedAt, SortDirection::Asc) => {
+                query.order((users::created_at.asc(), users::id.asc()))
+            }
+            (UserSortField::CreatedAt, SortDirection::Desc) => {
+                query.order((users::created_at.desc(), users::id.desc()))
+            }
+            (UserSortField::FirstName, SortDirection::Asc) => {
+                query.order((users::first_name.asc(), users::id.asc()))
+            }
+            (UserSortField::FirstName, SortDirection::Desc) => {
+                query.order((users::first_name.desc(), users::id.desc()))
+            }
+            (UserSortField::LastName, SortDirection::Asc) => {
+                query.order((users::last_name.asc(), users::id.asc()))
+            }
+            (UserSortField::LastName, SortDirection::Desc) => {
+                query.order((users::last_name.desc(), users::id.desc()))
+            }
+        };
+
+        if let Some(after) = filter.after {
+            let id = after.id;
+            query = match (after.key, filter.direction) {
+                (UserCursorKey::Id, SortDirection::Asc) => query.filter(users::id.gt(id)),
+                (UserCursorKey::Id, SortDirection::Desc) => query.filter(users::id.lt(id)),
+                (UserCursorKey::CreatedAt(created_at), SortDirection::Asc) => query.filter(
+                    users::created_at
+                        .gt(created_at)
+                        .or(users::created_at.eq(created_at).and(users::id.gt(id))),
+                ),
+                (UserCursorKey::CreatedAt(created_at), SortDirection::Desc) => query.filter(
+                    users::created_at
+                        .lt(created_at)
+                        .or(users::created_at.eq(created_at).and(users::id.lt(id))),
+                ),
+                (UserCursorKey::FirstName(first_name), SortDirection::Asc) => query.filter(
+                    users::first_name
+                        .gt(first_name.clone())
+                        .or(users::first_name.eq(first_name).and(users::id.gt(id))),
+                ),
+                (UserCursorKey::FirstName(first_name), SortDirection::Desc) => query.filter(
+                    users::first_name
+                        .lt(first_name.clone())
+                        .or(users::first_name.eq(first_name).and(users::id.lt(id))),
+                ),
+                (UserCursorKey::LastName(last_name), SortDirection::Asc) => query.filter(
+                    users::last_name
+                        .gt(last_name.clone())
+                        .or(users::last_name.eq(last_name).and(users::id.gt(id))),
+                ),
+                (UserCursorKey::LastName(last_name), SortDirection::Desc) => query.filter(
+                    users::last_name
+                        .lt(last_name.clone())
+                        .or(users::last_name.eq(last_name).and(users::id.lt(id))),
+                ),
+            };
+        }
+
+        let result = query.limit(filter.limit).load(&mut conn).await?;
+
+        result.into_iter().map(decrypt_user).collect()
+    }
+
+    async fn search_by_name(
+        &self,
+        hospital_id: i32,
+        name: String,
+        name_romanized: Option<String>,
+        limit: i64,
+    ) -> Result<Vec<UserSearchHit>> {
+        let mut conn = self.db_pool.get().await?;
+
+        // `%` ใช้ GIN trigram index ได้ ส่วน similarity() ใช้จัดอันดับ
+        let rows = diesel::sql_query(
+            r#"
+            SELECT users.*,
+                GREATEST(
+                    similarity(first_name, $1),
+                    similarity(last_name, $1),
+                    similarity(first_name || ' ' || last_name, $1),
+                    similarity(name_romanized, $2)
+                ) AS score
+            FROM users
+            WHERE deleted_at IS NULL
+                AND id IN (SELECT user_id FROM hospital_memberships WHERE hospital_id = $4)
+                AND (
+                    first_name % $1
+                    OR last_name % $1
+                    OR (first_name || ' ' || last_name) % $1
+                    OR ($2 <> '' AND name_romanized % $2)
+                )
+            ORDER BY score DESC, id ASC
+            LIMIT $3
+        "#,
+        )
+        .bind::<Text, _>(name)
+        .bind::<Text, _>(name_romanized.unwrap_or_default())
+        .bind::<BigInt, _>(limit)
+        .bind::<Integer, _>(hospital_id)
+        .load::<UserSearchRow>(&mut conn)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(UserSearchHit {
+                    user: decrypt_user(row.user)?,
+                    score: row.score,
+                })
+            })
+            .collect()
+    }
+
     async fn remove_by_id(&self, id: i32) -> Result<()> {
         let mut conn = self.db_pool.get().await?;
         diesel::update(users::table)
@@ -54,47 +372,113 @@ impl UsersRepository for UsersPostgres {
         Ok(())
     }
 
-    async fn add_role_to_user_by_id(&self, role: Roles, id: i32) -> Result<()> {
+    async fn find_roles(&self, hospital_id: i32, user_id: i32) -> Result<Vec<Role>> {
         let mut conn = self.db_pool.get().await?;
-        let role_str = role.to_string();
 
-        diesel::sql_query(
-            r#"
-            UPDATE users
-            SET role = CASE
-                WHEN NOT ($1 = ANY(role)) THEN array_append(role, $1)
-                ELSE role
-            END,
-                updated_at = NOW()
-            WHERE id = $2
-        "#,
+        role_grants::find_roles(&mut conn, hospital_id, user_id).await
+    }
+
+    async fn find_roles_by_user_ids(
+        &self,
+        hospital_id: i32,
+        user_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, Vec<Role>>> {
+        let mut conn = self.db_pool.get().await?;
+
+        role_grants::find_roles_by_user_ids(&mut conn, hospital_id, user_ids).await
+    }
+
+    async fn grant_role(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+        role: Role,
+        granted_by: Option<i32>,
+        valid_from: Option<NaiveDateTime>,
+        valid_until: Option<NaiveDateTime>,
+    ) -> Result<()> {
+        let mut conn = self.db_pool.get().await?;
+
+        role_grants::grant_role(
+            &mut conn,
+            hospital_id,
+            user_id,
+            role,
+            granted_by,
+            valid_from,
+            valid_until,
         )
-        .bind::<Text, _>(role_str)
-        .bind::<Integer, _>(id)
-        .execute(&mut conn)
-        .await?;
+        .await
+    }
 
-        Ok(())
+    async fn find_role_grants(
+        &self,
+        hospital_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<RoleGrantModel>> {
+        let mut conn = self.db_pool.get().await?;
+
+        role_grants::find_grants(&mut conn, hospital_id, user_id).await
     }
 
-    async fn remove_role_from_user_by_id(&self, role: Roles, id: i32) -> Result<()> {
+    async fn revoke_role(&self, hospital_id: i32, user_id: i32, role: Role) -> Result<bool> {
         let mut conn = self.db_pool.get().await?;
-        let role_str = role.to_string();
 
-        // ลบทุก occurrence ของค่านั้นในอาเรย์
-        diesel::sql_query(
-            r#"
-            UPDATE users
-            SET role = array_remove(role, $1),
-                updated_at = NOW()
-            WHERE id = $2
-        "#,
+        role_grants::revoke_role(&mut conn, hospital_id, user_id, role).await
+    }
+}
+
+#[derive(QueryableByName)]
+struct UserSearchRow {
+    #[diesel(embed)]
+    user: UserEntity,
+    #[diesel(sql_type = Float4)]
+    score: f32,
+}
+
+/// Gives the user the hospital's next hospital number, grants `role` and records the
+/// registration consents.
+async fn register_at_hospital(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+    user_id: i32,
+    role: Role,
+    registered_by: Option<i32>,
+    registration_consents: &RegistrationConsents,
+) -> Result<RegisterUserResponseModel> {
+    let hospital_number =
+        hospital_memberships::ensure_membership(conn, hospital_id, user_id).await?;
+    role_grants::grant_role(conn, hospital_id, user_id, role, registered_by, None, None).await?;
+    consents::accept_at_registration(conn, user_id, registration_consents, registered_by).await?;
+
+    if let Some(actor_id) = registered_by {
+        audit_logs::record(
+            conn,
+            Some(actor_id),
+            AuditAction::UserRegisteredOnBehalf,
+            Some(user_id),
+            serde_json::json!({ "role": role, "hospital_id": hospital_id }),
         )
-        .bind::<Text, _>(role_str)
-        .bind::<Integer, _>(id)
-        .execute(&mut conn)
         .await?;
-
-        Ok(())
     }
+
+    Ok(RegisterUserResponseModel {
+        user_id,
+        hospital_number,
+    })
+}
+
+fn decrypt_user(mut user: UserEntity) -> Result<UserEntity> {
+    user.citizen_id = field_encryption::decrypt(&user.citizen_id)?;
+    user.phone_number = field_encryption::decrypt(&user.phone_number)?;
+
+    Ok(user)
+}
+
+/// Escapes `%`, `_` and `\` so user input is matched literally by LIKE.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
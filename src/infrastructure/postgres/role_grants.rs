@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, QueryDsl, SelectableHelper, dsl::insert_into, pg::Pg,
+    sql_types::Integer,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::{
+    domain::{
+        entities::roles::{InsertUserRoleEntity, UserRoleEntity},
+        value_objects::roles::{Role, RoleGrantModel},
+    },
+    infrastructure::postgres::{
+        hospital_memberships,
+        schema::{roles, user_roles},
+    },
+};
+
+/// Ids of users currently holding `role` at the hospital, for use as
+/// `users::id.eq_any(holders_of(hospital_id, role))`.
+pub fn holders_of(hospital_id: i32, role: Role) -> user_roles::BoxedQuery<'static, Pg, Integer> {
+    let now = chrono::Utc::now().naive_utc();
+
+    user_roles::table
+        .filter(user_roles::hospital_id.eq(hospital_id))
+        .filter(
+            user_roles::role_id.eq_any(
+                roles::table
+                    .filter(roles::name.eq(role.to_string()))
+                    .select(roles::id),
+            ),
+        )
+        .filter(
+            user_roles::valid_from
+                .is_null()
+                .or(user_roles::valid_from.le(now)),
+        )
+        .filter(
+            user_roles::valid_until
+                .is_null()
+                .or(user_roles::valid_until.gt(now)),
+        )
+        .select(user_roles::user_id)
+        .into_boxed()
+}
+
+/// Roles the user currently holds at the hospital. Grants that have not started or have ended
+/// are left out.
+pub async fn find_roles(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+    user_id: i32,
+) -> Result<Vec<Role>> {
+    let now = chrono::Utc::now().naive_utc();
+
+    let names: Vec<String> = user_roles::table
+        .inner_join(roles::table)
+        .filter(user_roles::hospital_id.eq(hospital_id))
+        .filter(user_roles::user_id.eq(user_id))
+        .filter(
+            user_roles::valid_from
+                .is_null()
+                .or(user_roles::valid_from.le(now)),
+        )
+        .filter(
+            user_roles::valid_until
+                .is_null()
+                .or(user_roles::valid_until.gt(now)),
+        )
+        .order(roles::id.asc())
+        .select(roles::name)
+        .load(conn)
+        .await?;
+
+    Ok(names.iter().filter_map(|name| name.parse().ok()).collect())
+}
+
+/// `find_roles` for a page of users in one query; users without roles are absent.
+pub async fn find_roles_by_user_ids(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+    user_ids: Vec<i32>,
+) -> Result<HashMap<i32, Vec<Role>>> {
+    let now = chrono::Utc::now().naive_utc();
+
+    let rows: Vec<(i32, String)> = user_roles::table
+        .inner_join(roles::table)
+        .filter(user_roles::hospital_id.eq(hospital_id))
+        .filter(user_roles::user_id.eq_any(user_ids))
+        .filter(
+            user_roles::valid_from
+                .is_null()
+                .or(user_roles::valid_from.le(now)),
+        )
+        .filter(
+            user_roles::valid_until
+                .is_null()
+                .or(user_roles::valid_until.gt(now)),
+        )
+        .order((user_roles::user_id.asc(), roles::id.asc()))
+        .select((user_roles::user_id, roles::name))
+        .load(conn)
+        .await?;
+
+    let mut result: HashMap<i32, Vec<Role>> = HashMap::new();
+    for (user_id, name) in rows {
+        // role ที่ย้ายมาจากข้อมูลเก่าแต่ไม่มีใน enum ถูกข้ามไป
+        if let Ok(role) = name.parse() {
+            result.entry(user_id).or_default().push(role);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Grants at the hospital not yet ended, including ones starting in the future, in role order.
+pub async fn find_grants(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+    user_id: i32,
+) -> Result<Vec<RoleGrantModel>> {
+    let now = chrono::Utc::now().naive_utc();
+
+    let rows: Vec<(String, UserRoleEntity)> = user_roles::table
+        .inner_join(roles::table)
+        .filter(user_roles::hospital_id.eq(hospital_id))
+        .filter(user_roles::user_id.eq(user_id))
+        .filter(
+            user_roles::valid_until
+                .is_null()
+                .or(user_roles::valid_until.gt(now)),
+        )
+        .order(roles::id.asc())
+        .select((roles::name, UserRoleEntity::as_select()))
+        .load(conn)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(name, grant)| {
+            Some(RoleGrantModel {
+                role: name.parse().ok()?,
+                granted_by: grant.granted_by,
+                granted_at: grant.granted_at,
+                valid_from: grant.valid_from,
+                valid_until: grant.valid_until,
+            })
+        })
+        .collect())
+}
+
+/// Grants `role`, or replaces `granted_by`, `granted_at` and the validity window when the user
+/// already holds it. Replacing the window re-arms the expiry notice. Users not yet registered
+/// at the hospital are given a hospital number there first.
+pub async fn grant_role(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+    user_id: i32,
+    role: Role,
+    granted_by: Option<i32>,
+    valid_from: Option<NaiveDateTime>,
+    valid_until: Option<NaiveDateTime>,
+) -> Result<()> {
+    let role_id = roles::table
+        .filter(roles::name.eq(role.to_string()))
+        .select(roles::id)
+        .first::<i32>(conn)
+        .await?;
+    hospital_memberships::ensure_membership(conn, hospital_id, user_id).await?;
+    let now = chrono::Utc::now().naive_utc();
+
+    insert_into(user_roles::table)
+        .values(InsertUserRoleEntity {
+            hospital_id,
+            user_id,
+            role_id,
+            granted_by,
+            granted_at: now,
+            valid_until,
+            valid_from,
+        })
+        .on_conflict((
+            user_roles::hospital_id,
+            user_roles::user_id,
+            user_roles::role_id,
+        ))
+        .do_update()
+        .set((
+            user_roles::granted_by.eq(granted_by),
+            user_roles::granted_at.eq(now),
+            user_roles::valid_from.eq(valid_from),
+            user_roles::valid_until.eq(valid_until),
+            user_roles::expiry_notified_at.eq(None::<NaiveDateTime>),
+            user_roles::expired_at.eq(None::<NaiveDateTime>),
+        ))
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Returns `false` when the user did not hold the role at the hospital.
+pub async fn revoke_role(
+    conn: &mut AsyncPgConnection,
+    hospital_id: i32,
+    user_id: i32,
+    role: Role,
+) -> Result<bool> {
+    let deleted = diesel::delete(
+        user_roles::table
+            .filter(user_roles::hospital_id.eq(hospital_id))
+            .filter(user_roles::user_id.eq(user_id))
+            .filter(
+                user_roles::role_id.eq_any(
+                    roles::table
+                        .filter(roles::name.eq(role.to_string()))
+                        .select(roles::id),
+                ),
+            ),
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(deleted > 0)
+}
@@ -1,21 +1,496 @@
 // @generated automatically by Diesel CLI.
 
 diesel::table! {
-    users (id) {
+    api_keys (id) {
+        id -> Int4,
+        #[max_length = 16]
+        prefix -> Varchar,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        #[max_length = 100]
+        name -> Varchar,
+        scopes -> Array<Varchar>,
+        allowed_ips -> Array<Varchar>,
+        created_by -> Nullable<Int4>,
+        expires_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        hospital_id -> Int4,
+    }
+}
+
+diesel::table! {
+    audit_logs (id) {
+        id -> Int4,
+        actor_id -> Nullable<Int4>,
+        #[max_length = 100]
+        action -> Varchar,
+        subject_user_id -> Nullable<Int4>,
+        details -> Jsonb,
+        created_at -> Timestamp,
+        #[max_length = 8]
+        priority -> Varchar,
+    }
+}
+
+diesel::table! {
+    care_assignments (id) {
+        id -> Int4,
+        hospital_id -> Int4,
+        doctor_id -> Int4,
+        patient_id -> Int4,
+        assigned_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+        ended_by -> Nullable<Int4>,
+        ended_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    consent_purposes (id) {
+        id -> Int4,
+        #[max_length = 64]
+        code -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        description -> Text,
+        required_at_registration -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    consent_records (id) {
+        id -> Int4,
+        user_id -> Int4,
+        consent_version_id -> Int4,
+        #[max_length = 16]
+        action -> Varchar,
+        #[max_length = 16]
+        channel -> Varchar,
+        recorded_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    consent_versions (id) {
+        id -> Int4,
+        purpose_id -> Int4,
+        version -> Int4,
+        text -> Text,
+        effective_from -> Timestamp,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    data_exports (id) {
+        #[max_length = 64]
+        id -> Varchar,
+        user_id -> Int4,
+        #[max_length = 8]
+        format -> Varchar,
+        #[max_length = 16]
+        status -> Varchar,
+        content -> Nullable<Bytea>,
+        error -> Nullable<Text>,
+        created_at -> Timestamp,
+        completed_at -> Nullable<Timestamp>,
+        expires_at -> Timestamp,
+        claimed_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    doctor_application_transitions (id) {
+        id -> Int4,
+        application_id -> Int4,
+        #[max_length = 16]
+        from_status -> Nullable<Varchar>,
+        #[max_length = 16]
+        to_status -> Varchar,
+        actor_id -> Int4,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    doctor_applications (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 32]
+        license_number -> Varchar,
+        specialties -> Array<Varchar>,
+        #[max_length = 100]
+        department -> Nullable<Varchar>,
+        documents -> Jsonb,
+        #[max_length = 16]
+        status -> Varchar,
+        review_notes -> Nullable<Text>,
+        reviewed_by -> Nullable<Int4>,
+        reviewed_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        hospital_id -> Int4,
+    }
+}
+
+diesel::table! {
+    doctor_profiles (user_id) {
+        user_id -> Int4,
+        #[max_length = 32]
+        license_number -> Varchar,
+        specialties -> Array<Varchar>,
+        languages -> Array<Varchar>,
+        #[max_length = 100]
+        department -> Nullable<Varchar>,
+        bio -> Nullable<Text>,
+        #[max_length = 255]
+        photo_url -> Nullable<Varchar>,
+        verified_at -> Nullable<Timestamp>,
+        verified_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    emergency_accesses (id) {
+        id -> Int4,
+        hospital_id -> Int4,
+        user_id -> Int4,
+        patient_id -> Int4,
+        reason -> Text,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        reviewed_by -> Nullable<Int4>,
+        reviewed_at -> Nullable<Timestamp>,
+        review_notes -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    erasures (id) {
+        id -> Int4,
+        user_id -> Int4,
+        hospital_id -> Int4,
+        #[max_length = 32]
+        legal_basis -> Varchar,
+        notes -> Text,
+        erased_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    guardianships (id) {
+        id -> Int4,
+        guardian_id -> Int4,
+        dependent_id -> Int4,
+        #[max_length = 20]
+        relationship -> Varchar,
+        consent_evidence -> Jsonb,
+        valid_until -> Nullable<Timestamp>,
+        created_by -> Int4,
+        created_at -> Timestamp,
+        revoked_by -> Nullable<Int4>,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    hospital_memberships (hospital_id, user_id) {
+        hospital_id -> Int4,
+        user_id -> Int4,
+        #[max_length = 32]
+        hospital_number -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    hospital_number_sequences (hospital_id, year_code) {
+        hospital_id -> Int4,
+        year_code -> Int2,
+        last_value -> Int4,
+    }
+}
+
+diesel::table! {
+    hospitals (id) {
         id -> Int4,
         #[max_length = 32]
-        citizen_id -> Varchar,
+        code -> Varchar,
+        #[max_length = 200]
+        name -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        #[max_length = 8]
+        hn_prefix -> Varchar,
+        hn_sequence_digits -> Int2,
+        hn_buddhist_year -> Bool,
+    }
+}
+
+diesel::table! {
+    idempotency_keys (id) {
+        id -> Int4,
+        #[max_length = 255]
+        idempotency_key -> Varchar,
+        #[max_length = 255]
+        scope -> Varchar,
+        #[max_length = 64]
+        fingerprint -> Varchar,
+        status_code -> Nullable<Int4>,
+        #[max_length = 255]
+        content_type -> Nullable<Varchar>,
+        response_body -> Nullable<Bytea>,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Int4,
+        recipient_id -> Int4,
+        #[max_length = 100]
+        kind -> Varchar,
+        subject_user_id -> Nullable<Int4>,
+        payload -> Jsonb,
+        created_at -> Timestamp,
+        read_at -> Nullable<Timestamp>,
+        #[max_length = 8]
+        priority -> Varchar,
+    }
+}
+
+diesel::table! {
+    oauth_authorization_codes (id) {
+        id -> Int4,
+        #[max_length = 64]
+        code -> Varchar,
+        #[max_length = 64]
+        client_id -> Varchar,
+        user_id -> Int4,
+        #[max_length = 255]
+        redirect_uri -> Varchar,
+        scopes -> Array<Varchar>,
+        #[max_length = 128]
+        code_challenge -> Varchar,
+        #[max_length = 16]
+        code_challenge_method -> Varchar,
+        #[max_length = 255]
+        nonce -> Nullable<Varchar>,
+        expires_at -> Timestamp,
+        consumed_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    oauth_clients (id) {
+        id -> Int4,
+        #[max_length = 64]
+        client_id -> Varchar,
+        #[max_length = 255]
+        client_secret -> Nullable<Varchar>,
+        #[max_length = 100]
+        name -> Varchar,
+        redirect_uris -> Array<Varchar>,
+        allowed_scopes -> Array<Varchar>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        deleted_at -> Nullable<Timestamp>,
+        hospital_id -> Int4,
+    }
+}
+
+diesel::table! {
+    oauth_consents (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 64]
+        client_id -> Varchar,
+        scopes -> Array<Varchar>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    patient_profiles (user_id) {
+        user_id -> Int4,
+        date_of_birth -> Date,
+        #[max_length = 16]
+        sex -> Varchar,
+        #[max_length = 8]
+        blood_group -> Nullable<Varchar>,
+        allergies -> Array<Text>,
+        chronic_conditions -> Array<Text>,
+        address -> Nullable<Jsonb>,
+        emergency_contacts -> Jsonb,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    permissions (id) {
+        id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    role_permissions (role_id, permission_id) {
+        role_id -> Int4,
+        permission_id -> Int4,
+    }
+}
+
+diesel::table! {
+    roles (id) {
+        id -> Int4,
+        #[max_length = 50]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    service_accounts (id) {
+        id -> Int4,
+        #[max_length = 64]
+        client_id -> Varchar,
+        #[max_length = 255]
+        client_secret -> Varchar,
+        #[max_length = 100]
+        name -> Varchar,
+        scopes -> Array<Varchar>,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        deleted_at -> Nullable<Timestamp>,
+        hospital_id -> Int4,
+    }
+}
+
+diesel::table! {
+    sessions (id) {
+        #[max_length = 64]
+        id -> Varchar,
+        user_id -> Int4,
+        #[max_length = 64]
+        client_id -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    user_roles (hospital_id, user_id, role_id) {
+        user_id -> Int4,
+        role_id -> Int4,
+        granted_by -> Nullable<Int4>,
+        granted_at -> Timestamp,
+        valid_until -> Nullable<Timestamp>,
+        valid_from -> Nullable<Timestamp>,
+        expiry_notified_at -> Nullable<Timestamp>,
+        expired_at -> Nullable<Timestamp>,
+        hospital_id -> Int4,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        citizen_id -> Text,
         #[max_length = 100]
         first_name -> Varchar,
         #[max_length = 100]
         last_name -> Varchar,
-        #[max_length = 32]
-        phone_number -> Varchar,
+        phone_number -> Text,
         #[max_length = 255]
         password -> Varchar,
-        role -> Array<Varchar>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
         deleted_at -> Nullable<Timestamp>,
+        #[max_length = 64]
+        citizen_id_hash -> Nullable<Varchar>,
+        #[max_length = 64]
+        phone_number_hash -> Nullable<Varchar>,
+        citizen_id_prefix_hashes -> Array<Varchar>,
+        name_romanized -> Text,
     }
 }
+
+diesel::joinable!(api_keys -> hospitals (hospital_id));
+diesel::joinable!(api_keys -> users (created_by));
+diesel::joinable!(care_assignments -> hospitals (hospital_id));
+diesel::joinable!(consent_records -> consent_versions (consent_version_id));
+diesel::joinable!(consent_versions -> consent_purposes (purpose_id));
+diesel::joinable!(consent_versions -> users (created_by));
+diesel::joinable!(data_exports -> users (user_id));
+diesel::joinable!(doctor_application_transitions -> doctor_applications (application_id));
+diesel::joinable!(doctor_application_transitions -> users (actor_id));
+diesel::joinable!(doctor_applications -> hospitals (hospital_id));
+diesel::joinable!(emergency_accesses -> hospitals (hospital_id));
+diesel::joinable!(erasures -> hospitals (hospital_id));
+diesel::joinable!(hospital_memberships -> hospitals (hospital_id));
+diesel::joinable!(hospital_memberships -> users (user_id));
+diesel::joinable!(hospital_number_sequences -> hospitals (hospital_id));
+diesel::joinable!(notifications -> users (recipient_id));
+diesel::joinable!(oauth_authorization_codes -> users (user_id));
+diesel::joinable!(oauth_clients -> hospitals (hospital_id));
+diesel::joinable!(oauth_consents -> users (user_id));
+diesel::joinable!(patient_profiles -> users (user_id));
+diesel::joinable!(role_permissions -> permissions (permission_id));
+diesel::joinable!(role_permissions -> roles (role_id));
+diesel::joinable!(service_accounts -> hospitals (hospital_id));
+diesel::joinable!(service_accounts -> users (created_by));
+diesel::joinable!(sessions -> users (user_id));
+diesel::joinable!(user_roles -> hospitals (hospital_id));
+diesel::joinable!(user_roles -> roles (role_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    api_keys,
+    audit_logs,
+    care_assignments,
+    consent_purposes,
+    consent_records,
+    consent_versions,
+    data_exports,
+    doctor_application_transitions,
+    doctor_applications,
+    doctor_profiles,
+    emergency_accesses,
+    erasures,
+    guardianships,
+    hospital_memberships,
+    hospital_number_sequences,
+    hospitals,
+    idempotency_keys,
+    notifications,
+    oauth_authorization_codes,
+    oauth_clients,
+    oauth_consents,
+    patient_profiles,
+    permissions,
+    role_permissions,
+    roles,
+    service_accounts,
+    sessions,
+    user_roles,
+    users,
+);
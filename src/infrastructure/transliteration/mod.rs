@@ -0,0 +1,342 @@
+//! Rough Thai → Latin romanization (RTGS-like) for fuzzy name matching.
+//!
+//! It does not split syllables the way a dictionary-based romanizer would; it only has to put
+//! `สมชาย` and `Somchai` close enough for trigram similarity.
+
+fn is_thai_consonant(c: char) -> bool {
+    ('\u{0E01}'..='\u{0E2E}').contains(&c)
+}
+
+/// Vowels written before the consonant they follow in speech.
+fn is_leading_vowel(c: char) -> bool {
+    matches!(c, 'เ' | 'แ' | 'โ' | 'ใ' | 'ไ')
+}
+
+/// Vowels written after, above or below the consonant.
+fn is_following_vowel(c: char) -> bool {
+    matches!(
+        c,
+        'ะ' | 'ั' | 'า' | 'ำ' | 'ิ' | 'ี' | 'ึ' | 'ื' | 'ุ' | 'ู' | 'ๅ' | 'ฤ' | 'ฦ'
+    )
+}
+
+/// Tone marks and other signs that are not pronounced on their own.
+fn is_silent_mark(c: char) -> bool {
+    matches!(c, '่' | '้' | '๊' | '๋' | '็' | 'ํ' | 'ฺ' | 'ๆ' | 'ฯ')
+}
+
+fn initial(c: char) -> &'static str {
+    match c {
+        'ก' => "k",
+        'ข' | 'ฃ' | 'ค' | 'ฅ' | 'ฆ' => "kh",
+        'ง' => "ng",
+        'จ' | 'ฉ' | 'ช' | 'ฌ' => "ch",
+        'ซ' | 'ศ' | 'ษ' | 'ส' => "s",
+        'ญ' | 'ย' => "y",
+        'ฎ' | 'ด' => "d",
+        'ฏ' | 'ต' => "t",
+        'ฐ' | 'ฑ' | 'ฒ' | 'ถ' | 'ท' | 'ธ' => "th",
+        'ณ' | 'น' => "n",
+        'บ' => "b",
+        'ป' => "p",
+        'ผ' | 'พ' | 'ภ' => "ph",
+        'ฝ' | 'ฟ' => "f",
+        'ม' => "m",
+        'ร' => "r",
+        'ล' | 'ฬ' => "l",
+        'ว' => "w",
+        'ห' | 'ฮ' => "h",
+        _ => "",
+    }
+}
+
+fn final_sound(c: char) -> &'static str {
+    match c {
+        'ก' | 'ข' | 'ค' | 'ฆ' => "k",
+        'ง' => "ng",
+        'ญ' | 'ณ' | 'น' | 'ร' | 'ล' | 'ฬ' => "n",
+        'บ' | 'ป' | 'พ' | 'ฟ' | 'ภ' => "p",
+        'ม' => "m",
+        'ย' => "i",
+        'ว' => "o",
+        'อ' => "o",
+        _ => "t",
+    }
+}
+
+fn vowel(c: char) -> &'static str {
+    match c {
+        'ะ' | 'ั' | 'า' | 'ๅ' => "a",
+        'ำ' => "am",
+        'ิ' | 'ี' => "i",
+        'ึ' | 'ื' => "ue",
+        'ุ' | 'ู' => "u",
+        'ฤ' => "rue",
+        'ฦ' => "lue",
+        'เ' => "e",
+        'แ' => "ae",
+        'โ' => "o",
+        'ใ' | 'ไ' => "ai",
+        _ => "",
+    }
+}
+
+/// Second consonant of a true cluster such as `กร`, `ปล`, `คว`.
+fn is_cluster(first: char, second: char) -> bool {
+    matches!(second, 'ร' | 'ล' | 'ว') && matches!(first, 'ก' | 'ข' | 'ค' | 'ต' | 'ป' | 'ผ' | 'พ')
+}
+
+#[derive(Default)]
+struct Romanizer {
+    output: String,
+    leading_vowel: Option<char>,
+    in_syllable: bool,
+    has_vowel: bool,
+    last_initial: Option<char>,
+    word_start: usize,
+}
+
+impl Romanizer {
+    /// An initial left without a vowel was really a final with an implicit `o`
+    /// (`กมล` → `komon`, `ธนากร` → `thanakon`).
+    fn close_syllable(&mut self) {
+        let unvoweled_initial = self
+            .last_initial
+            .filter(|_| self.in_syllable && !self.has_vowel);
+        if let Some(c) = unvoweled_initial {
+            let initial = initial(c);
+            let start = self.output.len().saturating_sub(initial.len());
+            if !initial.is_empty() && start > self.word_start && self.output.ends_with(initial) {
+                self.output.truncate(start);
+                self.output.push('o');
+                self.output.push_str(final_sound(c));
+            }
+        }
+
+        self.in_syllable = false;
+        self.has_vowel = false;
+    }
+
+    fn consonant(&mut self, chars: &[char], i: usize) {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let next_is_vowel = next.is_some_and(is_following_vowel);
+
+        // พยัญชนะที่ตามด้วยสระเริ่มพยางค์ใหม่เสมอ
+        if !self.in_syllable || next_is_vowel {
+            if self.in_syllable
+                && !self.has_vowel
+                && !self.last_initial.is_some_and(|first| is_cluster(first, c))
+            {
+                // สระอะลดรูป เช่น ธนา
+                self.output.push('a');
+            }
+
+            self.output.push_str(initial(c));
+            self.last_initial = Some(c);
+            self.has_vowel = false;
+
+            if let Some(v) = self.leading_vowel.take() {
+                // เ/แ/โ + พยัญชนะควบ เช่น เพลง: ใส่สระหลังตัวควบ
+                if next.is_some_and(|n| matches!(n, 'ร' | 'ล' | 'ว'))
+                    && chars.get(i + 2).is_some_and(|n| is_thai_consonant(*n))
+                {
+                    self.leading_vowel = Some(v);
+                } else {
+                    self.output.push_str(vowel(v));
+                    self.has_vowel = true;
+                }
+            }
+
+            self.in_syllable = true;
+            return;
+        }
+
+        // อ หลังพยัญชนะต้นที่ยังไม่มีสระคือสระออ เช่น ทอง
+        if c == 'อ' && !self.has_vowel && self.leading_vowel.is_none() {
+            self.output.push('o');
+            self.has_vowel = true;
+            return;
+        }
+
+        if !self.has_vowel {
+            if let Some(v) = self.leading_vowel.take() {
+                self.output.push_str(initial(c));
+                self.output.push_str(vowel(v));
+                self.has_vowel = true;
+                return;
+            }
+
+            // ไม่มีรูปสระ = สระโอะลดรูป เช่น คน
+            self.output.push('o');
+        }
+
+        self.output.push_str(final_sound(c));
+        self.in_syllable = false;
+        self.has_vowel = false;
+    }
+}
+
+/// Marks what a thanthakhat (`์`) silences: the consonant under it with any vowel written on
+/// that consonant (`ศักดิ์`), and the consonant before a silenced `ร` (`จันทร์`).
+fn silenced(chars: &[char]) -> Vec<bool> {
+    let mut silent = vec![false; chars.len()];
+
+    for (i, c) in chars.iter().enumerate() {
+        if *c != '์' {
+            continue;
+        }
+        silent[i] = true;
+
+        let mut j = i;
+        while j > 0 && matches!(chars[j - 1], 'ิ' | 'ุ') {
+            j -= 1;
+            silent[j] = true;
+        }
+        if j == 0 || !is_thai_consonant(chars[j - 1]) {
+            continue;
+        }
+        j -= 1;
+        silent[j] = true;
+
+        // ทร์ ตร์ ไม่ออกเสียงทั้งคู่ แต่ อร์ เช่น เบอร์ ยังออกเสียง อ
+        if chars[j] == 'ร' && j > 0 && is_thai_consonant(chars[j - 1]) && chars[j - 1] != 'อ' {
+            silent[j - 1] = true;
+        }
+    }
+
+    silent
+}
+
+/// Lower-case romanization. Latin letters and digits pass through lower-cased; anything else
+/// becomes a single space.
+pub fn romanize(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let silent = silenced(&chars);
+    let mut romanizer = Romanizer::default();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if silent[i] {
+            i += 1;
+            continue;
+        }
+
+        if is_thai_consonant(c) {
+            if c == 'ร' && next == Some('ร') {
+                // ร หัน: รร = อัน
+                romanizer.output.push('a');
+                romanizer.in_syllable = true;
+                romanizer.has_vowel = true;
+                i += 2;
+                if !chars.get(i).is_some_and(|n| is_thai_consonant(*n)) {
+                    romanizer.output.push('n');
+                    romanizer.in_syllable = false;
+                    romanizer.has_vowel = false;
+                }
+                continue;
+            }
+
+            romanizer.consonant(&chars, i);
+        } else if is_leading_vowel(c) {
+            romanizer.close_syllable();
+            romanizer.leading_vowel = Some(c);
+        } else if is_following_vowel(c) {
+            romanizer.output.push_str(vowel(c));
+            romanizer.has_vowel = true;
+            if c == 'ำ' || c == 'ะ' {
+                romanizer.in_syllable = false;
+                romanizer.has_vowel = false;
+            }
+        } else if is_silent_mark(c) {
+            // ไม่ออกเสียง
+        } else {
+            romanizer.close_syllable();
+            if c.is_ascii_alphanumeric() {
+                romanizer.output.push(c.to_ascii_lowercase());
+            } else if !romanizer.output.ends_with(' ') {
+                romanizer.output.push(' ');
+            }
+            romanizer.last_initial = None;
+            romanizer.word_start = romanizer.output.len();
+        }
+
+        i += 1;
+    }
+
+    romanizer.close_syllable();
+    romanizer.output.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn romanizes_common_names() {
+        assert_eq!(romanize("สมชาย"), "somchai");
+        assert_eq!(romanize("วิไล"), "wilai");
+        assert_eq!(romanize("แก้ว"), "kaeo");
+    }
+
+    #[test]
+    fn unwritten_o_between_consonants() {
+        assert_eq!(romanize("กมล"), "komon");
+        assert_eq!(romanize("คน"), "khon");
+        assert_eq!(romanize("สมพร"), "somphon");
+    }
+
+    #[test]
+    fn unwritten_a_before_a_vowel_syllable() {
+        assert_eq!(romanize("ธนากร"), "thanakon");
+        assert_eq!(romanize("นภา"), "napha");
+    }
+
+    #[test]
+    fn leading_vowel_before_a_cluster() {
+        assert_eq!(romanize("เพลง"), "phleng");
+    }
+
+    #[test]
+    fn o_ang_after_an_initial_is_a_vowel() {
+        assert_eq!(romanize("ทอง"), "thong");
+        assert_eq!(romanize("ก้อย"), "koi");
+    }
+
+    #[test]
+    fn ro_han_reads_as_an() {
+        assert_eq!(romanize("กรรม"), "kam");
+        assert_eq!(romanize("สุพรรณ"), "supan");
+    }
+
+    #[test]
+    fn karan_silences_the_marked_consonant() {
+        assert_eq!(romanize("สุริยันต์"), "suriyan");
+        assert_eq!(romanize("การ์ด"), "kat");
+        // สระบนตัวที่ถูกการันต์ก็ไม่ออกเสียง
+        assert_eq!(romanize("ศักดิ์"), "sak");
+        // ทร์ ไม่ออกเสียงทั้งคู่
+        assert_eq!(romanize("จันทร์"), "chan");
+    }
+
+    #[test]
+    fn latin_passes_through_lower_cased() {
+        assert_eq!(romanize("Somchai"), "somchai");
+        assert_eq!(romanize("John Smith 2"), "john smith 2");
+    }
+
+    #[test]
+    fn mixed_latin_and_thai() {
+        assert_eq!(romanize("Somchai ใจดี"), "somchai chaidi");
+        assert_eq!(romanize("สมชาย Jaidee"), "somchai jaidee");
+    }
+
+    #[test]
+    fn punctuation_becomes_a_single_space() {
+        assert_eq!(romanize("สมชาย  -  ใจดี."), "somchai chaidi");
+        assert_eq!(romanize(""), "");
+    }
+}
@@ -0,0 +1,17 @@
+use std::io::{Cursor, Write};
+
+use anyhow::Result;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+/// A deflated zip archive holding one file.
+pub fn single_file(name: &str, content: &[u8]) -> Result<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+    writer.start_file(
+        name,
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+    )?;
+    writer.write_all(content)?;
+
+    Ok(writer.finish()?.into_inner())
+}
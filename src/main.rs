@@ -4,7 +4,8 @@ use medbook_userservice::{
     config::config_loader,
     infrastructure::{
         axum_http::http_serve::start,
-        postgres::{postgres_connection, postgres_migration},
+        background_tasks::{data_exports, role_grant_expiry},
+        postgres::{name_romanization, pii_encryption, postgres_connection, postgres_migration},
     },
 };
 use tracing::{error, info};
@@ -43,6 +44,48 @@ async fn main() {
 
     info!("Database migrations have been applied successfully");
 
+    // เข้ารหัสแถวที่บันทึกไว้ก่อนเปิดใช้ field encryption ก่อนรับ request
+    // แถวที่ยังไม่มี blind index จะ login ด้วยเลขบัตรไม่ได้และกันการสมัครซ้ำไม่ได้
+    match pii_encryption::encrypt_existing_users(&postgres_pool).await {
+        Ok(encrypted) => info!("Encrypted {} existing users", encrypted),
+        Err(e) => {
+            error!("Failed to encrypt existing users: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // เติม name_romanized ให้แถวที่สมัครก่อนมีการค้นหาชื่อแบบ fuzzy ก่อนรับ request
+    match name_romanization::romanize_existing_users(&postgres_pool).await {
+        Ok(romanized) => info!("Romanized names of {} existing users", romanized),
+        Err(e) => {
+            error!("Failed to romanize existing user names: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    let role_grant_expiry_config = match config_loader::get_role_grant_expiry_env() {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Failed to load role grant expiry config: {}", e);
+            std::process::exit(1);
+        }
+    };
+    // หมดอายุ role grant ตาม valid_until และแจ้ง admin ล่วงหน้า
+    tokio::spawn(role_grant_expiry::run(
+        postgres_pool.clone(),
+        role_grant_expiry_config,
+    ));
+
+    let data_export_config = match config_loader::get_data_export_env() {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Failed to load data export config: {}", e);
+            std::process::exit(1);
+        }
+    };
+    // สร้าง export ที่เข้าคิวไว้ และลบ export ที่หมดอายุ
+    tokio::spawn(data_exports::run(postgres_pool.clone(), data_export_config));
+
     start(Arc::new(dotenvy_env), postgres_pool)
         .await
         .expect("Failed to start server")